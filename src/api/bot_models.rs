@@ -147,3 +147,529 @@ impl ListBotsRequest {
         params.join("&")
     }
 }
+
+/// Bot 人设与回复逻辑
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotPromptInfo {
+    /// 人设与回复逻辑提示词
+    #[serde(default)]
+    pub prompt: String,
+}
+
+/// Bot 开场白配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotOnboardingInfo {
+    /// 开场白
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prologue: Option<String>,
+    /// 开场白预置问题
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggested_questions: Vec<String>,
+}
+
+/// Bot 模型配置；查询时对应 `model_info`，创建 / 更新时对应 `model_info_config`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotModelConfig {
+    /// 模型 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    /// 模型名称（仅查询时返回）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_name: Option<String>,
+    /// 生成随机性
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Top P
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// 最大回复长度
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// 携带上下文轮数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context_round: Option<u32>,
+    /// 输出格式：text / markdown / json
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<String>,
+    /// 其余模型参数（如 frequency_penalty、cache_type），原样透传
+    #[serde(flatten, default)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Bot 插件中的单个工具
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotPluginApiInfo {
+    /// 工具 ID
+    pub api_id: String,
+    /// 工具名称
+    #[serde(default)]
+    pub name: String,
+    /// 工具描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Bot 绑定的插件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotPluginInfo {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 插件名称
+    #[serde(default)]
+    pub name: String,
+    /// 插件描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 插件图标 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// 插件中启用的工具
+    #[serde(default)]
+    pub api_info_list: Vec<BotPluginApiInfo>,
+}
+
+/// Bot 绑定的工作流
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotWorkflowInfo {
+    /// 工作流 ID
+    pub id: String,
+    /// 工作流名称
+    #[serde(default)]
+    pub name: String,
+    /// 工作流描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 工作流图标 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+}
+
+/// Bot 知识库配置
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotKnowledge {
+    /// 绑定的知识库 ID
+    #[serde(default)]
+    pub dataset_ids: Vec<String>,
+    /// 是否自动调用知识库（false 时需在提示词中按需调用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_call: Option<bool>,
+    /// 检索策略：0 语义检索、1 混合检索、20 全文检索
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_strategy: Option<i32>,
+}
+
+/// Bot 用户变量
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotVariable {
+    /// 变量名
+    pub keyword: String,
+    /// 默认值
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_value: Option<String>,
+    /// 变量描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 是否启用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable: Option<bool>,
+    /// 其余字段（如 channel、prompt_enable），原样透传
+    #[serde(flatten, default)]
+    pub extra: std::collections::HashMap<String, serde_json::Value>,
+}
+
+/// Bot 详细配置（已发布的线上版本）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotDetail {
+    /// Bot ID
+    pub bot_id: String,
+    /// Bot 名称
+    #[serde(default)]
+    pub name: String,
+    /// Bot 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Bot 图标 URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// 创建时间戳
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub create_time: Option<u64>,
+    /// 更新时间戳
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub update_time: Option<u64>,
+    /// 线上版本号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Bot 模式：0 单 Agent、1 多 Agent
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_mode: Option<i32>,
+    /// 人设与回复逻辑
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_info: Option<BotPromptInfo>,
+    /// 开场白
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onboarding_info: Option<BotOnboardingInfo>,
+    /// 模型配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_info: Option<BotModelConfig>,
+    /// 插件列表
+    #[serde(default)]
+    pub plugin_info_list: Vec<BotPluginInfo>,
+    /// 工作流列表
+    #[serde(default)]
+    pub workflow_info_list: Vec<BotWorkflowInfo>,
+    /// 知识库配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<BotKnowledge>,
+    /// 用户变量
+    #[serde(default)]
+    pub variables: Vec<BotVariable>,
+}
+
+/// 创建 / 更新 Bot 时绑定的插件工具
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BotPluginIdInfo {
+    /// 插件 ID
+    pub plugin_id: String,
+    /// 插件中的工具 ID
+    pub api_id: String,
+}
+
+/// 插件工具列表
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotPluginIdList {
+    pub id_list: Vec<BotPluginIdInfo>,
+}
+
+/// 创建 / 更新 Bot 时绑定的工作流
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotWorkflowIdInfo {
+    /// 工作流 ID
+    pub id: String,
+}
+
+/// 工作流列表
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotWorkflowIdList {
+    pub ids: Vec<BotWorkflowIdInfo>,
+}
+
+/// 创建与更新 Bot 共用的可选配置；未设置的字段不提交，更新时保持原值
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BotConfig {
+    /// Bot 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 图标文件 ID（通过文件上传接口获取）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon_file_id: Option<String>,
+    /// 人设与回复逻辑
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_info: Option<BotPromptInfo>,
+    /// 开场白
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onboarding_info: Option<BotOnboardingInfo>,
+    /// 插件工具
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plugin_id_list: Option<BotPluginIdList>,
+    /// 工作流
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_id_list: Option<BotWorkflowIdList>,
+    /// 模型配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_info_config: Option<BotModelConfig>,
+}
+
+impl BotConfig {
+    /// 是否未设置任何字段
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.icon_file_id.is_none()
+            && self.prompt_info.is_none()
+            && self.onboarding_info.is_none()
+            && self.plugin_id_list.is_none()
+            && self.workflow_id_list.is_none()
+            && self.model_info_config.is_none()
+    }
+}
+
+/// 创建 Bot 请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBotRequest {
+    /// 工作空间 ID
+    pub space_id: String,
+    /// Bot 名称
+    pub name: String,
+    /// 可选配置
+    #[serde(flatten)]
+    pub config: BotConfig,
+}
+
+impl CreateBotRequest {
+    /// 创建新的 Bot 创建请求
+    pub fn new(space_id: String, name: String) -> Self {
+        Self {
+            space_id,
+            name,
+            config: BotConfig::default(),
+        }
+    }
+
+    /// 设置可选配置
+    pub fn with_config(mut self, config: BotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 设置人设与回复逻辑
+    pub fn with_prompt(mut self, prompt: String) -> Self {
+        self.config.prompt_info = Some(BotPromptInfo { prompt });
+        self
+    }
+}
+
+/// 创建 Bot 响应数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateBotData {
+    /// 新 Bot 的 ID
+    pub bot_id: String,
+}
+
+/// 更新 Bot 请求；更新的是草稿，需发布后才对线上生效
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBotRequest {
+    /// Bot ID
+    pub bot_id: String,
+    /// 新名称
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 可选配置
+    #[serde(flatten)]
+    pub config: BotConfig,
+    /// 知识库配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<BotKnowledge>,
+}
+
+impl UpdateBotRequest {
+    /// 创建新的 Bot 更新请求
+    pub fn new(bot_id: String) -> Self {
+        Self {
+            bot_id,
+            name: None,
+            config: BotConfig::default(),
+            knowledge: None,
+        }
+    }
+
+    /// 设置新名称
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// 设置可选配置
+    pub fn with_config(mut self, config: BotConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// 设置人设与回复逻辑
+    pub fn with_prompt(mut self, prompt: String) -> Self {
+        self.config.prompt_info = Some(BotPromptInfo { prompt });
+        self
+    }
+
+    /// 设置知识库配置
+    pub fn with_knowledge(mut self, knowledge: BotKnowledge) -> Self {
+        self.knowledge = Some(knowledge);
+        self
+    }
+
+    /// 是否没有任何需要更新的字段
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.config.is_empty() && self.knowledge.is_none()
+    }
+}
+
+/// API 渠道 ID，发布 Bot 的默认渠道
+pub const API_CONNECTOR_ID: &str = "1024";
+
+/// 发布 Bot 请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishBotRequest {
+    /// Bot ID
+    pub bot_id: String,
+    /// 发布渠道 ID（默认 1024 为 API 渠道）
+    pub connector_ids: Vec<String>,
+}
+
+impl PublishBotRequest {
+    /// 创建新的发布请求，默认发布到 API 渠道
+    pub fn new(bot_id: String) -> Self {
+        Self {
+            bot_id,
+            connector_ids: vec![API_CONNECTOR_ID.to_string()],
+        }
+    }
+
+    /// 设置发布渠道
+    pub fn with_connector_ids(mut self, connector_ids: Vec<String>) -> Self {
+        self.connector_ids = connector_ids;
+        self
+    }
+}
+
+/// 发布 Bot 响应数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishBotData {
+    /// Bot ID
+    pub bot_id: String,
+    /// 发布后的版本号
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Bot 配置文件格式版本
+pub const BOT_CONFIG_FILE_VERSION: u32 = 1;
+
+/// 可纳入版本管理的 Bot 配置文件
+///
+/// 只包含可迁移的配置：不含 bot_id、版本号、时间戳等随环境变化的字段，
+/// 列表按 ID 排序，同一配置导出的内容逐字节一致。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotConfigFile {
+    /// 文件格式版本
+    pub format_version: u32,
+    /// Bot 名称
+    pub name: String,
+    /// Bot 描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 人设与回复逻辑
+    #[serde(default)]
+    pub prompt: String,
+    /// 开场白
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub onboarding_info: Option<BotOnboardingInfo>,
+    /// 模型配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<BotModelConfig>,
+    /// 插件工具
+    #[serde(default)]
+    pub plugins: Vec<BotPluginIdInfo>,
+    /// 工作流 ID
+    #[serde(default)]
+    pub workflow_ids: Vec<String>,
+    /// 知识库配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<BotKnowledge>,
+    /// 用户变量（Coze 创建 / 更新接口不支持设置，导入时跳过）
+    #[serde(default)]
+    pub variables: Vec<BotVariable>,
+}
+
+impl BotConfigFile {
+    /// 从 Bot 详细配置生成配置文件内容
+    pub fn from_detail(detail: &BotDetail) -> Self {
+        let model = detail.model_info.clone().map(|mut model| {
+            // model_name 只读，创建 / 更新时不提交
+            model.model_name = None;
+            model
+        });
+        let mut plugins: Vec<BotPluginIdInfo> = detail
+            .plugin_info_list
+            .iter()
+            .flat_map(|plugin| {
+                plugin.api_info_list.iter().map(|api| BotPluginIdInfo {
+                    plugin_id: plugin.plugin_id.clone(),
+                    api_id: api.api_id.clone(),
+                })
+            })
+            .collect();
+        plugins.sort();
+        let mut workflow_ids: Vec<String> = detail
+            .workflow_info_list
+            .iter()
+            .map(|w| w.id.clone())
+            .collect();
+        workflow_ids.sort();
+        let knowledge = detail.knowledge.clone().map(|mut knowledge| {
+            knowledge.dataset_ids.sort();
+            knowledge
+        });
+        let mut variables = detail.variables.clone();
+        variables.sort_by(|a, b| a.keyword.cmp(&b.keyword));
+
+        Self {
+            format_version: BOT_CONFIG_FILE_VERSION,
+            name: detail.name.clone(),
+            description: detail.description.clone(),
+            prompt: detail
+                .prompt_info
+                .as_ref()
+                .map(|p| p.prompt.clone())
+                .unwrap_or_default(),
+            onboarding_info: detail.onboarding_info.clone(),
+            model,
+            plugins,
+            workflow_ids,
+            knowledge,
+            variables,
+        }
+    }
+
+    /// 解析配置文件内容并校验格式版本
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let file: Self =
+            serde_json::from_str(content).map_err(|e| format!("Invalid bot config file: {e}"))?;
+        if file.format_version != BOT_CONFIG_FILE_VERSION {
+            return Err(format!(
+                "Unsupported bot config format_version {} (expected {BOT_CONFIG_FILE_VERSION})",
+                file.format_version
+            ));
+        }
+        if file.name.is_empty() {
+            return Err("Bot config file is missing name".to_string());
+        }
+        Ok(file)
+    }
+
+    /// 序列化为确定性的 JSON：键按字母序、两空格缩进、末尾换行
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        // 先转为 Value（对象键有序），避免 HashMap 字段的遍历顺序影响输出
+        let value = serde_json::to_value(self)?;
+        Ok(serde_json::to_string_pretty(&value)? + "\n")
+    }
+
+    /// 创建 / 更新共用的配置；插件与工作流总是提交，使 Bot 与文件保持一致
+    pub fn to_bot_config(&self) -> BotConfig {
+        BotConfig {
+            description: self.description.clone(),
+            icon_file_id: None,
+            prompt_info: Some(BotPromptInfo {
+                prompt: self.prompt.clone(),
+            }),
+            onboarding_info: self.onboarding_info.clone(),
+            plugin_id_list: Some(BotPluginIdList {
+                id_list: self.plugins.clone(),
+            }),
+            workflow_id_list: Some(BotWorkflowIdList {
+                ids: self
+                    .workflow_ids
+                    .iter()
+                    .map(|id| BotWorkflowIdInfo { id: id.clone() })
+                    .collect(),
+            }),
+            model_info_config: self.model.clone(),
+        }
+    }
+}
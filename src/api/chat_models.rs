@@ -18,15 +18,44 @@ pub enum ContentType {
     Image,
     File,
     Audio,
+    /// 多模态消息：content 为 `ContentPart` 数组的 JSON 字符串
+    #[serde(rename = "object_string")]
+    ObjectString,
+    /// 卡片消息（仅出现在 Bot 回复中）
+    Card,
+}
+
+/// 多模态消息的内容片段；图片 / 文件通过已上传的 file_id 或公网 URL 引用
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ContentPart {
+    Text {
+        text: String,
+    },
+    Image {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_url: Option<String>,
+    },
+    File {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        file_url: Option<String>,
+    },
 }
 
 /// 消息元数据
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct MessageMetaData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mention_info: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_info: Option<serde_json::Value>,
+    /// 自定义键值对（消息 API 的 meta_data 为任意字符串键值）
+    #[serde(flatten, default)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// 聊天消息
@@ -94,17 +123,47 @@ pub struct ChatResponse {
     pub usage: Option<ChatUsage>,
 }
 
-/// 流式聊天事件类型
+/// 端插件执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolOutput {
+    /// 对应 `required_action.submit_tool_outputs.tool_calls[].id`
+    pub tool_call_id: String,
+    pub output: String,
+}
+
+/// 提交端插件执行结果请求；conversation_id / chat_id 以查询参数传递
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitToolOutputsRequest {
+    #[serde(skip_serializing)]
+    pub conversation_id: String,
+    #[serde(skip_serializing)]
+    pub chat_id: String,
+    pub tool_outputs: Vec<ToolOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+/// 流式聊天事件类型（同时接受 SSE `event:` 行中的点分名称）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum StreamEventType {
+    #[serde(alias = "conversation.chat.created")]
+    ConversationChatCreated,
+    #[serde(alias = "conversation.message.delta")]
     ConversationMessageDelta,
+    #[serde(alias = "conversation.chat.completed")]
     ConversationChatCompleted,
+    #[serde(alias = "conversation.chat.in_progress")]
     ConversationChatInProgress,
+    #[serde(alias = "conversation.chat.failed")]
     ConversationChatFailed,
+    #[serde(alias = "conversation.chat.requires_action")]
     ConversationChatRequiresAction,
     Done,
     Error,
+    /// 其他事件（如 conversation.message.completed）
+    #[serde(other)]
+    Other,
 }
 
 /// 流式聊天增量内容
@@ -126,6 +185,9 @@ pub struct StreamChatResponse {
     pub conversation_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<String>,
+    /// 所属对话 ID（消息类事件携带；对话类事件的 `id` 即为对话 ID）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -134,6 +196,9 @@ pub struct StreamChatResponse {
     pub usage: Option<ChatUsage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error: Option<serde_json::Value>,
+    /// requires_action 事件中待执行的端插件调用
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_action: Option<serde_json::Value>,
 }
 
 impl ChatMessage {
@@ -147,6 +212,61 @@ impl ChatMessage {
             meta_data: None,
         }
     }
+
+    /// 创建多模态（object_string）消息
+    pub fn object_string(role: MessageRole, parts: &[ContentPart]) -> Self {
+        Self {
+            role,
+            // 片段只含字符串字段，序列化不会失败
+            content: Some(serde_json::to_string(parts).unwrap_or_default()),
+            content_type: Some(ContentType::ObjectString),
+            object_string: None,
+            meta_data: None,
+        }
+    }
+}
+
+impl ContentPart {
+    pub fn text(text: impl Into<String>) -> Self {
+        ContentPart::Text { text: text.into() }
+    }
+
+    pub fn image_file(file_id: impl Into<String>) -> Self {
+        ContentPart::Image {
+            file_id: Some(file_id.into()),
+            file_url: None,
+        }
+    }
+
+    pub fn image_url(url: impl Into<String>) -> Self {
+        ContentPart::Image {
+            file_id: None,
+            file_url: Some(url.into()),
+        }
+    }
+
+    pub fn file(file_id: impl Into<String>) -> Self {
+        ContentPart::File {
+            file_id: Some(file_id.into()),
+            file_url: None,
+        }
+    }
+
+    /// 文本不能为空，图片 / 文件必须且只能指定 file_id 与 file_url 之一
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            ContentPart::Text { text } if text.is_empty() => Err("text part is empty".to_string()),
+            ContentPart::Text { .. } => Ok(()),
+            ContentPart::Image { file_id, file_url } | ContentPart::File { file_id, file_url } => {
+                match (file_id, file_url) {
+                    (Some(_), None) | (None, Some(_)) => Ok(()),
+                    _ => {
+                        Err("image/file part needs exactly one of file_id or file_url".to_string())
+                    }
+                }
+            }
+        }
+    }
 }
 
 impl ChatRequest {
@@ -177,8 +297,30 @@ impl ChatRequest {
         self
     }
 
+    /// 以多模态内容替换本轮的用户消息
+    pub fn with_content_parts(mut self, parts: &[ContentPart]) -> Self {
+        self.additional_messages = vec![ChatMessage::object_string(MessageRole::User, parts)];
+        self
+    }
+
     pub fn with_custom_variables(mut self, variables: HashMap<String, String>) -> Self {
         self.custom_variables = Some(variables);
         self
     }
 }
+
+impl SubmitToolOutputsRequest {
+    pub fn new(conversation_id: String, chat_id: String, tool_outputs: Vec<ToolOutput>) -> Self {
+        Self {
+            conversation_id,
+            chat_id,
+            tool_outputs,
+            stream: Some(false),
+        }
+    }
+
+    pub fn with_stream(mut self, stream: bool) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+}
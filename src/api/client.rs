@@ -1,7 +1,15 @@
+use crate::api::endpoints::endpoint_group;
 use crate::api::endpoints::KNOWLEDGE_DOCUMENT_CREATE_URL;
+use crate::api::envelope;
 use crate::api::error::{ApiError, ApiErrorData};
+use crate::api::pagination::{Page, PageCursor, PageToken, Paginator};
+use crate::api::rate_limit::RateLimiter;
+use crate::api::retry::{self, RetryMode};
+use crate::api::sse::{SseDecoder, SseEvent};
+use crate::utils::config::CozeConfig;
 // Chat completion models removed (unused)
 use reqwest::{Client, Response};
+use std::sync::Arc;
 use std::time::Duration;
 use urlencoding::encode;
 
@@ -10,12 +18,36 @@ pub struct CozeApiClient {
     client: Client,
     base_url: String,
     api_key: String,
+    retry_attempts: u32,
+    // 非流式请求的总超时；流式请求只受连接与读取空闲超时限制
+    timeout: Duration,
+    // 使用同一个 `RateLimiter` 的客户端（含克隆）共享限流状态
+    limiter: Arc<RateLimiter>,
 }
 
 impl CozeApiClient {
     pub fn new(base_url: String, api_key: String) -> Result<Self, ApiError> {
+        Self::from_config(&CozeConfig {
+            base_url,
+            api_token: api_key,
+            ..CozeConfig::default()
+        })
+    }
+
+    /// 按 `CozeConfig` 构建客户端：`timeout` 是非流式请求的总超时，对 SSE 流只作为连接与两次读取间的空闲超时，
+    /// `retry_attempts` 为瞬时失败的最大重试次数。
+    /// 限流器按 `rate_limits` 单独创建，只在本客户端及其克隆间生效
+    pub fn from_config(config: &CozeConfig) -> Result<Self, ApiError> {
+        Self::with_limiter(config, Arc::new(RateLimiter::new(&config.rate_limits)?))
+    }
+
+    /// 同 `from_config`，但使用外部传入的限流器，使多个客户端（如各会话各自的客户端）共享同一份限流预算
+    pub fn with_limiter(config: &CozeConfig, limiter: Arc<RateLimiter>) -> Result<Self, ApiError> {
+        let timeout = Duration::from_secs(config.timeout);
+        // 不设置总超时，否则长时间运行的工作流 / 对话流会在中途被切断
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .connect_timeout(timeout)
+            .read_timeout(timeout)
             .build()
             .map_err(|e| {
                 ApiError::ConfigError(ApiErrorData::new(
@@ -28,47 +60,116 @@ impl CozeApiClient {
 
         Ok(Self {
             client,
-            base_url,
-            api_key,
-            // Per-request timeout already configured in reqwest client; no extra field needed
+            base_url: config.base_url.clone(),
+            api_key: config.api_token.clone(),
+            retry_attempts: config.retry_attempts,
+            timeout,
+            limiter,
         })
     }
 
+    pub fn retry_attempts(&self) -> u32 {
+        self.retry_attempts
+    }
+
     async fn send_raw_request(
         &self,
         method: &str,
         url: &str,
         body: Option<serde_json::Value>,
     ) -> Result<Response, ApiError> {
-        let request = self
-            .client
-            .request(method.parse().unwrap(), url)
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            // Per official upload spec: include Agw-Js-Conv to preserve numeric precision (harmless elsewhere)
-            .header("Agw-Js-Conv", "str");
+        self.send_request(method, url, body, RetryMode::Auto).await
+    }
 
-        let request = match body {
-            Some(b) => request.json(&b),
-            None => request,
+    /// 发送请求：`retry` 允许时，对连接失败、超时、429、5xx 与包裹内的 4029 按指数退避重试至多 `retry_attempts` 次。
+    /// 最后一次仍失败时原样返回响应（或错误），由调用方按原有逻辑处理。
+    async fn send_request(
+        &self,
+        method: &str,
+        url: &str,
+        body: Option<serde_json::Value>,
+        retry: RetryMode,
+    ) -> Result<Response, ApiError> {
+        let method: reqwest::Method = method.parse().map_err(|_| {
+            ApiError::ConfigError(ApiErrorData::new(
+                "config",
+                format!("Invalid HTTP method: {method}"),
+                None,
+                None,
+            ))
+        })?;
+        let max_retries = if retry.allows(&method) {
+            self.retry_attempts
+        } else {
+            0
         };
 
-        let response = request.send().await?;
-        Ok(response)
+        let group = endpoint_group(url.strip_prefix(self.base_url.as_str()).unwrap_or(url));
+        let mut attempt = 0;
+        loop {
+            // 每次尝试都单独计入限流；退避等待期间不占用并发名额
+            let permit = self.limiter.acquire(group).await;
+            let request = self
+                .client
+                .request(method.clone(), url)
+                .timeout(self.timeout)
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .header("Content-Type", "application/json")
+                // Per official upload spec: include Agw-Js-Conv to preserve numeric precision (harmless elsewhere)
+                .header("Agw-Js-Conv", "str");
+
+            let request = match &body {
+                Some(b) => request.json(b),
+                None => request,
+            };
+
+            let delay = match request.send().await {
+                Ok(response)
+                    if attempt < max_retries && retry::is_retryable_status(response.status()) =>
+                {
+                    match retry::retry_after(response.headers()) {
+                        Some(wait) if wait > retry::MAX_RETRY_AFTER => return Ok(response),
+                        Some(wait) => wait,
+                        None => retry::backoff_delay(attempt),
+                    }
+                }
+                Ok(response) if attempt < max_retries && response.status().is_success() => {
+                    match envelope::peek_code(response).await? {
+                        (_, Some(retry::RATE_LIMITED_CODE)) => retry::backoff_delay(attempt),
+                        (response, _) => return Ok(response),
+                    }
+                }
+                Ok(response) => return Ok(response),
+                Err(e) if e.is_builder() => return Err(e.into()),
+                Err(e) => {
+                    let err = ApiError::from(e);
+                    if attempt >= max_retries || !err.is_retryable() {
+                        return Err(err);
+                    }
+                    retry::backoff_delay(attempt)
+                }
+            };
+            drop(permit);
+
+            attempt += 1;
+            tracing::warn!(
+                "{} {} failed, retry {}/{} in {:?}",
+                method,
+                url,
+                attempt,
+                max_retries,
+                delay
+            );
+            tokio::time::sleep(delay).await;
+        }
     }
 
     async fn process_response<T>(&self, response: Response) -> Result<T, ApiError>
     where
         T: serde::de::DeserializeOwned,
     {
-        let status = response.status();
-        let body = response.text().await?;
-
-        if !status.is_success() {
-            return Err(ApiError::from_response(status, body));
-        }
-
-        serde_json::from_str(&body).map_err(ApiError::from)
+        let json = envelope::read(response).await?;
+        serde_json::from_value(json).map_err(ApiError::from)
     }
 
     /// Execute a generic API request
@@ -133,16 +234,30 @@ impl CozeApiClient {
 
     /// Create knowledge base with permission (legacy compatibility method)
     /// This method wraps the standard create_dataset API with additional parameters
+    ///
+    /// /v1/datasets 不支持设置权限，知识库对所在空间成员可见；传入 `permission` 时返回
+    /// BadRequest，而不是静默忽略。
     #[allow(dead_code)]
     pub async fn create_knowledge_base_with_permission(
         &self,
         name: String,
         description: Option<String>,
         space_id: Option<String>,
-        _permission: Option<i32>, // Note: permission parameter not used in current API
+        permission: Option<i32>,
     ) -> Result<serde_json::Value, ApiError> {
         use crate::api::knowledge_models::CreateDatasetRequest;
 
+        if let Some(permission) = permission {
+            return Err(ApiError::BadRequest(ApiErrorData::new(
+                "bad_request",
+                format!(
+                    "permission={permission} is not supported: /v1/datasets cannot set dataset permissions"
+                ),
+                None,
+                None,
+            )));
+        }
+
         // Default space_id if not provided (this should be configured properly)
         let space_id = space_id.unwrap_or_else(|| "default_space".to_string());
 
@@ -171,9 +286,14 @@ impl CozeApiClient {
         let url = format!("{}{}", self.base_url, KNOWLEDGE_DOCUMENT_CREATE_URL);
         let sanitized = req.sanitized();
         let payload = serde_json::to_value(&sanitized).map_err(ApiError::from)?;
-        println!("Upload payload: {payload:?}");
+        // 请求体含 Base64 文件内容，只记录文件数量
+        tracing::debug!(
+            dataset_id = %sanitized.dataset_id,
+            documents = sanitized.document_bases.len(),
+            "上传知识库文件"
+        );
         let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
-        println!("Upload response: {resp:?}");
+        tracing::debug!(status = %resp.status(), "知识库文件上传响应");
         self.process_response(resp).await
     }
 
@@ -192,6 +312,90 @@ impl CozeApiClient {
         self.process_response(resp).await
     }
 
+    /// 查看知识库中的文件列表
+    pub async fn list_documents(
+        &self,
+        request: &crate::api::knowledge_models::ListDocumentsRequest,
+    ) -> Result<crate::api::knowledge_models::ListDocumentsResponse, ApiError> {
+        use crate::api::endpoints::datasets_cn::LIST_DOCUMENTS;
+
+        let url = format!("{}{}", self.base_url, LIST_DOCUMENTS);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 只读查询，可安全重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 自动翻页列出知识库中的文件
+    pub fn list_documents_paginated(
+        &self,
+        dataset_id: &str,
+        page_size: u32,
+        start: PageCursor,
+    ) -> Paginator<'_, crate::api::knowledge_models::KnowledgeDocumentInfo> {
+        use crate::api::knowledge_models::ListDocumentsRequest;
+
+        let dataset_id = dataset_id.to_string();
+        Paginator::new(start, move |token| {
+            let dataset_id = dataset_id.clone();
+            async move {
+                let page_num = PageCursor::page_num(token)?;
+                let request = ListDocumentsRequest::new(dataset_id).with_page(page_num, page_size);
+                let result = self.list_documents(&request).await?;
+                Ok(Page::numbered(
+                    result.document_infos,
+                    page_num,
+                    page_size,
+                    result.total,
+                    None,
+                ))
+            }
+        })
+    }
+
+    /// 修改知识库文件的名称或在线网页的更新规则
+    pub async fn update_document(
+        &self,
+        request: &crate::api::knowledge_models::UpdateDocumentRequest,
+    ) -> Result<(), ApiError> {
+        use crate::api::endpoints::datasets_cn::UPDATE_DOCUMENT;
+
+        let url = format!("{}{}", self.base_url, UPDATE_DOCUMENT);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 同样的修改重复提交结果不变，可安全重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
+    /// 删除知识库文件，单次最多 100 个
+    pub async fn delete_documents(
+        &self,
+        request: &crate::api::knowledge_models::DeleteDocumentsRequest,
+    ) -> Result<(), ApiError> {
+        use crate::api::endpoints::datasets_cn::DELETE_DOCUMENTS;
+        use crate::api::knowledge_models::MAX_DELETE_DOCUMENTS;
+
+        if request.document_ids.is_empty() || request.document_ids.len() > MAX_DELETE_DOCUMENTS {
+            return Err(ApiError::BadRequest(ApiErrorData::new(
+                "bad_request",
+                format!("document_ids must contain 1..={MAX_DELETE_DOCUMENTS} ids"),
+                None,
+                None,
+            )));
+        }
+        let url = format!("{}{}", self.base_url, DELETE_DOCUMENTS);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
     // retrieve_conversation_v1 & get_conversation_messages_v3 removed (not used)
 
     pub async fn list_conversations_v1(
@@ -212,7 +416,7 @@ impl CozeApiClient {
             url.push_str(&format!("&workspace_id={}", encode(ws)));
         }
         if let Some(p) = page {
-            url.push_str(&format!("&page={p}"));
+            url.push_str(&format!("&page_num={p}"));
         }
         if let Some(ps) = page_size {
             url.push_str(&format!("&page_size={ps}"));
@@ -221,6 +425,302 @@ impl CozeApiClient {
         self.process_response(resp).await
     }
 
+    /// 自动翻页列出 Bot 的会话，条目为接口返回的原始会话对象
+    pub fn list_conversations_paginated(
+        &self,
+        bot_id: &str,
+        workspace_id: Option<&str>,
+        page_size: u32,
+        start: PageCursor,
+    ) -> Paginator<'_, serde_json::Value> {
+        let bot_id = bot_id.to_string();
+        let workspace_id = workspace_id.map(|s| s.to_string());
+        Paginator::new(start, move |token| {
+            let bot_id = bot_id.clone();
+            let workspace_id = workspace_id.clone();
+            async move {
+                let page_num = PageCursor::page_num(token)?;
+                let body = self
+                    .list_conversations_v1(
+                        &bot_id,
+                        workspace_id.as_deref(),
+                        Some(page_num),
+                        Some(page_size),
+                    )
+                    .await?;
+                let data = envelope::data(&body);
+                let items = data
+                    .get("conversations")
+                    .or_else(|| data.get("items"))
+                    .and_then(|v| v.as_array())
+                    .cloned()
+                    .unwrap_or_default();
+                let total = data.get("total").and_then(|v| v.as_u64());
+                let has_more = data.get("has_more").and_then(|v| v.as_bool());
+                Ok(Page::numbered(items, page_num, page_size, total, has_more))
+            }
+        })
+    }
+
+    /// 创建会话，可附带初始消息与元数据
+    pub async fn create_conversation(
+        &self,
+        request: &crate::api::conversation_models::CreateConversationRequest,
+    ) -> Result<crate::api::conversation_models::ConversationInfo, ApiError> {
+        use crate::api::endpoints::conversation::CREATE_CONVERSATION;
+
+        let url = format!("{}{}", self.base_url, CREATE_CONVERSATION);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 查看会话信息
+    pub async fn retrieve_conversation(
+        &self,
+        conversation_id: &str,
+    ) -> Result<crate::api::conversation_models::ConversationInfo, ApiError> {
+        use crate::api::endpoints::conversation::RETRIEVE_CONVERSATION;
+
+        let url = format!(
+            "{}{}?conversation_id={}",
+            self.base_url,
+            RETRIEVE_CONVERSATION,
+            encode(conversation_id)
+        );
+        let resp = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 清除会话上下文：之后的对话不再携带此前的消息，历史消息仍保留
+    pub async fn clear_conversation(
+        &self,
+        conversation_id: &str,
+    ) -> Result<crate::api::conversation_models::ConversationSection, ApiError> {
+        use crate::api::endpoints::conversation::CLEAR_CONVERSATION;
+
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            CLEAR_CONVERSATION.replace("{conversation_id}", &encode(conversation_id))
+        );
+        let resp = self.send_raw_request("POST", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 删除会话及其消息
+    pub async fn delete_conversation(&self, conversation_id: &str) -> Result<(), ApiError> {
+        use crate::api::endpoints::conversation::DELETE_CONVERSATION;
+
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            DELETE_CONVERSATION.replace("{conversation_id}", &encode(conversation_id))
+        );
+        let resp = self.send_raw_request("DELETE", &url, None).await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
+    /// 在会话中创建一条消息（不会触发 Bot 回复）
+    pub async fn create_message(
+        &self,
+        conversation_id: &str,
+        message: &crate::api::chat_models::ChatMessage,
+    ) -> Result<crate::api::message_models::ConversationMessage, ApiError> {
+        use crate::api::endpoints::conversation::CREATE_MESSAGE;
+
+        let url = format!(
+            "{}{}?conversation_id={}",
+            self.base_url,
+            CREATE_MESSAGE,
+            encode(conversation_id)
+        );
+        let payload = Self::message_payload(message, true)?;
+        let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 查看会话的消息列表，按 `before_id` / `after_id` 游标翻页
+    pub async fn list_messages(
+        &self,
+        conversation_id: &str,
+        request: &crate::api::message_models::ListMessagesRequest,
+    ) -> Result<crate::api::message_models::ListMessagesResponse, ApiError> {
+        use crate::api::endpoints::conversation::LIST_MESSAGES;
+
+        let url = format!(
+            "{}{}?conversation_id={}",
+            self.base_url,
+            LIST_MESSAGES,
+            encode(conversation_id)
+        );
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 以 POST 实现的只读查询，可安全重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        let parsed = envelope::read(resp).await?;
+        // 分页游标与 data 同级
+        serde_json::from_value(parsed).map_err(ApiError::from)
+    }
+
+    /// 自动翻页查看会话消息：沿 `before_id` / `after_id` 的方向继续翻页，
+    /// 都未设置时按 `order` 决定方向（desc 向更早、asc 向更新）
+    pub fn list_messages_paginated(
+        &self,
+        conversation_id: &str,
+        request: &crate::api::message_models::ListMessagesRequest,
+        start: PageCursor,
+    ) -> Paginator<'_, crate::api::message_models::ConversationMessage> {
+        let conversation_id = conversation_id.to_string();
+        let request = request.clone();
+        Paginator::new(start, move |token| {
+            let conversation_id = conversation_id.clone();
+            let mut request = request.clone();
+            async move {
+                let ascending = request.order.as_deref() == Some("asc");
+                let mut before = true;
+                match token {
+                    Some(PageToken::BeforeId(id)) => {
+                        request.before_id = Some(id);
+                        request.after_id = None;
+                    }
+                    Some(PageToken::AfterId(id)) => {
+                        request.after_id = Some(id);
+                        request.before_id = None;
+                        before = false;
+                    }
+                    Some(PageToken::Page(_)) => {
+                        return Err(ApiError::BadRequest(ApiErrorData::new(
+                            "bad_request",
+                            "message lists take before_id / after_id cursors".to_string(),
+                            None,
+                            None,
+                        )))
+                    }
+                    None => {
+                        before = match (&request.before_id, &request.after_id) {
+                            (Some(_), _) => true,
+                            (None, Some(_)) => false,
+                            (None, None) => !ascending,
+                        }
+                    }
+                }
+                let response = self.list_messages(&conversation_id, &request).await?;
+                // 沿同一方向继续：向更早取列表中最早的一条，向更新取最新的一条
+                let edge = if before == ascending {
+                    response.first_id
+                } else {
+                    response.last_id
+                };
+                let next = edge.filter(|_| response.has_more).map(|id| {
+                    if before {
+                        PageToken::BeforeId(id)
+                    } else {
+                        PageToken::AfterId(id)
+                    }
+                });
+                Ok(Page {
+                    items: response.data,
+                    next,
+                    total: None,
+                })
+            }
+        })
+    }
+
+    /// 查看单条消息
+    pub async fn retrieve_message(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+    ) -> Result<crate::api::message_models::ConversationMessage, ApiError> {
+        use crate::api::endpoints::conversation::RETRIEVE_MESSAGE;
+
+        let url = format!(
+            "{}{}?conversation_id={}&message_id={}",
+            self.base_url,
+            RETRIEVE_MESSAGE,
+            encode(conversation_id),
+            encode(message_id)
+        );
+        let resp = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 修改消息的内容与元数据（角色不可修改）
+    pub async fn update_message(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+        message: &crate::api::chat_models::ChatMessage,
+    ) -> Result<crate::api::message_models::ConversationMessage, ApiError> {
+        use crate::api::endpoints::conversation::MODIFY_MESSAGE;
+
+        let url = format!(
+            "{}{}?conversation_id={}&message_id={}",
+            self.base_url,
+            MODIFY_MESSAGE,
+            encode(conversation_id),
+            encode(message_id)
+        );
+        let payload = Self::message_payload(message, false)?;
+        // 以相同内容覆盖，重复提交结果一致
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        let parsed = envelope::read(resp).await?;
+        // 修改接口的消息位于顶层 message 字段
+        let message = parsed
+            .get("message")
+            .unwrap_or_else(|| envelope::data(&parsed));
+        serde_json::from_value(message.clone()).map_err(ApiError::from)
+    }
+
+    /// 删除消息，返回被删除的消息
+    pub async fn delete_message(
+        &self,
+        conversation_id: &str,
+        message_id: &str,
+    ) -> Result<crate::api::message_models::ConversationMessage, ApiError> {
+        use crate::api::endpoints::conversation::DELETE_MESSAGE;
+
+        let url = format!(
+            "{}{}?conversation_id={}&message_id={}",
+            self.base_url,
+            DELETE_MESSAGE,
+            encode(conversation_id),
+            encode(message_id)
+        );
+        let resp = self.send_raw_request("POST", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 消息接口的请求体：content_type 缺省为 text；修改消息时不提交 role
+    fn message_payload(
+        message: &crate::api::chat_models::ChatMessage,
+        with_role: bool,
+    ) -> Result<serde_json::Value, ApiError> {
+        let mut payload = serde_json::to_value(message).map_err(ApiError::from)?;
+        if let Some(obj) = payload.as_object_mut() {
+            obj.remove("object_string");
+            if !with_role {
+                obj.remove("role");
+            }
+            if message.content.is_some() && !obj.contains_key("content_type") {
+                obj.insert("content_type".to_string(), serde_json::json!("text"));
+            }
+        }
+        Ok(payload)
+    }
+
     /// 创建知识库 (符合 POST /v1/datasets API 文档规范)
     pub async fn create_dataset(
         &self,
@@ -236,6 +736,75 @@ impl CozeApiClient {
         self.process_response(resp).await
     }
 
+    /// 修改知识库的名称、描述与图标（全量更新，见 `UpdateDatasetRequest`）
+    pub async fn update_dataset(
+        &self,
+        dataset_id: &str,
+        request: &crate::api::knowledge_models::UpdateDatasetRequest,
+    ) -> Result<(), ApiError> {
+        use crate::api::endpoints::datasets_v1::UPDATE_DATASET;
+
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            UPDATE_DATASET.replace("{dataset_id}", &encode(dataset_id))
+        );
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        let resp = self.send_raw_request("PUT", &url, Some(payload)).await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
+    /// 删除知识库，知识库中的文件一并删除，且不可恢复
+    pub async fn delete_dataset(&self, dataset_id: &str) -> Result<(), ApiError> {
+        use crate::api::endpoints::datasets_v1::DELETE_DATASET;
+
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            DELETE_DATASET.replace("{dataset_id}", &encode(dataset_id))
+        );
+        let resp = self.send_raw_request("DELETE", &url, None).await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
+    /// 查看知识库文件的上传（切片）进度
+    pub async fn document_progress(
+        &self,
+        dataset_id: &str,
+        document_ids: &[String],
+    ) -> Result<Vec<crate::api::knowledge_models::DocumentProgress>, ApiError> {
+        use crate::api::endpoints::datasets_v1::DOCUMENT_PROGRESS;
+        use crate::api::knowledge_models::{DocumentProgressRequest, DocumentProgressResponse};
+
+        if document_ids.is_empty() {
+            return Err(ApiError::BadRequest(ApiErrorData::new(
+                "bad_request",
+                "document_ids must not be empty".to_string(),
+                None,
+                None,
+            )));
+        }
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            DOCUMENT_PROGRESS.replace("{dataset_id}", &encode(dataset_id))
+        );
+        let request = DocumentProgressRequest {
+            document_ids: document_ids.to_vec(),
+        };
+        let payload = serde_json::to_value(&request).map_err(ApiError::from)?;
+        // 只读查询，可安全重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        let parsed = envelope::read(resp).await?;
+        let data: DocumentProgressResponse =
+            serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)?;
+        Ok(data.data)
+    }
+
     /// Official /v1/datasets list API (as per public documentation)
     pub async fn list_datasets(
         &self,
@@ -288,19 +857,15 @@ impl CozeApiClient {
             url.push_str(&format!("&page_size={ps}"));
         }
         let resp = self.send_raw_request("GET", &url, None).await?;
-        let raw_text_status = resp.status();
-        let text = resp.text().await?;
-        if !raw_text_status.is_success() {
-            return Err(ApiError::from_response(raw_text_status, text));
-        }
+        let raw = envelope::read(resp).await?;
         // Try direct deserialize first
-        let parsed: Result<crate::api::ListDatasetsApiResponse, _> = serde_json::from_str(&text);
+        let parsed: Result<crate::api::ListDatasetsApiResponse, _> =
+            serde_json::from_value(raw.clone());
         if let Ok(r) = parsed {
             return Ok(r.into_internal());
         }
         // Fallback: tolerant mapping similar to CN version
-        let raw: serde_json::Value = serde_json::from_str(&text).map_err(ApiError::from)?;
-        let data = raw.get("data").unwrap_or(&raw);
+        let data = envelope::data(&raw);
         let list = data
             .get("dataset_list")
             .or_else(|| data.get("datasets"))
@@ -445,6 +1010,42 @@ impl CozeApiClient {
         Ok(crate::api::ListKnowledgeBasesResponse { datasets, total })
     }
 
+    /// 自动翻页列出知识库；`page_size` 为 1~300
+    pub fn list_datasets_paginated(
+        &self,
+        space_id: &str,
+        name: Option<&str>,
+        format_type: Option<i32>,
+        page_size: u32,
+        start: PageCursor,
+    ) -> Paginator<'_, crate::api::KnowledgeBaseInfo> {
+        let space_id = space_id.to_string();
+        let name = name.map(|s| s.to_string());
+        Paginator::new(start, move |token| {
+            let space_id = space_id.clone();
+            let name = name.clone();
+            async move {
+                let page_num = PageCursor::page_num(token)?;
+                let result = self
+                    .list_datasets(
+                        &space_id,
+                        name.as_deref(),
+                        format_type,
+                        Some(page_num),
+                        Some(page_size),
+                    )
+                    .await?;
+                Ok(Page::numbered(
+                    result.datasets,
+                    page_num,
+                    page_size,
+                    Some(result.total as u64),
+                    None,
+                ))
+            }
+        })
+    }
+
     // ---- Chat API methods ----
 
     /// 发送聊天请求（非流式）
@@ -460,42 +1061,10 @@ impl CozeApiClient {
 
         let payload = serde_json::to_value(&req).map_err(ApiError::from)?;
         let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
-
-        // 解析响应
-        let status = resp.status();
-        let body = resp.text().await?;
-
-        if !status.is_success() {
-            return Err(ApiError::from_response(status, body));
-        }
-
-        // 尝试解析为标准响应格式
-        let response_value: serde_json::Value =
-            serde_json::from_str(&body).map_err(ApiError::from)?;
-
-        // 检查是否有业务错误
-        if let Some(code) = response_value.get("code") {
-            if let Some(code_num) = code.as_i64() {
-                if code_num != 0 {
-                    let msg = response_value
-                        .get("msg")
-                        .or_else(|| response_value.get("message"))
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("Unknown error");
-                    return Err(ApiError::BadRequest(ApiErrorData::new(
-                        "api_error",
-                        format!("API returned error code {code_num}: {msg}"),
-                        Some(status.as_u16()),
-                        Some(body),
-                    )));
-                }
-            }
-        }
+        let response_value = envelope::read(resp).await?;
 
         // 提取data字段或使用整个响应
-        let data = response_value.get("data").unwrap_or(&response_value);
-
-        serde_json::from_value(data.clone()).map_err(ApiError::from)
+        serde_json::from_value(envelope::data(&response_value).clone()).map_err(ApiError::from)
     }
 
     /// 发送流式聊天请求
@@ -507,7 +1076,6 @@ impl CozeApiClient {
         ApiError,
     > {
         use crate::api::endpoints::chat::CHAT_V3_STREAM;
-        use futures::stream::StreamExt;
 
         let url = format!("{}{}", self.base_url, CHAT_V3_STREAM);
 
@@ -515,10 +1083,25 @@ impl CozeApiClient {
         req.stream = Some(true); // 确保流式
 
         let payload = serde_json::to_value(&req).map_err(ApiError::from)?;
+        self.open_event_stream(CHAT_V3_STREAM, &url, payload, Self::parse_sse_event)
+            .await
+    }
 
+    /// POST 请求并将 SSE 响应按 `parse` 解码为事件流（chat、submit_tool_outputs 与工作流共用）
+    async fn open_event_stream<T>(
+        &self,
+        path: &str,
+        url: &str,
+        payload: serde_json::Value,
+        parse: fn(&SseEvent) -> Result<Option<T>, ApiError>,
+    ) -> Result<impl futures::Stream<Item = Result<T, ApiError>>, ApiError> {
+        use futures::stream::StreamExt;
+
+        // 流式响应的并发名额一直持有到流结束
+        let permit = self.limiter.acquire(endpoint_group(path)).await;
         let request_builder = self
             .client
-            .post(&url)
+            .post(url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .header("Accept", "text/event-stream")
@@ -528,89 +1111,278 @@ impl CozeApiClient {
         let response = request_builder.send().await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            return Err(ApiError::from_response(status, body));
+            return Err(envelope::error_from(response).await);
+        }
+        // 业务错误（如鉴权失败）以普通 JSON 包裹返回，而非 SSE
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json"));
+        if is_json {
+            let body = envelope::read(response).await?;
+            return Err(ApiError::InvalidResponseFormat(ApiErrorData::new(
+                "invalid_response",
+                "expected an event stream".to_string(),
+                None,
+                Some(body.to_string()),
+            )));
         }
 
+        let mut decoder = SseDecoder::new();
         let stream = response
             .bytes_stream()
-            .map(|chunk_result| {
-                chunk_result.map_err(ApiError::from).and_then(|chunk| {
-                    let text = String::from_utf8_lossy(&chunk);
-                    Self::parse_sse_chunk(&text)
-                })
+            .map(move |chunk_result| {
+                let _permit = &permit;
+                let events: Vec<_> = match chunk_result {
+                    Ok(chunk) => decoder
+                        .push(&chunk)
+                        .iter()
+                        .filter_map(|event| parse(event).transpose())
+                        .collect(),
+                    Err(e) => vec![Err(ApiError::from(e))],
+                };
+                futures::stream::iter(events)
             })
-            .filter_map(|result| async move {
-                match result {
-                    Ok(Some(response)) => Some(Ok(response)),
-                    Ok(None) => None, // 跳过空块或注释
-                    Err(e) => Some(Err(e)),
-                }
-            });
+            .flatten();
 
         Ok(stream)
     }
 
-    /// 解析SSE (Server-Sent Events) 数据块
-    fn parse_sse_chunk(
-        text: &str,
+    /// 将一条 SSE 事件转换为流式响应；`event:` 行给出事件类型，data 中未携带时补入
+    fn parse_sse_event(
+        event: &SseEvent,
     ) -> Result<Option<crate::api::chat_models::StreamChatResponse>, ApiError> {
-        for line in text.lines() {
-            let line = line.trim();
+        use crate::api::chat_models::{StreamChatResponse, StreamEventType};
+
+        let data = event.data.trim();
+        if data == "[DONE]" || event.event.as_deref() == Some("done") {
+            return Ok(Some(StreamChatResponse {
+                event: StreamEventType::Done,
+                conversation_id: None,
+                id: None,
+                chat_id: None,
+                created_at: None,
+                delta: None,
+                usage: None,
+                last_error: None,
+                required_action: None,
+            }));
+        }
+        if data.is_empty() {
+            return Ok(None);
+        }
 
-            // 跳过空行和注释
-            if line.is_empty() || line.starts_with(':') {
-                continue;
-            }
+        let parsed: serde_json::Value = serde_json::from_str(data).map_err(ApiError::from)?;
 
-            // 解析data字段
-            if let Some(data) = line.strip_prefix("data: ") {
-                if data == "[DONE]" {
-                    return Ok(Some(crate::api::chat_models::StreamChatResponse {
-                        event: crate::api::chat_models::StreamEventType::Done,
-                        conversation_id: None,
-                        id: None,
-                        created_at: None,
-                        delta: None,
-                        usage: None,
-                        last_error: None,
-                    }));
-                }
+        // 检查是否有业务错误
+        envelope::check(&parsed, None, None, data)?;
 
-                let parsed: serde_json::Value =
-                    serde_json::from_str(data).map_err(ApiError::from)?;
-
-                // 检查是否有业务错误
-                if let Some(code) = parsed.get("code") {
-                    if let Some(code_num) = code.as_i64() {
-                        if code_num != 0 {
-                            let msg = parsed
-                                .get("msg")
-                                .or_else(|| parsed.get("message"))
-                                .and_then(|v| v.as_str())
-                                .unwrap_or("Unknown error");
-                            return Err(ApiError::BadRequest(ApiErrorData::new(
-                                "stream_error",
-                                format!("Stream returned error code {code_num}: {msg}"),
-                                None,
-                                Some(data.to_string()),
-                            )));
-                        }
-                    }
-                }
+        // 提取data字段或使用整个响应
+        let mut data_field = envelope::data(&parsed).clone();
+        if let (Some(name), Some(obj)) = (&event.event, data_field.as_object_mut()) {
+            obj.entry("event")
+                .or_insert_with(|| serde_json::Value::String(name.clone()));
+        }
 
-                // 提取data字段或使用整个响应
-                let data_field = parsed.get("data").unwrap_or(&parsed);
+        let response: StreamChatResponse =
+            serde_json::from_value(data_field).map_err(ApiError::from)?;
 
-                let response: crate::api::chat_models::StreamChatResponse =
-                    serde_json::from_value(data_field.clone()).map_err(ApiError::from)?;
+        Ok(Some(response))
+    }
+
+    /// 解析工作流 SSE 事件：事件类型来自 `event:` 行，Error 事件转换为 `ApiError`
+    fn parse_workflow_event(
+        event: &SseEvent,
+    ) -> Result<Option<crate::api::workflow_models::WorkflowStreamEvent>, ApiError> {
+        use crate::api::workflow_models::{WorkflowEventType, WorkflowStreamEvent};
+
+        let data = event.data.trim();
+        let mut parsed: serde_json::Value = if data.is_empty() {
+            serde_json::json!({})
+        } else {
+            serde_json::from_str(data).map_err(ApiError::from)?
+        };
+        envelope::check(&parsed, None, None, data)?;
+
+        let name = event.event.as_deref().unwrap_or("Message");
+        if name == "Error" {
+            let code = parsed
+                .get("error_code")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(-1);
+            let message = parsed
+                .get("error_message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("workflow failed")
+                .to_string();
+            return Err(ApiError::from_coze_code(
+                code as i32,
+                message,
+                None,
+                Some(data.to_string()),
+                None,
+            ));
+        }
+
+        let Some(obj) = parsed.as_object_mut() else {
+            return Err(ApiError::InvalidResponseFormat(ApiErrorData::new(
+                "invalid_response",
+                format!("unexpected workflow event data: {data}"),
+                None,
+                Some(data.to_string()),
+            )));
+        };
+        obj.insert("event".to_string(), serde_json::json!(name));
+        if let Some(id) = &event.id {
+            obj.entry("id")
+                .or_insert_with(|| serde_json::Value::String(id.clone()));
+        }
+        let response: WorkflowStreamEvent =
+            serde_json::from_value(parsed).map_err(ApiError::from)?;
+        if response.event == WorkflowEventType::Ping {
+            return Ok(None);
+        }
+        Ok(Some(response))
+    }
+
+    /// 列出工作空间中的工作流
+    pub async fn list_workflows(
+        &self,
+        request: &crate::api::workflow_models::ListWorkflowsRequest,
+    ) -> Result<crate::api::workflow_models::ListWorkflowsData, ApiError> {
+        use crate::api::endpoints::workflows::LIST_WORKFLOWS;
 
-                return Ok(Some(response));
+        let url = format!(
+            "{}{}?{}",
+            self.base_url,
+            LIST_WORKFLOWS,
+            request.to_query_params()
+        );
+        let resp = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 自动翻页列出工作流
+    pub fn list_workflows_paginated(
+        &self,
+        request: &crate::api::workflow_models::ListWorkflowsRequest,
+        start: PageCursor,
+    ) -> Paginator<'_, crate::api::workflow_models::WorkflowInfo> {
+        let request = request.clone();
+        Paginator::new(start, move |token| {
+            let request = request.clone();
+            async move {
+                let page_num = PageCursor::page_num(token)?;
+                let page_size = request.page_size;
+                let data = self
+                    .list_workflows(&request.with_page(page_num, page_size))
+                    .await?;
+                Ok(Page::numbered(
+                    data.items,
+                    page_num,
+                    page_size,
+                    None,
+                    Some(data.has_more),
+                ))
             }
+        })
+    }
+
+    /// 同步执行工作流；`is_async` 时立即返回 execute_id
+    pub async fn run_workflow(
+        &self,
+        request: &crate::api::workflow_models::RunWorkflowRequest,
+    ) -> Result<crate::api::workflow_models::RunWorkflowResponse, ApiError> {
+        use crate::api::endpoints::workflows::RUN_WORKFLOW;
+
+        let url = format!("{}{}", self.base_url, RUN_WORKFLOW);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 工作流可能有副作用（写数据库、发消息），不重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Never)
+            .await?;
+        let parsed = envelope::read(resp).await?;
+        // data（输出字符串）、execute_id、debug_url 均位于顶层
+        serde_json::from_value(parsed).map_err(ApiError::from)
+    }
+
+    /// 查询工作流执行记录（异步执行的状态与输出）
+    pub async fn get_workflow_run_history(
+        &self,
+        workflow_id: &str,
+        execute_id: &str,
+    ) -> Result<crate::api::workflow_models::WorkflowRunHistory, ApiError> {
+        use crate::api::endpoints::workflows::RUN_HISTORY;
+
+        let url = format!(
+            "{}{}",
+            self.base_url,
+            RUN_HISTORY
+                .replace("{workflow_id}", &encode(workflow_id))
+                .replace("{execute_id}", &encode(execute_id))
+        );
+        let resp = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        // data 为只含一条记录的数组
+        let mut records: Vec<crate::api::workflow_models::WorkflowRunHistory> =
+            serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)?;
+        if records.is_empty() {
+            return Err(ApiError::NotFound(ApiErrorData::new(
+                "not_found",
+                format!("no run history for execute_id {execute_id}"),
+                None,
+                Some(parsed.to_string()),
+            )));
         }
+        Ok(records.swap_remove(0))
+    }
+
+    /// 流式执行工作流
+    pub async fn stream_workflow(
+        &self,
+        request: &crate::api::workflow_models::RunWorkflowRequest,
+    ) -> Result<
+        impl futures::Stream<Item = Result<crate::api::workflow_models::WorkflowStreamEvent, ApiError>>,
+        ApiError,
+    > {
+        use crate::api::endpoints::workflows::STREAM_RUN_WORKFLOW;
 
-        Ok(None)
+        let url = format!("{}{}", self.base_url, STREAM_RUN_WORKFLOW);
+        let mut payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 流式接口不支持异步执行
+        if let Some(obj) = payload.as_object_mut() {
+            obj.remove("is_async");
+        }
+        self.open_event_stream(
+            STREAM_RUN_WORKFLOW,
+            &url,
+            payload,
+            Self::parse_workflow_event,
+        )
+        .await
+    }
+
+    /// 以用户输入恢复中断（Interrupt）的工作流，继续以流式返回
+    pub async fn resume_workflow(
+        &self,
+        request: &crate::api::workflow_models::ResumeWorkflowRequest,
+    ) -> Result<
+        impl futures::Stream<Item = Result<crate::api::workflow_models::WorkflowStreamEvent, ApiError>>,
+        ApiError,
+    > {
+        use crate::api::endpoints::workflows::STREAM_RESUME_WORKFLOW;
+
+        let url = format!("{}{}", self.base_url, STREAM_RESUME_WORKFLOW);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        self.open_event_stream(
+            STREAM_RESUME_WORKFLOW,
+            &url,
+            payload,
+            Self::parse_workflow_event,
+        )
+        .await
     }
 
     /// 使用类型化模型获取智能体列表
@@ -628,34 +1400,105 @@ impl CozeApiClient {
         );
 
         let response = self.send_raw_request("GET", &url, None).await?;
-        let text = response.text().await.map_err(ApiError::from)?;
-
-        let parsed: serde_json::Value = serde_json::from_str(&text).map_err(ApiError::from)?;
-
-        // 检查业务错误
-        if let Some(code) = parsed.get("code").and_then(|v| v.as_i64()) {
-            if code != 0 {
-                let msg = parsed
-                    .get("msg")
-                    .or_else(|| parsed.get("message"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown error");
-                return Err(ApiError::BadRequest(ApiErrorData::new(
-                    "list_bots",
-                    format!("API returned error code {code}: {msg}"),
-                    None,
-                    Some(text),
-                )));
-            }
-        }
+        let parsed = envelope::read(response).await?;
 
         // 解析响应
         let response: crate::api::bot_models::ListBotsResponse =
-            serde_json::from_str(&text).map_err(ApiError::from)?;
+            serde_json::from_value(parsed).map_err(ApiError::from)?;
 
         Ok(response)
     }
 
+    /// 自动翻页列出 Bot，页大小沿用 `request` 中的设置
+    pub fn list_bots_paginated(
+        &self,
+        request: &crate::api::bot_models::ListBotsRequest,
+        start: PageCursor,
+    ) -> Paginator<'_, crate::api::bot_models::BotInfo> {
+        let request = request.clone();
+        Paginator::new(start, move |token| {
+            let request = request.clone();
+            async move {
+                let page_num = PageCursor::page_num(token)?;
+                let page_size = request.page_size.unwrap_or(20);
+                let response = self
+                    .list_bots_typed(&request.with_page(page_num, page_size))
+                    .await?;
+                let total = u64::from(response.data.total);
+                Ok(Page::numbered(
+                    response.data.items,
+                    page_num,
+                    page_size,
+                    Some(total),
+                    None,
+                ))
+            }
+        })
+    }
+
+    /// 获取 Bot 已发布版本的详细配置（人设、模型、插件、知识库、开场白）
+    pub async fn retrieve_bot(
+        &self,
+        bot_id: &str,
+    ) -> Result<crate::api::bot_models::BotDetail, ApiError> {
+        use crate::api::endpoints::bots::RETRIEVE_BOT;
+
+        let url = format!(
+            "{}{}?bot_id={}",
+            self.base_url,
+            RETRIEVE_BOT,
+            encode(bot_id)
+        );
+        let resp = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 创建 Bot（草稿），返回新 Bot 的 ID
+    pub async fn create_bot(
+        &self,
+        request: &crate::api::bot_models::CreateBotRequest,
+    ) -> Result<crate::api::bot_models::CreateBotData, ApiError> {
+        use crate::api::endpoints::bots::CREATE_BOT;
+
+        let url = format!("{}{}", self.base_url, CREATE_BOT);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 更新 Bot 草稿配置；未设置的字段保持原值
+    pub async fn update_bot(
+        &self,
+        request: &crate::api::bot_models::UpdateBotRequest,
+    ) -> Result<(), ApiError> {
+        use crate::api::endpoints::bots::UPDATE_BOT;
+
+        let url = format!("{}{}", self.base_url, UPDATE_BOT);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        // 同样的配置重复提交结果不变，可安全重试
+        let resp = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        envelope::read(resp).await?;
+        Ok(())
+    }
+
+    /// 发布 Bot 到指定渠道
+    pub async fn publish_bot(
+        &self,
+        request: &crate::api::bot_models::PublishBotRequest,
+    ) -> Result<crate::api::bot_models::PublishBotData, ApiError> {
+        use crate::api::endpoints::bots::PUBLISH_BOT;
+
+        let url = format!("{}{}", self.base_url, PUBLISH_BOT);
+        let payload = serde_json::to_value(request).map_err(ApiError::from)?;
+        let resp = self.send_raw_request("POST", &url, Some(payload)).await?;
+        let parsed = envelope::read(resp).await?;
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
     /// 获取对话详情
     pub async fn get_chat_detail(
         &self,
@@ -673,30 +1516,10 @@ impl CozeApiClient {
         );
 
         let response = self.send_raw_request("GET", &url, None).await?;
-        let text = response.text().await.map_err(ApiError::from)?;
-
-        let parsed: serde_json::Value = serde_json::from_str(&text).map_err(ApiError::from)?;
-
-        // 检查业务错误
-        if let Some(code) = parsed.get("code").and_then(|v| v.as_i64()) {
-            if code != 0 {
-                let msg = parsed
-                    .get("msg")
-                    .or_else(|| parsed.get("message"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown error");
-                return Err(ApiError::BadRequest(ApiErrorData::new(
-                    "get_chat_detail",
-                    format!("API returned error code {code}: {msg}"),
-                    None,
-                    Some(text),
-                )));
-            }
-        }
+        let parsed = envelope::read(response).await?;
 
         // 提取data字段或使用整个响应
-        let data = parsed.get("data").unwrap_or(&parsed);
-        serde_json::from_value(data.clone()).map_err(ApiError::from)
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
     }
 
     /// 获取对话消息列表
@@ -716,26 +1539,7 @@ impl CozeApiClient {
         );
 
         let response = self.send_raw_request("GET", &url, None).await?;
-        let text = response.text().await.map_err(ApiError::from)?;
-
-        let parsed: serde_json::Value = serde_json::from_str(&text).map_err(ApiError::from)?;
-
-        // 检查业务错误
-        if let Some(code) = parsed.get("code").and_then(|v| v.as_i64()) {
-            if code != 0 {
-                let msg = parsed
-                    .get("msg")
-                    .or_else(|| parsed.get("message"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown error");
-                return Err(ApiError::BadRequest(ApiErrorData::new(
-                    "get_chat_messages",
-                    format!("API returned error code {code}: {msg}"),
-                    None,
-                    Some(text),
-                )));
-            }
-        }
+        let parsed = envelope::read(response).await?;
 
         // 提取messages字段
         let empty_array = serde_json::Value::Array(vec![]);
@@ -748,4 +1552,126 @@ impl CozeApiClient {
 
         serde_json::from_value(messages.clone()).map_err(ApiError::from)
     }
+
+    /// 取消进行中的对话（状态为 created / in_progress 时有效），返回取消后的对话详情
+    pub async fn cancel_chat(
+        &self,
+        conversation_id: &str,
+        chat_id: &str,
+    ) -> Result<crate::api::chat_models::ChatResponse, ApiError> {
+        use crate::api::endpoints::chat::CANCEL_CHAT;
+
+        let url = format!("{}{}", self.base_url, CANCEL_CHAT);
+        let payload = serde_json::json!({
+            "conversation_id": conversation_id,
+            "chat_id": chat_id,
+        });
+
+        // 重复取消没有副作用，可安全重试
+        let response = self
+            .send_request("POST", &url, Some(payload), RetryMode::Safe)
+            .await?;
+        let parsed = envelope::read(response).await?;
+
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 提交本地插件（requires_action）的执行结果，非流式返回对话详情
+    pub async fn submit_tool_outputs(
+        &self,
+        request: crate::api::chat_models::SubmitToolOutputsRequest,
+    ) -> Result<crate::api::chat_models::ChatResponse, ApiError> {
+        let mut req = request;
+        req.stream = Some(false);
+
+        let url = self.submit_tool_outputs_url(&req);
+        let payload = serde_json::to_value(&req).map_err(ApiError::from)?;
+        let response = self.send_raw_request("POST", &url, Some(payload)).await?;
+        let parsed = envelope::read(response).await?;
+
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 提交工具执行结果并以流式继续对话
+    pub async fn submit_tool_outputs_stream(
+        &self,
+        request: crate::api::chat_models::SubmitToolOutputsRequest,
+    ) -> Result<
+        impl futures::Stream<Item = Result<crate::api::chat_models::StreamChatResponse, ApiError>>,
+        ApiError,
+    > {
+        use crate::api::endpoints::chat::SUBMIT_TOOL_OUTPUTS;
+
+        let mut req = request;
+        req.stream = Some(true);
+
+        let url = self.submit_tool_outputs_url(&req);
+        let payload = serde_json::to_value(&req).map_err(ApiError::from)?;
+        self.open_event_stream(SUBMIT_TOOL_OUTPUTS, &url, payload, Self::parse_sse_event)
+            .await
+    }
+
+    fn submit_tool_outputs_url(
+        &self,
+        request: &crate::api::chat_models::SubmitToolOutputsRequest,
+    ) -> String {
+        use crate::api::endpoints::chat::SUBMIT_TOOL_OUTPUTS;
+
+        format!(
+            "{}{}?conversation_id={}&chat_id={}",
+            self.base_url,
+            SUBMIT_TOOL_OUTPUTS,
+            encode(&request.conversation_id),
+            encode(&request.chat_id)
+        )
+    }
+
+    /// 上传文件（multipart/form-data），返回可在对话、知识库中引用的 file_id
+    pub async fn upload_file(
+        &self,
+        file_name: String,
+        bytes: Vec<u8>,
+    ) -> Result<crate::api::file_models::FileInfo, ApiError> {
+        use crate::api::endpoints::files::UPLOAD_FILE;
+
+        let url = format!("{}{}", self.base_url, UPLOAD_FILE);
+        let form = reqwest::multipart::Form::new().part(
+            "file",
+            reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+        );
+
+        // 上传不是幂等操作，不自动重试
+        let _permit = self.limiter.acquire(endpoint_group(UPLOAD_FILE)).await;
+        let response = self
+            .client
+            .post(&url)
+            .timeout(self.timeout)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .multipart(form)
+            .send()
+            .await?;
+        let parsed = envelope::read(response).await?;
+
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
+
+    /// 查看已上传文件的详情
+    pub async fn retrieve_file(
+        &self,
+        file_id: &str,
+    ) -> Result<crate::api::file_models::FileInfo, ApiError> {
+        use crate::api::endpoints::files::RETRIEVE_FILE;
+
+        let url = format!(
+            "{}{}?file_id={}",
+            self.base_url,
+            RETRIEVE_FILE,
+            encode(file_id)
+        );
+
+        let response = self.send_raw_request("GET", &url, None).await?;
+        let parsed = envelope::read(response).await?;
+
+        serde_json::from_value(envelope::data(&parsed).clone()).map_err(ApiError::from)
+    }
 }
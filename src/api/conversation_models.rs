@@ -0,0 +1,62 @@
+use crate::api::chat_models::ChatMessage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 会话信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationInfo {
+    /// 会话 ID
+    pub id: String,
+    /// 创建时间戳（秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    /// 创建时附加的元数据
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta_data: Option<HashMap<String, String>>,
+    /// 最新一段上下文的 section ID（清除上下文后变化）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_section_id: Option<String>,
+}
+
+/// 创建会话请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateConversationRequest {
+    /// 绑定的 Bot（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_id: Option<String>,
+    /// 会话的初始消息
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<ChatMessage>,
+    /// 自定义元数据（最多 16 对键值）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_data: Option<HashMap<String, String>>,
+}
+
+/// 清除上下文的结果：会话开启新的 section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationSection {
+    /// 新 section 的 ID
+    pub id: String,
+    pub conversation_id: String,
+}
+
+impl CreateConversationRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bot_id(mut self, bot_id: String) -> Self {
+        self.bot_id = Some(bot_id);
+        self
+    }
+
+    pub fn with_messages(mut self, messages: Vec<ChatMessage>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    pub fn with_meta_data(mut self, meta_data: HashMap<String, String>) -> Self {
+        self.meta_data = Some(meta_data);
+        self
+    }
+}
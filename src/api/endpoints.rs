@@ -3,21 +3,73 @@ pub const COZE_BASE_URL: &str = "https://api.coze.cn";
 
 // NOTE: Slimmed to only actively used endpoint groups to reduce warnings.
 
+/// 端点分组名称（与下方模块一一对应），用于客户端限流配置；未匹配的路径归入 `default`
+pub const ENDPOINT_GROUPS: &[&str] = &[
+    "conversation",
+    "datasets_v1",
+    "datasets_cn",
+    "bots",
+    "chat",
+    "files",
+    "workflows",
+    "default",
+];
+
+/// 按请求路径判断所属端点分组
+pub fn endpoint_group(path: &str) -> &'static str {
+    if path.starts_with("/v3/chat") {
+        "chat"
+    } else if path.starts_with("/v1/conversation") {
+        "conversation"
+    } else if path.starts_with("/v1/datasets") {
+        "datasets_v1"
+    } else if path.starts_with("/open_api/knowledge") {
+        "datasets_cn"
+    } else if path.starts_with("/v1/bot") {
+        "bots"
+    } else if path.starts_with("/v1/files") {
+        "files"
+    } else if path.starts_with("/v1/workflow") {
+        "workflows"
+    } else {
+        "default"
+    }
+}
+
 pub mod conversation {
     pub const LIST_CONVERSATIONS: &str = "/v1/conversations"; // used by list_conversations_v1
+    pub const CREATE_CONVERSATION: &str = "/v1/conversation/create"; // 创建会话（可带初始消息）
+    pub const RETRIEVE_CONVERSATION: &str = "/v1/conversation/retrieve"; // 查看会话信息
+    pub const CLEAR_CONVERSATION: &str = "/v1/conversations/{conversation_id}/clear"; // 清除上下文
+    pub const DELETE_CONVERSATION: &str = "/v1/conversations/{conversation_id}"; // 删除会话
+    pub const CREATE_MESSAGE: &str = "/v1/conversation/message/create"; // 创建消息
+    pub const LIST_MESSAGES: &str = "/v1/conversation/message/list"; // 查看消息列表（POST）
+    pub const RETRIEVE_MESSAGE: &str = "/v1/conversation/message/retrieve"; // 查看消息详情
+    pub const MODIFY_MESSAGE: &str = "/v1/conversation/message/modify"; // 修改消息
+    pub const DELETE_MESSAGE: &str = "/v1/conversation/message/delete"; // 删除消息
 }
 
 pub mod datasets_v1 {
     pub const LIST_DATASETS: &str = "/v1/datasets"; // canonical dataset listing
     pub const CREATE_DATASETS: &str = "/v1/datasets"; // 创建知识库 API
+    pub const UPDATE_DATASET: &str = "/v1/datasets/{dataset_id}"; // 修改知识库信息（PUT，全量更新）
+    pub const DELETE_DATASET: &str = "/v1/datasets/{dataset_id}"; // 删除知识库及其中的文件（DELETE）
+    pub const DOCUMENT_PROGRESS: &str = "/v1/datasets/{dataset_id}/process"; // 查看文件上传（切片）进度
 }
 
 pub mod datasets_cn {
     pub const GET_KNOWLEDGE_BASE: &str = "/open_api/knowledge/dataset"; // still used by get_dataset_cn for detail fetch
+    pub const LIST_DOCUMENTS: &str = "/open_api/knowledge/document/list"; // 查看知识库文件列表（POST）
+    pub const UPDATE_DOCUMENT: &str = "/open_api/knowledge/document/update"; // 修改文件名称 / 更新规则
+    pub const DELETE_DOCUMENTS: &str = "/open_api/knowledge/document/delete"; // 批量删除文件
 }
 
 pub mod bots {
     pub const LIST_BOTS: &str = "/v1/bots"; // used by list_bots
+    pub const RETRIEVE_BOT: &str = "/v1/bot/get_online_info"; // 已发布版本的配置
+    pub const CREATE_BOT: &str = "/v1/bot/create";
+    pub const UPDATE_BOT: &str = "/v1/bot/update"; // 更新草稿
+    pub const PUBLISH_BOT: &str = "/v1/bot/publish";
 }
 
 pub mod chat {
@@ -25,6 +77,22 @@ pub mod chat {
     pub const CHAT_V3_STREAM: &str = "/v3/chat";
     pub const GET_CHAT_DETAIL: &str = "/v3/chat/retrieve"; // 获取对话详情
     pub const GET_CHAT_MESSAGES: &str = "/v3/chat/message/list"; // 获取对话消息列表
+    pub const CANCEL_CHAT: &str = "/v3/chat/cancel"; // 取消进行中的对话
+    pub const SUBMIT_TOOL_OUTPUTS: &str = "/v3/chat/submit_tool_outputs"; // 提交端插件执行结果
+}
+
+pub mod files {
+    pub const UPLOAD_FILE: &str = "/v1/files/upload"; // multipart 上传，返回 file_id
+    pub const RETRIEVE_FILE: &str = "/v1/files/retrieve"; // 查看文件详情
+}
+
+pub mod workflows {
+    pub const LIST_WORKFLOWS: &str = "/v1/workflows"; // 工作流列表
+    pub const RUN_WORKFLOW: &str = "/v1/workflow/run"; // 同步（或异步）执行
+    pub const STREAM_RUN_WORKFLOW: &str = "/v1/workflow/stream_run"; // 流式执行（SSE）
+    pub const STREAM_RESUME_WORKFLOW: &str = "/v1/workflow/stream_resume"; // 恢复中断的流式执行
+    pub const RUN_HISTORY: &str = "/v1/workflows/{workflow_id}/run_histories/{execute_id}";
+    // 查询执行结果
 }
 
 // Chat completion endpoints removed (unused)
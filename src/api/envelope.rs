@@ -0,0 +1,109 @@
+// Coze 统一响应包裹 { code, msg, data, detail: { logid } } 的集中解码
+use crate::api::error::ApiError;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Response;
+use serde_json::Value;
+
+/// 日志 ID 的响应头（与 body 中的 detail.logid 一致）
+pub const LOGID_HEADER: &str = "x-tt-logid";
+
+/// 读取响应并解码包裹：HTTP 非 2xx 或业务 `code != 0` 时返回对应的 `ApiError`，否则返回完整 JSON
+pub async fn read(response: Response) -> Result<Value, ApiError> {
+    if !response.status().is_success() {
+        return Err(error_from(response).await);
+    }
+    let status = response.status().as_u16();
+    let header_logid = header_logid(&response);
+    let body = response.text().await?;
+    let json: Value = serde_json::from_str(&body).map_err(ApiError::from)?;
+    check(&json, Some(status), header_logid, &body)?;
+    Ok(json)
+}
+
+/// 将非 2xx 响应转换为 `ApiError`，保留 body 中的 code/msg 与 logid
+pub async fn error_from(response: Response) -> ApiError {
+    let status = response.status();
+    let header_logid = header_logid(&response);
+    let body = match response.text().await {
+        Ok(body) => body,
+        Err(e) => return e.into(),
+    };
+    let mut err = ApiError::from_response(status, body);
+    if err.logid().is_none() {
+        err.data_mut().logid = header_logid;
+    }
+    err
+}
+
+/// 检查已解析的包裹（也用于 SSE 中的单条数据）
+pub fn check(
+    json: &Value,
+    status: Option<u16>,
+    header_logid: Option<String>,
+    raw: &str,
+) -> Result<(), ApiError> {
+    match json.get("code").and_then(|v| v.as_i64()) {
+        Some(code) if code != 0 => {
+            let msg = json
+                .get("msg")
+                .or_else(|| json.get("message"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown error")
+                .to_string();
+            Err(ApiError::from_coze_code(
+                code as i32,
+                msg,
+                status,
+                Some(raw.to_string()),
+                logid_of(json).or(header_logid),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 包裹中的 `data` 字段；没有时返回整个响应
+pub fn data(json: &Value) -> &Value {
+    json.get("data").unwrap_or(json)
+}
+
+/// 提取 `detail.logid`
+pub fn logid_of(json: &Value) -> Option<String> {
+    json.get("detail")
+        .and_then(|d| d.get("logid"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// 缓冲 JSON 响应体并取出业务 `code`，同时返回一个可再次读取的等价响应。
+/// 重试循环借此识别 HTTP 200 包裹中的限流（4029）；非 JSON（如 SSE 流）响应原样返回
+pub async fn peek_code(response: Response) -> Result<(Response, Option<i64>), ApiError> {
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return Ok((response, None));
+    }
+    let status = response.status();
+    let version = response.version();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+    let code = serde_json::from_slice::<Value>(&body)
+        .ok()
+        .and_then(|json| json.get("code").and_then(|v| v.as_i64()));
+    let mut rebuilt = http::Response::new(body);
+    *rebuilt.status_mut() = status;
+    *rebuilt.version_mut() = version;
+    *rebuilt.headers_mut() = headers;
+    Ok((Response::from(rebuilt), code))
+}
+
+fn header_logid(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get(LOGID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+}
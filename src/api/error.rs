@@ -9,6 +9,12 @@ pub struct ApiErrorData {
     pub status: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_body: Option<String>,
+    /// Coze 业务错误码（响应包裹中的 `code`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<i32>,
+    /// Coze 请求日志 ID，提交工单时需要
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logid: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,12 +39,37 @@ impl ApiErrorData {
             message,
             status,
             raw_body,
+            code: None,
+            logid: None,
         }
     }
+
+    pub fn with_code(mut self, code: i32) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_logid(mut self, logid: Option<String>) -> Self {
+        self.logid = logid;
+        self
+    }
 }
 
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_message(f)?;
+        let d = self.data();
+        match (d.code, &d.logid) {
+            (Some(code), Some(logid)) => write!(f, " (code {code}, logid {logid})"),
+            (Some(code), None) => write!(f, " (code {code})"),
+            (None, Some(logid)) => write!(f, " (logid {logid})"),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl ApiError {
+    fn fmt_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ApiError::NetworkError(d) => write!(f, "Network error: {}", d.message),
             ApiError::TimeoutError(d) => write!(f, "Timeout error: {}", d.message),
@@ -100,6 +131,72 @@ impl From<std::io::Error> for ApiError {
 }
 
 impl ApiError {
+    /// 按 Coze 业务错误码映射错误类型；未知错误码归为 BadRequest
+    pub fn from_coze_code(
+        code: i32,
+        msg: String,
+        status: Option<u16>,
+        raw_body: Option<String>,
+        logid: Option<String>,
+    ) -> Self {
+        let data = |kind: &str| {
+            ApiErrorData::new(kind, msg.clone(), status, raw_body.clone())
+                .with_code(code)
+                .with_logid(logid.clone())
+        };
+        match code {
+            4000 => ApiError::BadRequest(data("bad_request")),
+            4001 => ApiError::AuthenticationError(data("authentication")),
+            4003 => ApiError::AuthorizationError(data("authorization")),
+            4004 => ApiError::NotFound(data("not_found")),
+            4029 => ApiError::RateLimitExceeded(data("rate_limited")),
+            5000 => ApiError::ServerError(data("server_error")),
+            _ => ApiError::BadRequest(data("api_error")),
+        }
+    }
+
+    pub fn data(&self) -> &ApiErrorData {
+        match self {
+            ApiError::NetworkError(d)
+            | ApiError::TimeoutError(d)
+            | ApiError::AuthenticationError(d)
+            | ApiError::AuthorizationError(d)
+            | ApiError::BadRequest(d)
+            | ApiError::NotFound(d)
+            | ApiError::RateLimitExceeded(d)
+            | ApiError::ServerError(d)
+            | ApiError::InvalidResponseFormat(d)
+            | ApiError::SerializationError(d)
+            | ApiError::ConfigError(d) => d,
+        }
+    }
+
+    pub fn data_mut(&mut self) -> &mut ApiErrorData {
+        match self {
+            ApiError::NetworkError(d)
+            | ApiError::TimeoutError(d)
+            | ApiError::AuthenticationError(d)
+            | ApiError::AuthorizationError(d)
+            | ApiError::BadRequest(d)
+            | ApiError::NotFound(d)
+            | ApiError::RateLimitExceeded(d)
+            | ApiError::ServerError(d)
+            | ApiError::InvalidResponseFormat(d)
+            | ApiError::SerializationError(d)
+            | ApiError::ConfigError(d) => d,
+        }
+    }
+
+    /// Coze 业务错误码（若有）
+    pub fn code(&self) -> Option<i32> {
+        self.data().code
+    }
+
+    /// Coze 请求日志 ID（若有）
+    pub fn logid(&self) -> Option<&str> {
+        self.data().logid.as_deref()
+    }
+
     pub fn from_response(status: reqwest::StatusCode, body: String) -> Self {
         let error_message = match serde_json::from_str::<serde_json::Value>(&body) {
             Ok(json) => json
@@ -111,9 +208,20 @@ impl ApiError {
             Err(_) => body.clone(),
         };
 
+        let (coze_code, logid) = match serde_json::from_str::<serde_json::Value>(&body) {
+            Ok(json) => (
+                json.get("code").and_then(|v| v.as_i64()).map(|c| c as i32),
+                crate::api::envelope::logid_of(&json),
+            ),
+            Err(_) => (None, None),
+        };
         let code = status.as_u16();
         let data = |kind: &str| {
-            ApiErrorData::new(kind, error_message.clone(), Some(code), Some(body.clone()))
+            let mut d =
+                ApiErrorData::new(kind, error_message.clone(), Some(code), Some(body.clone()))
+                    .with_logid(logid.clone());
+            d.code = coze_code;
+            d
         };
         match code {
             400 => ApiError::BadRequest(data("bad_request")),
@@ -126,5 +234,18 @@ impl ApiError {
         }
     }
 
-    // Removed unused helper methods (error_code, is_retryable, retry_delay_ms, user_friendly_message)
+    /// 是否属于可重试的瞬时错误（网络、超时、限流、5xx）
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::NetworkError(d) => d.status.is_none(),
+            ApiError::TimeoutError(_) | ApiError::RateLimitExceeded(_) => true,
+            ApiError::ServerError(d) => d
+                .status
+                .map(|s| matches!(s, 500 | 502 | 503 | 504))
+                .unwrap_or(true),
+            _ => false,
+        }
+    }
+
+    // Removed unused helper methods (retry_delay_ms, user_friendly_message)
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// 已上传文件信息（`/v1/files/upload` 与 `/v1/files/retrieve` 的 data）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInfo {
+    /// 文件 ID，可用于多模态消息、知识库图片与知识库图标
+    pub id: String,
+    /// 文件大小（字节）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    /// 上传时间戳（秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    /// 文件名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+}
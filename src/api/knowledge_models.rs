@@ -1,10 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // NOTE:
 // 当前文件包含未来可能开放/工具层尚未调用的模型结构体与辅助 new 方法。
 // 为减少编译警告噪音并保持后续扩展便利，暂时允许 dead_code。
 // 当相关功能落地后，可逐步移除 allow 并删除未使用结构。
-// (HashMap usage removed after pruning chat models)
 
 // Pruned unused chat & ancillary model structs to reduce warnings.
 
@@ -40,6 +40,17 @@ impl SourceInfo {
             source_file_id: None,
         }
     }
+
+    /// 在线网页（document_source = 1），仅支持文本类知识库
+    pub fn web_page(web_url: String) -> Self {
+        Self {
+            file_base64: None,
+            file_type: None,
+            web_url: Some(web_url),
+            document_source: Some(1),
+            source_file_id: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,8 +59,9 @@ pub struct DocumentBaseCn {
     pub source_info: SourceInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub caption: Option<String>,
+    /// 在线网页的自动更新规则，本地文件不适用
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub update_rule: Option<serde_json::Value>,
+    pub update_rule: Option<DocumentUpdateRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -111,11 +123,299 @@ pub struct KnowledgeDocumentUploadResponseCn {
     pub code: i32,
     pub msg: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub document_infos: Option<Vec<serde_json::Value>>,
+    pub document_infos: Option<Vec<KnowledgeDocumentInfo>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detail: Option<serde_json::Value>,
 }
 
+// === 知识库文件（文档）查看 / 修改 / 删除 ===
+
+/// 知识库文件信息（上传与文件列表接口的 document_infos 条目）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeDocumentInfo {
+    /// 上传接口的响应偶尔缺少该字段，缺失时为空串
+    #[serde(default)]
+    pub document_id: String,
+    #[serde(default)]
+    pub name: String,
+    /// 处理状态：0 处理中，1 处理完毕，9 处理失败
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// 处理失败时的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_descript: Option<String>,
+    /// 文件格式类型：0 文本、1 表格、2 图片
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format_type: Option<i32>,
+    /// 文件格式（扩展名），如 pdf、txt
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub file_type: Option<String>,
+    /// 上传方式：0 本地文件，1 在线网页
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_type: Option<i32>,
+    /// 在线网页的地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_url: Option<String>,
+    /// 文件大小（字节）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub size: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub char_count: Option<u64>,
+    /// 分段数量
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub slice_count: Option<u64>,
+    /// 被对话命中的次数
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub hit_count: Option<u64>,
+    /// 在线网页的更新方式：0 不自动更新，1 自动更新
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<i32>,
+    /// 自动更新的间隔（小时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_interval: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk_strategy: Option<serde_json::Value>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub create_time: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub update_time: Option<u64>,
+    /// 尚未映射的字段
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// 文件处理状态（0 处理中，1 处理完毕，9 处理失败）的中文描述，文件列表与处理进度共用
+pub fn document_status_text(status: Option<i32>) -> &'static str {
+    match status {
+        Some(0) => "处理中",
+        Some(1) => "处理完毕",
+        Some(9) => "处理失败",
+        _ => "未知",
+    }
+}
+
+impl KnowledgeDocumentInfo {
+    /// 处理状态的中文描述
+    pub fn status_text(&self) -> &'static str {
+        document_status_text(self.status)
+    }
+}
+
+/// 查看知识库文件列表请求（POST /open_api/knowledge/document/list）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDocumentsRequest {
+    pub dataset_id: String,
+    /// 页码，从 1 开始
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// 每页数量
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+}
+
+impl ListDocumentsRequest {
+    pub fn new(dataset_id: impl Into<String>) -> Self {
+        Self {
+            dataset_id: dataset_id.into(),
+            page: None,
+            size: None,
+        }
+    }
+
+    pub fn with_page(mut self, page: u32, size: u32) -> Self {
+        self.page = Some(page);
+        self.size = Some(size);
+        self
+    }
+}
+
+/// 知识库文件列表响应（字段与 code / msg 同级，没有 data 包裹）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListDocumentsResponse {
+    #[serde(default)]
+    pub document_infos: Vec<KnowledgeDocumentInfo>,
+    #[serde(
+        default,
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub total: Option<u64>,
+}
+
+/// 在线网页的更新规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentUpdateRule {
+    /// 0 不自动更新，1 自动更新
+    pub update_type: i32,
+    /// 自动更新的间隔（小时），最小 24
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_interval: Option<i32>,
+}
+
+impl DocumentUpdateRule {
+    /// 不自动更新
+    pub fn no_auto_update() -> Self {
+        Self {
+            update_type: 0,
+            update_interval: None,
+        }
+    }
+
+    /// 每隔 `hours` 小时自动更新
+    pub fn every_hours(hours: i32) -> Self {
+        Self {
+            update_type: 1,
+            update_interval: Some(hours),
+        }
+    }
+}
+
+/// 修改知识库文件请求（POST /open_api/knowledge/document/update），只提交设置过的字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDocumentRequest {
+    pub document_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_name: Option<String>,
+    /// 仅对在线网页生效
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_rule: Option<DocumentUpdateRule>,
+}
+
+impl UpdateDocumentRequest {
+    pub fn new(document_id: impl Into<String>) -> Self {
+        Self {
+            document_id: document_id.into(),
+            document_name: None,
+            update_rule: None,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.document_name = Some(name.into());
+        self
+    }
+
+    pub fn with_update_rule(mut self, rule: DocumentUpdateRule) -> Self {
+        self.update_rule = Some(rule);
+        self
+    }
+
+    /// 除 document_id 外没有任何要修改的字段
+    pub fn is_empty(&self) -> bool {
+        self.document_name.is_none() && self.update_rule.is_none()
+    }
+}
+
+/// 单次删除请求最多包含的文件数
+pub const MAX_DELETE_DOCUMENTS: usize = 100;
+
+/// 删除知识库文件请求（POST /open_api/knowledge/document/delete）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteDocumentsRequest {
+    pub document_ids: Vec<String>,
+}
+
+impl DeleteDocumentsRequest {
+    pub fn new(document_ids: Vec<String>) -> Self {
+        Self { document_ids }
+    }
+}
+
+/// 查看文件上传进度请求（POST /v1/datasets/:dataset_id/process）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentProgressRequest {
+    pub document_ids: Vec<String>,
+}
+
+/// 文件上传进度响应的 data 字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentProgressResponse {
+    #[serde(default)]
+    pub data: Vec<DocumentProgress>,
+}
+
+/// 单个文件的切片处理进度
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentProgress {
+    pub document_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub document_name: Option<String>,
+    /// 处理状态：0 处理中，1 处理完毕，9 处理失败
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// 处理失败时的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_descript: Option<String>,
+    /// 处理进度百分比（0-100）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub progress: Option<u64>,
+    /// 预计剩余时间（秒）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub remaining_time: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub size: Option<u64>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub file_type: Option<String>,
+    /// 在线网页或本地文件的地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_interval: Option<i32>,
+}
+
+impl DocumentProgress {
+    /// 是否仍在处理（未返回状态时按处理中对待）
+    pub fn is_processing(&self) -> bool {
+        matches!(self.status, Some(0) | None)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.status == Some(9)
+    }
+
+    /// 处理状态的中文描述
+    pub fn status_text(&self) -> &'static str {
+        document_status_text(self.status)
+    }
+}
+
 // === 创建知识库 API 相关模型 (基于 v1/datasets 规范) ===
 
 /// 创建知识库请求 (符合 POST /v1/datasets API 文档)
@@ -168,6 +468,41 @@ impl CreateDatasetRequest {
     }
 }
 
+/// 修改知识库信息请求（PUT /v1/datasets/:dataset_id）
+///
+/// 接口为全量更新：未设置的 description / file_id 会恢复默认，调用方需先补全当前值。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDatasetRequest {
+    /// 知识库名称，长度不超过 100 个字符
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 知识库图标，需传入【上传文件】API 返回的 file_id
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+}
+
+impl UpdateDatasetRequest {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            file_id: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// 设置知识库图标
+    pub fn with_icon(mut self, file_id: impl Into<String>) -> Self {
+        self.file_id = Some(file_id.into());
+        self
+    }
+}
+
 /// 创建知识库响应数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateDatasetOpenApiData {
@@ -0,0 +1,91 @@
+use crate::api::chat_models::ChatMessage;
+use serde::{Deserialize, Serialize};
+
+/// 会话中的一条消息：在 `ChatMessage`（角色、内容、元数据）之上附带服务端字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationMessage {
+    /// 消息 ID
+    pub id: String,
+    pub conversation_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_id: Option<String>,
+    /// 所属对话（chat）的 ID；手动创建的消息为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section_id: Option<String>,
+    /// 消息类型：question / answer / function_call / tool_output 等
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub message_type: Option<String>,
+    /// 创建时间戳（秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<i64>,
+    #[serde(flatten)]
+    pub message: ChatMessage,
+}
+
+/// 查看消息列表请求（conversation_id 通过查询参数传递）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListMessagesRequest {
+    /// 排序方式：desc（默认，从新到旧）/ asc
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
+    /// 只返回该对话（chat）中的消息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    /// 游标：返回此消息之前的消息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_id: Option<String>,
+    /// 游标：返回此消息之后的消息
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_id: Option<String>,
+    /// 每页数量，1~50，默认 50
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// 查看消息列表响应；`first_id` / `last_id` 可作为下一页的 `after_id` / `before_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMessagesResponse {
+    #[serde(default)]
+    pub data: Vec<ConversationMessage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_id: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl ListMessagesRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_order(mut self, order: String) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn with_chat_id(mut self, chat_id: String) -> Self {
+        self.chat_id = Some(chat_id);
+        self
+    }
+
+    pub fn with_before_id(mut self, before_id: String) -> Self {
+        self.before_id = Some(before_id);
+        self
+    }
+
+    pub fn with_after_id(mut self, after_id: String) -> Self {
+        self.after_id = Some(after_id);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
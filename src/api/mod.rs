@@ -1,9 +1,18 @@
 pub mod bot_models;
 pub mod chat_models;
 pub mod client;
+pub mod conversation_models;
 pub mod endpoints;
+pub mod envelope;
 pub mod error;
+pub mod file_models;
 pub mod knowledge_models;
+pub mod message_models;
+pub mod pagination;
+pub mod rate_limit;
+pub mod retry;
+pub mod sse;
+pub mod workflow_models;
 
 pub use client::CozeApiClient;
 // pub use knowledge_models::*; // 注释掉未使用的导入
@@ -0,0 +1,201 @@
+//! 列表接口的通用自动翻页
+//!
+//! Coze 的列表接口有两种翻页方式：页码（`page_num` + `page_size`）与消息列表的
+//! ID 游标（`before_id` / `after_id`）。`Paginator` 把任意列表接口包装为按页
+//! 产出的异步流，调用方可以逐页 / 逐条消费，也可以按条数上限一次性收集。
+use crate::api::error::{ApiError, ApiErrorData};
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// 某一页的位置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PageToken {
+    /// 页码（从 1 开始）
+    Page(u32),
+    /// 查看此消息之前的消息
+    BeforeId(String),
+    /// 查看此消息之后的消息
+    AfterId(String),
+}
+
+/// 翻页游标：页位置加上该页中需要跳过的条数
+///
+/// 按条数上限截断时，剩余的条目仍在同一页中，`skip` 记录已返回的部分，
+/// 继续翻页时既不会遗漏也不会重复。序列化为 `{"page": 3}`、
+/// `{"before_id": "...", "skip": 5}` 这样的对象，可原样传回工具；
+/// 没有页位置（`{}`、`{"skip": 5}`）表示列表开头。
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PageCursor {
+    #[serde(flatten)]
+    pub token: Option<PageToken>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub skip: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl PageCursor {
+    /// 指向某一页开头的游标
+    pub fn new(token: PageToken) -> Self {
+        Self {
+            token: Some(token),
+            skip: 0,
+        }
+    }
+
+    /// 页码分页的页码；没有页位置时为第 1 页
+    pub fn page_num(token: Option<PageToken>) -> Result<u32, ApiError> {
+        match token {
+            None => Ok(1),
+            Some(PageToken::Page(n)) if n >= 1 => Ok(n),
+            Some(other) => Err(ApiError::BadRequest(ApiErrorData::new(
+                "bad_request",
+                format!("cursor {other:?} is not a page number cursor"),
+                None,
+                None,
+            ))),
+        }
+    }
+}
+
+/// 列表接口返回的一页数据
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// 下一页位置；None 表示已是最后一页
+    pub next: Option<PageToken>,
+    /// 接口返回的总数（部分接口只返回 has_more）
+    pub total: Option<u64>,
+}
+
+impl<T> Page<T> {
+    /// 按页码分页：由 total 或 has_more 推算是否还有下一页
+    pub fn numbered(
+        items: Vec<T>,
+        page_num: u32,
+        page_size: u32,
+        total: Option<u64>,
+        has_more: Option<bool>,
+    ) -> Self {
+        let more = match (has_more, total) {
+            (Some(has_more), _) => has_more,
+            (None, Some(total)) => u64::from(page_num) * u64::from(page_size) < total,
+            // 两者都没有时，以是否取满一页判断
+            (None, None) => items.len() as u32 >= page_size,
+        };
+        Self {
+            items,
+            next: more.then(|| PageToken::Page(page_num + 1)),
+            total,
+        }
+    }
+}
+
+/// 按条数上限收集的结果
+#[derive(Debug, Clone)]
+pub struct Collected<T> {
+    pub items: Vec<T>,
+    /// 继续翻页的游标；None 表示已取完
+    pub next: Option<PageCursor>,
+    pub total: Option<u64>,
+}
+
+type PageStream<'a, T> = BoxStream<'a, Result<(PageCursor, Page<T>), ApiError>>;
+
+/// 自动翻页的异步流，每次产出（该页游标, 该页数据）
+///
+/// 只有在被轮询时才请求下一页；某一页请求失败时产出错误并结束。
+pub struct Paginator<'a, T> {
+    pages: PageStream<'a, T>,
+}
+
+impl<'a, T: Send + 'a> Paginator<'a, T> {
+    /// 从 `start` 开始，用 `fetch` 逐页请求，直到没有下一页；`fetch` 收到 None 时请求列表开头
+    pub fn new<F, Fut>(start: PageCursor, mut fetch: F) -> Self
+    where
+        F: FnMut(Option<PageToken>) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<Page<T>, ApiError>> + Send + 'a,
+    {
+        let pages = stream::try_unfold(Some(start), move |cursor| {
+            let request = cursor.as_ref().map(|c| fetch(c.token.clone()));
+            async move {
+                let (Some(cursor), Some(request)) = (cursor, request) else {
+                    return Ok(None);
+                };
+                let mut page = request.await?;
+                // 空页视为结束，避免接口误报 has_more 时无限翻页
+                if page.items.is_empty() {
+                    page.next = None;
+                }
+                let next = page.next.clone().map(PageCursor::new);
+                page.items.drain(..cursor.skip.min(page.items.len()));
+                Ok(Some(((cursor, page), next)))
+            }
+        })
+        .boxed();
+        Self { pages }
+    }
+
+    /// 逐条产出所有页的条目
+    pub fn items(self) -> BoxStream<'a, Result<T, ApiError>> {
+        self.pages
+            .map_ok(|(_, page)| stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+
+    /// 收集最多 `max_pages` 页、最多 `limit` 条（None 表示不限），并返回继续翻页的游标
+    pub async fn collect(
+        mut self,
+        limit: Option<usize>,
+        max_pages: Option<usize>,
+    ) -> Result<Collected<T>, ApiError> {
+        let mut items = Vec::new();
+        let mut next = None;
+        let mut total = None;
+        let mut pages = 0;
+        while let Some((cursor, page)) = self.pages.try_next().await? {
+            pages += 1;
+            total = page.total.or(total);
+            let page_len = page.items.len();
+            let remaining = limit.map(|l| l.saturating_sub(items.len()));
+            match remaining {
+                Some(remaining) if page_len > remaining => {
+                    // 在页中截断：下次从同一页的剩余部分继续
+                    items.extend(page.items.into_iter().take(remaining));
+                    next = Some(PageCursor {
+                        token: cursor.token,
+                        skip: cursor.skip + remaining,
+                    });
+                    break;
+                }
+                _ => {
+                    items.extend(page.items);
+                    next = page.next.map(PageCursor::new);
+                }
+            }
+            if next.is_none()
+                || limit.is_some_and(|l| items.len() >= l)
+                || max_pages.is_some_and(|m| pages >= m)
+            {
+                break;
+            }
+        }
+        Ok(Collected { items, next, total })
+    }
+}
+
+impl<T> Stream for Paginator<'_, T> {
+    type Item = Result<(PageCursor, Page<T>), ApiError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.pages.as_mut().poll_next(cx)
+    }
+}
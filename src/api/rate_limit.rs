@@ -0,0 +1,131 @@
+// 客户端限流：每个端点分组一个令牌桶 + 并发信号量，超限时排队等待而不是直接失败
+use crate::api::endpoints::ENDPOINT_GROUPS;
+use crate::api::error::{ApiError, ApiErrorData};
+use crate::utils::config::RateLimitConfig;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, capacity: f64) -> Self {
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last: Instant::now(),
+        }
+    }
+
+    /// 预约一个令牌并返回需要等待的时长；令牌不足时余额记为负数，后来者顺延，保证先到先得
+    fn reserve(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct GroupLimiter {
+    bucket: Option<Mutex<TokenBucket>>,
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl GroupLimiter {
+    fn new(group: &str, config: &RateLimitConfig) -> Result<Self, ApiError> {
+        let bucket = match config.requests_per_second {
+            Some(rate) if rate.is_finite() && rate > 0.0 => {
+                let burst = config.burst.unwrap_or(1).max(1);
+                Some(Mutex::new(TokenBucket::new(rate, burst as f64)))
+            }
+            Some(rate) => {
+                return Err(invalid(format!(
+                    "rate_limits.{group}.requests_per_second must be positive, got {rate}"
+                )))
+            }
+            None => None,
+        };
+        let semaphore = match config.max_concurrency {
+            Some(0) => {
+                return Err(invalid(format!(
+                    "rate_limits.{group}.max_concurrency must be at least 1"
+                )))
+            }
+            Some(n) => Some(Arc::new(Semaphore::new(n))),
+            None => None,
+        };
+        Ok(Self { bucket, semaphore })
+    }
+}
+
+/// 持有期间占用所在分组的一个并发名额
+#[derive(Debug)]
+pub struct RatePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// 按端点分组的限流器，分组名见 `endpoints::ENDPOINT_GROUPS`
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    groups: HashMap<&'static str, GroupLimiter>,
+}
+
+impl RateLimiter {
+    /// 未单独配置的分组套用 `default`（各分组独立计数）；两者都没有则不限制
+    pub fn new(config: &BTreeMap<String, RateLimitConfig>) -> Result<Self, ApiError> {
+        if let Some(unknown) = config
+            .keys()
+            .find(|k| !ENDPOINT_GROUPS.contains(&k.as_str()))
+        {
+            return Err(invalid(format!(
+                "unknown rate limit group: {unknown} (expected one of: {})",
+                ENDPOINT_GROUPS.join(", ")
+            )));
+        }
+        let mut groups = HashMap::new();
+        for group in ENDPOINT_GROUPS {
+            if let Some(c) = config.get(*group).or_else(|| config.get("default")) {
+                groups.insert(*group, GroupLimiter::new(group, c)?);
+            }
+        }
+        Ok(Self { groups })
+    }
+
+    /// 等待令牌与并发名额；请求完成（permit 被 drop）后释放并发名额
+    pub async fn acquire(&self, group: &str) -> RatePermit {
+        let Some(limiter) = self.groups.get(group) else {
+            return RatePermit { _permit: None };
+        };
+        if let Some(bucket) = &limiter.bucket {
+            let wait = bucket.lock().unwrap().reserve(Instant::now());
+            if !wait.is_zero() {
+                tracing::debug!("rate limit: {} request queued for {:?}", group, wait);
+                tokio::time::sleep(wait).await;
+            }
+        }
+        let permit = match &limiter.semaphore {
+            // 信号量从不关闭，acquire_owned 不会失败
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        RatePermit { _permit: permit }
+    }
+}
+
+fn invalid(message: String) -> ApiError {
+    ApiError::ConfigError(ApiErrorData::new("config", message, None, None))
+}
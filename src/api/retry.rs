@@ -0,0 +1,62 @@
+// 请求重试策略：指数退避 + 抖动，429 时遵循 Retry-After
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::time::{Duration, SystemTime};
+
+/// 首次重试的基础等待时间，之后每次翻倍
+pub const BASE_DELAY: Duration = Duration::from_millis(200);
+/// 单次退避等待上限
+pub const MAX_DELAY: Duration = Duration::from_secs(10);
+/// 服务端要求的 Retry-After 超过该值时不再等待，直接返回限流响应
+pub const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// 请求是否允许自动重试
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryMode {
+    /// 按 HTTP 方法判断：仅幂等方法（GET/HEAD/PUT/DELETE/OPTIONS）重试
+    Auto,
+    /// 调用方显式声明可安全重试（如以 POST 实现的只读查询）
+    Safe,
+    /// 从不重试
+    Never,
+}
+
+impl RetryMode {
+    pub fn allows(self, method: &Method) -> bool {
+        match self {
+            RetryMode::Auto => method.is_idempotent(),
+            RetryMode::Safe => true,
+            RetryMode::Never => false,
+        }
+    }
+}
+
+/// 值得重试的瞬时状态码：限流与网关/服务暂不可用
+pub fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 500 | 502 | 503 | 504)
+}
+
+/// HTTP 200 包裹中表示限流的业务码，与 429 同样重试
+pub const RATE_LIMITED_CODE: i64 = 4029;
+
+/// 第 `attempt` 次重试（从 0 开始）的等待时间：指数增长并封顶，取后一半区间随机抖动
+pub fn backoff_delay(attempt: u32) -> Duration {
+    let exp = BASE_DELAY
+        .saturating_mul(1u32 << attempt.min(16))
+        .min(MAX_DELAY);
+    let half = exp / 2;
+    half + half.mul_f64(rand::random::<f64>())
+}
+
+/// 解析 `Retry-After` 头：支持秒数与 HTTP-date 两种格式
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(
+        at.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
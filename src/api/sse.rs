@@ -0,0 +1,130 @@
+// 增量 SSE (Server-Sent Events) 解码器：按字节缓冲，事件可跨多个网络块，也可在一个块内批量到达
+use std::time::Duration;
+
+/// 一条完整的 SSE 事件
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SseEvent {
+    /// `event:` 字段，未提供时为 None（规范中的默认类型 "message"）
+    pub event: Option<String>,
+    /// 多行 `data:` 以 `\n` 拼接
+    pub data: String,
+    /// 最近一次出现的 `id:`（跨事件保留）
+    pub id: Option<String>,
+    /// 本事件携带的 `retry:` 重连间隔
+    pub retry: Option<Duration>,
+}
+
+/// 按 WHATWG 事件流规范解析：行结束符支持 LF / CRLF / CR，`:` 开头为注释，空行分派事件
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+    // 上一块以 CR 结尾时，下一块开头的 LF 属于同一个换行
+    skip_lf: bool,
+    started: bool,
+    event: Option<String>,
+    data: Vec<String>,
+    last_id: Option<String>,
+    retry: Option<Duration>,
+}
+
+impl SseDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 送入一个网络块，返回其中已完整的事件；不完整的行留在缓冲区等待后续数据
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        let mut chunk = chunk;
+        if self.skip_lf && !chunk.is_empty() {
+            if chunk[0] == b'\n' {
+                chunk = &chunk[1..];
+            }
+            self.skip_lf = false;
+        }
+        self.buf.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        let mut start = 0;
+        let mut i = 0;
+        while i < self.buf.len() {
+            match self.buf[i] {
+                b'\n' => {
+                    self.take_line(start, i, &mut events);
+                    i += 1;
+                }
+                b'\r' => {
+                    self.take_line(start, i, &mut events);
+                    i += 1;
+                    match self.buf.get(i) {
+                        Some(b'\n') => i += 1,
+                        Some(_) => {}
+                        None => self.skip_lf = true,
+                    }
+                }
+                _ => {
+                    i += 1;
+                    continue;
+                }
+            }
+            start = i;
+        }
+        self.buf.drain(..start);
+        events
+    }
+
+    fn take_line(&mut self, start: usize, end: usize, events: &mut Vec<SseEvent>) {
+        let mut line = &self.buf[start..end];
+        // 流开头的 UTF-8 BOM 忽略
+        if !self.started {
+            self.started = true;
+            line = line.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(line);
+        }
+        // 整行到齐后再解码，避免多字节字符被网络块截断
+        let line = String::from_utf8_lossy(line).into_owned();
+        if let Some(event) = self.process_line(&line) {
+            events.push(event);
+        }
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => self.data.push(value.to_string()),
+            // 含 NUL 的 id 按规范忽略
+            "id" if !value.contains('\0') => self.last_id = Some(value.to_string()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(ms) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(ms));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// 空行：有 data 时产出事件，随后重置 event / data / retry
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event = self.event.take();
+        let retry = self.retry.take();
+        if self.data.is_empty() {
+            return None;
+        }
+        let data = std::mem::take(&mut self.data).join("\n");
+        Some(SseEvent {
+            event,
+            data,
+            id: self.last_id.clone(),
+            retry,
+        })
+    }
+}
@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 工作流类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WorkflowMode {
+    /// 工作流
+    Workflow,
+    /// 对话流
+    Chatflow,
+}
+
+/// 工作流创建者
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowCreator {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// 工作流信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowInfo {
+    /// 工作流 ID
+    pub workflow_id: String,
+    /// 工作流名称
+    pub workflow_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// 所属应用 ID（资源库中的工作流为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creator: Option<WorkflowCreator>,
+    /// 创建时间戳（秒）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub created_at: Option<u64>,
+    /// 更新时间戳（秒）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub updated_at: Option<u64>,
+}
+
+/// 工作流列表查询参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListWorkflowsRequest {
+    /// 工作空间 ID（必选）
+    pub workspace_id: String,
+    /// 工作流类型筛选（可选，默认全部）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_mode: Option<WorkflowMode>,
+    /// 只列出该应用下的工作流（可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    /// 分页页码（默认 1）
+    pub page_num: u32,
+    /// 每页数据量（默认 20，最大 30）
+    pub page_size: u32,
+}
+
+/// 工作流列表数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListWorkflowsData {
+    #[serde(default)]
+    pub items: Vec<WorkflowInfo>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+/// 执行工作流请求（同步与流式共用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunWorkflowRequest {
+    pub workflow_id: String,
+    /// 开始节点的输入参数
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, serde_json::Value>,
+    /// 工作流中包含数据库 / 变量等节点时需关联的 Bot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_id: Option<String>,
+    /// 应用中的工作流需指定应用 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_id: Option<String>,
+    /// 额外字段（如 latitude / longitude / user_id）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext: Option<HashMap<String, String>>,
+    /// 异步执行：立即返回 execute_id（仅同步接口有效）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_async: Option<bool>,
+}
+
+/// 工作流执行消耗
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowUsage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<u64>,
+}
+
+/// 同步执行工作流的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunWorkflowResponse {
+    /// 结束节点输出，JSON 字符串
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// 异步执行时返回的执行 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execute_id: Option<String>,
+    /// 扣子平台上的调试页面
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<WorkflowUsage>,
+}
+
+/// 工作流执行状态（run_histories 的 execute_status）
+pub mod execute_status {
+    pub const RUNNING: &str = "Running";
+    pub const SUCCESS: &str = "Success";
+    pub const FAIL: &str = "Fail";
+}
+
+/// 工作流执行记录（异步执行时通过 execute_id 查询）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunHistory {
+    pub execute_id: String,
+    /// 执行状态：Running / Success / Fail
+    pub execute_status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connector_id: Option<String>,
+    /// 执行方式：0 同步，1 流式，2 异步
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_mode: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logid: Option<String>,
+    /// 开始时间戳（秒）
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub create_time: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub update_time: Option<u64>,
+    /// 结束节点输出，JSON 字符串（执行中为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// 输出超过上限时被截断
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_output_trimmed: Option<bool>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub token: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<String>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::api::deserialize_optional_u64_from_string_or_number"
+    )]
+    pub error_code: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_url: Option<String>,
+}
+
+impl WorkflowRunHistory {
+    pub fn is_running(&self) -> bool {
+        self.execute_status == execute_status::RUNNING
+    }
+}
+
+/// 恢复中断的工作流请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeWorkflowRequest {
+    pub workflow_id: String,
+    /// 中断事件 ID（Interrupt 事件的 interrupt_data.event_id）
+    pub event_id: String,
+    /// 用户对中断的回复
+    pub resume_data: String,
+    /// 中断类型（Interrupt 事件的 interrupt_data.type）
+    pub interrupt_type: i64,
+}
+
+/// 流式工作流事件类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum WorkflowEventType {
+    /// 节点输出的消息
+    Message,
+    /// 执行出错
+    Error,
+    /// 执行结束
+    Done,
+    /// 等待用户输入（问答节点等），需调用 resume 继续
+    Interrupt,
+    /// 心跳
+    #[serde(rename = "PING")]
+    Ping,
+    /// 未识别的事件
+    #[serde(other)]
+    Other,
+}
+
+/// 中断信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowInterruptData {
+    pub event_id: String,
+    #[serde(rename = "type")]
+    pub interrupt_type: i64,
+}
+
+/// 流式工作流事件（各事件类型的字段合并为一个结构，未出现的字段为空）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowStreamEvent {
+    pub event: WorkflowEventType,
+    /// SSE 事件 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Message：消息内容
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Message / Interrupt：输出节点名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_seq_id: Option<String>,
+    /// Message：节点是否已输出完毕
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_is_finish: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<WorkflowUsage>,
+    /// Interrupt：中断信息
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interrupt_data: Option<WorkflowInterruptData>,
+    /// Done：调试页面
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub debug_url: Option<String>,
+}
+
+impl ListWorkflowsRequest {
+    pub fn new(workspace_id: String) -> Self {
+        Self {
+            workspace_id,
+            workflow_mode: None,
+            app_id: None,
+            page_num: 1,
+            page_size: 20,
+        }
+    }
+
+    pub fn with_workflow_mode(mut self, mode: WorkflowMode) -> Self {
+        self.workflow_mode = Some(mode);
+        self
+    }
+
+    pub fn with_app_id(mut self, app_id: String) -> Self {
+        self.app_id = Some(app_id);
+        self
+    }
+
+    pub fn with_page(mut self, page_num: u32, page_size: u32) -> Self {
+        self.page_num = page_num;
+        self.page_size = page_size;
+        self
+    }
+
+    /// 构建查询参数字符串
+    pub fn to_query_params(&self) -> String {
+        let mut params = vec![
+            format!("workspace_id={}", urlencoding::encode(&self.workspace_id)),
+            format!("page_num={}", self.page_num),
+            format!("page_size={}", self.page_size),
+        ];
+        if let Some(mode) = &self.workflow_mode {
+            let mode = match mode {
+                WorkflowMode::Workflow => "workflow",
+                WorkflowMode::Chatflow => "chatflow",
+            };
+            params.push(format!("workflow_mode={mode}"));
+        }
+        if let Some(app_id) = &self.app_id {
+            params.push(format!("app_id={}", urlencoding::encode(app_id)));
+        }
+        params.join("&")
+    }
+}
+
+impl RunWorkflowRequest {
+    pub fn new(workflow_id: String) -> Self {
+        Self {
+            workflow_id,
+            parameters: HashMap::new(),
+            bot_id: None,
+            app_id: None,
+            ext: None,
+            is_async: None,
+        }
+    }
+
+    pub fn with_parameters(mut self, parameters: HashMap<String, serde_json::Value>) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    pub fn with_bot_id(mut self, bot_id: String) -> Self {
+        self.bot_id = Some(bot_id);
+        self
+    }
+
+    pub fn with_app_id(mut self, app_id: String) -> Self {
+        self.app_id = Some(app_id);
+        self
+    }
+
+    pub fn with_ext(mut self, ext: HashMap<String, String>) -> Self {
+        self.ext = Some(ext);
+        self
+    }
+
+    pub fn with_async(mut self, is_async: bool) -> Self {
+        self.is_async = Some(is_async);
+        self
+    }
+}
+
+impl ResumeWorkflowRequest {
+    pub fn new(
+        workflow_id: String,
+        event_id: String,
+        resume_data: String,
+        interrupt_type: i64,
+    ) -> Self {
+        Self {
+            workflow_id,
+            event_id,
+            resume_data,
+            interrupt_type,
+        }
+    }
+}
@@ -90,7 +90,23 @@ impl KnowledgeManager {
             .await
     }
 
+    /// 修改知识库名称、描述与图标（全量更新）
+    pub async fn update_dataset(
+        &self,
+        dataset_id: &str,
+        request: &crate::api::knowledge_models::UpdateDatasetRequest,
+    ) -> Result<(), ApiError> {
+        self.client.update_dataset(dataset_id, request).await
+    }
+
+    /// 删除知识库及其中的文件
+    pub async fn delete_dataset(&self, dataset_id: &str) -> Result<(), ApiError> {
+        self.client.delete_dataset(dataset_id).await
+    }
+
     /// Create knowledge base with permission
+    ///
+    /// `permission` 目前不被 API 支持，传入时返回错误
     pub async fn create_knowledge_base_with_permission(
         &self,
         name: &str,
@@ -2,3 +2,5 @@ pub mod api;
 pub mod knowledge;
 pub mod models;
 pub mod tools;
+pub mod transport;
+pub mod utils;
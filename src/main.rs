@@ -1,8 +1,3 @@
-mod api;
-// knowledge module removed after pruning
-mod models;
-mod tools;
-
 use rmcp::{
     handler::server::ServerHandler,
     model::{
@@ -13,70 +8,239 @@ use rmcp::{
     ErrorData as McpError,
 };
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::env;
 use std::sync::Arc;
 use tracing::info;
 
-use crate::api::endpoints::COZE_BASE_URL;
-use crate::api::CozeApiClient;
-use crate::tools::coze_tools::CozeTools;
+use coze_mcp_server::api::bot_models::{BotConfigFile, API_CONNECTOR_ID};
+use coze_mcp_server::api::rate_limit::RateLimiter;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::bot_config::{self, ImportTarget};
+use coze_mcp_server::tools::context::{bearer_token, SessionAccess, SessionContext};
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use coze_mcp_server::tools::local_files::LocalFiles;
+use coze_mcp_server::tools::progress::ProgressNotifier;
+use coze_mcp_server::transport;
+use coze_mcp_server::utils::config::{CliArgs, Config, CozeConfig, TransportType};
+use coze_mcp_server::utils::logging::init_logging;
 
 #[derive(Clone)]
 pub struct CozeServer {
-    _coze_client: Arc<CozeApiClient>,
-    tools: Arc<CozeTools>,
-    _default_space_id: String,
+    // 启动时生效的配置（含进程级凭证），仅在会话未提供自己的 token 且 `access` 允许时使用其 token
+    coze_config: CozeConfig,
+    active_profile: Option<String>,
+    profiles: Arc<BTreeMap<String, CozeConfig>>,
+    // 进程级限流器：所有会话、所有 token 的客户端共享同一份限流预算
+    limiter: Arc<RateLimiter>,
+    // 工具按路径访问本地文件的范围，由传输方式与 server.file_root 决定
+    local_files: LocalFiles,
+    // 会话可使用的进程级能力（如回退到进程级 token），由传输方式与 server 配置决定
+    access: SessionAccess,
+    // 当前 MCP 会话的凭证与 profile；HTTP/SSE 模式下每个会话一份
+    session: Arc<SessionContext>,
 }
 
 impl CozeServer {
-    pub fn new(
-        api_base_url: String,
-        api_token: String,
-        default_space_id: String,
-    ) -> Result<Self, Box<dyn std::error::Error>> {
-        let coze_client_instance = CozeApiClient::new(api_base_url, api_token)?;
-        let coze_client = Arc::new(coze_client_instance);
-        let tools = Arc::new(CozeTools::new(
-            coze_client.clone(),
-            default_space_id.clone(),
-        ));
+    pub fn new(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let coze_config = config.active_coze()?;
+        // 提前构建一次客户端以校验配置（如非法超时）
+        CozeApiClient::from_config(&coze_config)?;
+        let active_profile = config.profile.clone();
+        let profiles = Arc::new(config.resolved_profiles());
+        let limiter = Arc::new(RateLimiter::new(&coze_config.rate_limits)?);
+        let local_files = LocalFiles::for_server(&config.server)?;
+        let access = SessionAccess::for_server(&config.server);
+        let session = Arc::new(
+            SessionContext::with_profiles(
+                coze_config.clone(),
+                active_profile.clone(),
+                profiles.clone(),
+            )
+            .with_limiter(limiter.clone())
+            .with_local_files(local_files.clone())
+            .with_access(access.clone()),
+        );
 
         Ok(Self {
-            _coze_client: coze_client,
-            tools,
-            _default_space_id: default_space_id,
+            coze_config,
+            active_profile,
+            profiles,
+            limiter,
+            local_files,
+            access,
+            session,
         })
     }
+
+    /// 为新的 MCP 会话创建实例：共享进程级配置，但拥有独立的会话凭证与 profile
+    pub fn for_session(&self) -> Self {
+        Self {
+            session: Arc::new(
+                SessionContext::with_profiles(
+                    self.coze_config.clone(),
+                    self.active_profile.clone(),
+                    self.profiles.clone(),
+                )
+                .with_limiter(self.limiter.clone())
+                .with_local_files(self.local_files.clone())
+                .with_access(self.access.clone()),
+            ),
+            ..self.clone()
+        }
+    }
+
+    /// 解析本次调用使用的工具实例：请求头 Bearer token > configure 工具 > profile / 进程级凭证（远程会话需显式开启）
+    async fn resolve_tools(
+        &self,
+        context: &RequestContext<RoleServer>,
+    ) -> Result<Arc<CozeTools>, CallToolResult> {
+        let header_token = context
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| bearer_token(&parts.headers));
+        match self.session.tools(header_token.as_deref()).await {
+            Ok(Some(tools)) => Ok(tools),
+            Ok(None) => Err(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(
+                    "未配置 Coze 凭证：请通过 Authorization: Bearer 请求头或 configure 工具提供 API Key",
+                )]),
+                is_error: Some(true),
+                structured_content: Some(serde_json::json!({"error": "missing_credentials"})),
+            }),
+            Err(e) => Err(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "创建会话客户端失败: {e}"
+                ))]),
+                is_error: Some(true),
+                structured_content: Some(serde_json::json!({"error": e.to_string()})),
+            }),
+        }
+    }
 }
 
 impl ServerHandler for CozeServer {
     async fn call_tool(
         &self,
         params: CallToolRequestParam,
-        _context: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         let tool_name = &params.name;
         let args_value: Option<Value> = params.arguments.as_ref().map(|m| Value::Object(m.clone()));
 
         info!("Calling tool: {}", tool_name);
 
+        // 不依赖 Coze 凭证的工具
+        match &tool_name[..] {
+            "configure" => return self.session.configure(args_value).await,
+            "profiles" => return self.session.profiles_tool(args_value).await,
+            "ping" => {
+                return Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text("pong")]),
+                    is_error: Some(false),
+                    structured_content: Some(serde_json::json!({"ok":true})),
+                })
+            }
+            _ => {}
+        }
+
+        let tools = match self.resolve_tools(&context).await {
+            Ok(tools) => tools,
+            Err(result) => return Ok(result),
+        };
+
         let result: Result<CallToolResult, McpError> = match &tool_name[..] {
-            "list_bots" => self.tools.list_bots(args_value.clone()).await,
-            "list_knowledge_bases" => self.tools.list_knowledge_bases(args_value.clone()).await,
-            "create_dataset" => self.tools.create_dataset(args_value.clone()).await,
+            "list_bots" => tools.list_bots(args_value.clone()).await,
+            "get_bot" => tools.get_bot(args_value.clone()).await,
+            "create_bot" => tools.create_bot(args_value.clone()).await,
+            "update_bot" => tools.update_bot(args_value.clone()).await,
+            "publish_bot" => tools.publish_bot(args_value.clone()).await,
+            "export_bot" => tools.export_bot(args_value.clone()).await,
+            "import_bot" => tools.import_bot(args_value.clone()).await,
+            "list_knowledge_bases" => tools.list_knowledge_bases(args_value.clone()).await,
+            "create_dataset" => tools.create_dataset(args_value.clone()).await,
+            "update_dataset" => tools.update_dataset(args_value.clone()).await,
+            "delete_dataset" => tools.delete_dataset(args_value.clone()).await,
             "upload_document_to_knowledge_base" => {
-                self.tools
-                    .upload_document_to_knowledge_base(args_value.clone())
+                tools
+                    .upload_document_to_knowledge_base_with_context(
+                        args_value.clone(),
+                        context.ct.clone(),
+                    )
+                    .await
+            }
+            "get_document_progress" => tools.get_document_progress(args_value.clone()).await,
+            "list_knowledge_documents" => tools.list_knowledge_documents(args_value.clone()).await,
+            "update_knowledge_document" => {
+                tools.update_knowledge_document(args_value.clone()).await
+            }
+            "delete_knowledge_documents" => {
+                tools.delete_knowledge_documents(args_value.clone()).await
+            }
+            "list_conversations" => tools.list_conversations(args_value.clone()).await,
+            "chat" => {
+                tools
+                    .chat_with_context(args_value.clone(), context.ct.clone())
+                    .await
+            }
+            "chat_stream" => {
+                tools
+                    .chat_stream_with_context(
+                        args_value.clone(),
+                        ProgressNotifier::from_context(&context),
+                        context.ct.clone(),
+                    )
+                    .await
+            }
+            "upload_file" => tools.upload_file(args_value.clone()).await,
+            "get_file_info" => tools.get_file_info(args_value.clone()).await,
+            "create_conversation" => tools.create_conversation(args_value.clone()).await,
+            "get_conversation" => tools.get_conversation(args_value.clone()).await,
+            "clear_conversation" => tools.clear_conversation(args_value.clone()).await,
+            "delete_conversation" => tools.delete_conversation(args_value.clone()).await,
+            "create_message" => tools.create_message(args_value.clone()).await,
+            "list_messages" => tools.list_messages(args_value.clone()).await,
+            "get_message" => tools.get_message(args_value.clone()).await,
+            "update_message" => tools.update_message(args_value.clone()).await,
+            "delete_message" => tools.delete_message(args_value.clone()).await,
+            "list_workflows" => tools.list_workflows(args_value.clone()).await,
+            "run_workflow" => {
+                tools
+                    .run_workflow_with_context(args_value.clone(), context.ct.clone())
+                    .await
+            }
+            "run_workflow_stream" => {
+                tools
+                    .run_workflow_stream_with_context(
+                        args_value.clone(),
+                        ProgressNotifier::from_context(&context),
+                        context.ct.clone(),
+                    )
+                    .await
+            }
+            "get_workflow_run_history" => {
+                tools
+                    .get_workflow_run_history_with_context(args_value.clone(), context.ct.clone())
+                    .await
+            }
+            "resume_workflow" => {
+                tools
+                    .resume_workflow_with_context(
+                        args_value.clone(),
+                        ProgressNotifier::from_context(&context),
+                        context.ct.clone(),
+                    )
+                    .await
+            }
+            "submit_tool_outputs" => {
+                tools
+                    .submit_tool_outputs_with_context(
+                        args_value.clone(),
+                        ProgressNotifier::from_context(&context),
+                        context.ct.clone(),
+                    )
                     .await
             }
-            "list_conversations" => self.tools.list_conversations(args_value.clone()).await,
-            "chat" => self.tools.chat(args_value.clone()).await,
-            "chat_stream" => self.tools.chat_stream(args_value.clone()).await,
-            "ping" => Ok(CallToolResult {
-                content: Some(vec![rmcp::model::Content::text("pong")]),
-                is_error: Some(false),
-                structured_content: Some(serde_json::json!({"ok":true})),
-            }),
             _ => Err(McpError::invalid_params(
                 format!("Unknown tool: {tool_name}"),
                 None,
@@ -90,7 +254,7 @@ impl ServerHandler for CozeServer {
         _params: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
-        // 精选7个最常用的工具
+        // 精选常用工具
         let tools = vec![
             // 1. Bot管理 - 核心功能
             Tool {
@@ -119,7 +283,10 @@ impl ServerHandler for CozeServer {
                         "page_size": {
                             "type": "number",
                             "description": "每页数量，默认20"
-                        }
+                        },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 page）" },
+                        "all_pages": { "type": "boolean", "description": "自动翻页直到最后一页（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数" }
                     },
                     "required": []
                 }).as_object().unwrap().clone()),
@@ -129,11 +296,18 @@ impl ServerHandler for CozeServer {
             // 2. 知识库管理 - 核心功能
             Tool {
                 name: "list_knowledge_bases".into(),
-                description: Some("列出所有知识库".into()),
+                description: Some("列出知识库 - 支持名称 / 类型筛选与自动翻页".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
                         "space_id": { "type": "string", "description": "空间ID (可选，使用默认space_id)" },
+                        "name": { "type": "string", "description": "按名称模糊筛选（可选）" },
+                        "format_type": { "type": "integer", "enum": [0, 1, 2], "description": "按类型筛选：0 文本、1 表格、2 图片（可选）" },
+                        "page": { "type": "integer", "minimum": 1, "description": "页码，默认 1" },
+                        "page_size": { "type": "integer", "minimum": 1, "maximum": 300, "description": "每页数量，默认 100" },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 page）" },
+                        "all_pages": { "type": "boolean", "description": "自动翻页直到最后一页（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数" }
                     },
                     "required": []
                 }).as_object().unwrap().clone()),
@@ -177,17 +351,23 @@ impl ServerHandler for CozeServer {
             // 4. 文档上传 - 重要功能
             Tool {
                 name: "upload_document_to_knowledge_base".into(),
-                description: Some("上传本地文档到知识库".into()),
+                description: Some("上传本地文档或在线网页（web_url）到知识库；在线网页可设置自动更新".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
                         "dataset_id": { "type": "string", "description": "知识库ID" },
-                        "file_path": { "type": "string", "description": "本地文件路径" },
-                        "document_name": { "type": "string", "description": "文档名称（可选）" },
+                        "file_path": { "type": "string", "description": "本地文件路径（与 web_url 二选一）" },
+                        "web_url": { "type": "string", "description": "在线网页地址（http/https，与 file_path 二选一，仅文本知识库）" },
+                        "document_name": { "type": "string", "description": "文档名称（可选，默认文件名或网页地址）" },
+                        "auto_update": { "type": "boolean", "description": "在线网页是否自动更新；开启且未指定间隔时每 24 小时更新" },
+                        "update_interval": { "type": "integer", "minimum": 24, "description": "在线网页自动更新间隔（小时，最小 24）" },
                         "chunk_size": { "type": "number", "description": "分片大小（可选，默认800）" },
-                        "chunk_overlap": { "type": "number", "description": "分片重叠（可选，默认100）" }
+                        "chunk_overlap": { "type": "number", "description": "分片重叠（可选，默认100）" },
+                        "wait_until_processed": { "type": "boolean", "description": "上传后轮询切片进度，直到文件处理完毕或失败，返回每个文件的状态（超时返回 timeout: true，客户端取消返回 cancelled: true）；默认 false" },
+                        "timeout_secs": { "type": "integer", "minimum": 0, "maximum": 600, "description": "最长等待秒数，默认 120" },
+                        "poll_interval_secs": { "type": "integer", "minimum": 1, "maximum": 60, "description": "轮询间隔秒数，默认 2" }
                     },
-                    "required": ["dataset_id", "file_path"]
+                    "required": ["dataset_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
                 output_schema: None,
@@ -202,7 +382,10 @@ impl ServerHandler for CozeServer {
                         "workspace_id": { "type": "string", "description": "工作区ID (可选，使用默认space_id)" },
                         "bot_id": { "type": "string", "description": "Bot ID（必填）" },
                         "page": { "type": "number", "description": "页码，默认1" },
-                        "page_size": { "type": "number", "description": "每页数量，默认20" }
+                        "page_size": { "type": "number", "description": "每页数量，默认20，最大50" },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 page）" },
+                        "all_pages": { "type": "boolean", "description": "自动翻页直到最后一页（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数" }
                     },
                     "required": ["bot_id"]
                 }).as_object().unwrap().clone()),
@@ -217,7 +400,21 @@ impl ServerHandler for CozeServer {
                     "type": "object",
                     "properties": {
                         "bot_id": { "type": "string", "description": "Bot ID（必填）" },
-                        "message": { "type": "string", "description": "要发送的消息内容（必填）" },
+                        "message": { "type": "string", "description": "要发送的文本消息（与 content 至少提供其一）" },
+                        "content": {
+                            "type": "array",
+                            "description": "多模态内容片段（可选）：文本、图片（file_id 或 file_url）、文件（file_id 或 file_url）；与 message 同时提供时 message 作为第一段文本",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "type": { "type": "string", "enum": ["text", "image", "file"] },
+                                    "text": { "type": "string", "description": "type=text 时的文本" },
+                                    "file_id": { "type": "string", "description": "已上传文件的 ID" },
+                                    "file_url": { "type": "string", "description": "公网可访问的文件 / 图片 URL" }
+                                },
+                                "required": ["type"]
+                            }
+                        },
                         "user_id": { "type": "string", "description": "用户ID（可选）" },
                         "conversation_id": { "type": "string", "description": "对话ID（可选，不提供则创建新对话）" },
                         "custom_variables": {
@@ -226,7 +423,7 @@ impl ServerHandler for CozeServer {
                             "additionalProperties": { "type": "string" }
                         }
                     },
-                    "required": ["bot_id", "message"]
+                    "required": ["bot_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
                 output_schema: None,
@@ -234,12 +431,26 @@ impl ServerHandler for CozeServer {
             // 7. 流式聊天对话 - 重要功能
             Tool {
                 name: "chat_stream".into(),
-                description: Some("发送流式聊天消息".into()),
+                description: Some("发送流式聊天消息；请求携带 progressToken 时逐段推送回复增量".into()),
                 input_schema: Arc::new(serde_json::json!({
                     "type": "object",
                     "properties": {
                         "bot_id": { "type": "string", "description": "Bot ID（必填）" },
-                        "message": { "type": "string", "description": "要发送的消息内容（必填）" },
+                        "message": { "type": "string", "description": "要发送的文本消息（与 content 至少提供其一）" },
+                        "content": {
+                            "type": "array",
+                            "description": "多模态内容片段（可选）：文本、图片（file_id 或 file_url）、文件（file_id 或 file_url）；与 message 同时提供时 message 作为第一段文本",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "type": { "type": "string", "enum": ["text", "image", "file"] },
+                                    "text": { "type": "string", "description": "type=text 时的文本" },
+                                    "file_id": { "type": "string", "description": "已上传文件的 ID" },
+                                    "file_url": { "type": "string", "description": "公网可访问的文件 / 图片 URL" }
+                                },
+                                "required": ["type"]
+                            }
+                        },
                         "user_id": { "type": "string", "description": "用户ID（可选）" },
                         "conversation_id": { "type": "string", "description": "对话ID（可选，不提供则创建新对话）" },
                         "custom_variables": {
@@ -248,7 +459,571 @@ impl ServerHandler for CozeServer {
                             "additionalProperties": { "type": "string" }
                         }
                     },
-                    "required": ["bot_id", "message"]
+                    "required": ["bot_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 8. 会话凭证配置 - 共享部署时每个用户使用自己的 Coze 身份
+            Tool {
+                name: "configure".into(),
+                description: Some("为当前会话设置 Coze API Key（及可选的默认 space_id），仅对本会话生效".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "api_key": { "type": "string", "description": "Coze 个人访问令牌（pat_ 开头）" },
+                        "space_id": { "type": "string", "description": "本会话默认空间ID（可选）" }
+                    },
+                    "required": ["api_key"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 9. 多租户 profile 切换
+            Tool {
+                name: "profiles".into(),
+                description: Some("列出配置文件中的命名 profile（各自的 base_url / 空间 / 超时）；传入 name 时将当前会话切换到该 profile（HTTP / SSE 传输下需运维者开启 server.allow_profile_switch）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string", "description": "要切换到的 profile 名称（可选，不传则仅列出）" }
+                    },
+                    "required": []
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 10. 提交端插件执行结果 - 对话 requires_action 后继续
+            Tool {
+                name: "submit_tool_outputs".into(),
+                description: Some("对话状态为 requires_action 时，提交端插件（tool_calls）的执行结果并继续对话".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "对话所在会话ID（必填）" },
+                        "chat_id": { "type": "string", "description": "requires_action 结果中的 chat_id（必填）" },
+                        "tool_outputs": {
+                            "type": "array",
+                            "description": "各 tool_call 的执行结果（必填）",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "tool_call_id": { "type": "string", "description": "tool_calls[].id" },
+                                    "output": { "type": "string", "description": "执行结果，非字符串会被序列化为 JSON 字符串" }
+                                },
+                                "required": ["tool_call_id", "output"]
+                            }
+                        },
+                        "stream": { "type": "boolean", "description": "是否以流式继续对话（可选，默认 false）" }
+                    },
+                    "required": ["conversation_id", "chat_id", "tool_outputs"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 11. 上传文件 - 获取多模态对话、知识库图片与图标所需的 file_id
+            Tool {
+                name: "upload_file".into(),
+                description: Some("上传文件到 Coze（单文件不超过 512MB），返回 file_id".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "服务器本地文件路径（与 file_base64 二选一；HTTP / SSE 传输下仅限 --file-root 目录内）" },
+                        "file_base64": { "type": "string", "description": "Base64 编码的文件内容（与 file_path 二选一，需同时提供 file_name）" },
+                        "file_name": { "type": "string", "description": "上传后的文件名（使用 file_path 时可选，默认取路径中的文件名）" }
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 12. 查看文件详情
+            Tool {
+                name: "get_file_info".into(),
+                description: Some("查看已上传文件的详情（文件名、大小、上传时间）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "file_id": { "type": "string", "description": "upload_file 返回的 file_id（必填）" }
+                    },
+                    "required": ["file_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 13. 创建会话 - 可绑定 Bot 并附带初始消息
+            Tool {
+                name: "create_conversation".into(),
+                description: Some("创建会话，可选绑定 Bot、附带初始消息与元数据，返回 conversation_id".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "bot_id": { "type": "string", "description": "绑定的 Bot ID（可选）" },
+                        "messages": {
+                            "type": "array",
+                            "description": "初始消息（可选）",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "role": { "type": "string", "enum": ["user", "assistant"], "description": "消息角色，默认 user" },
+                                    "content": {
+                                        "description": "字符串为文本消息；数组为多模态片段，格式同 chat 的 content",
+                                        "oneOf": [
+                                            { "type": "string" },
+                                            { "type": "array", "items": { "type": "object" } }
+                                        ]
+                                    }
+                                },
+                                "required": ["content"]
+                            }
+                        },
+                        "meta_data": {
+                            "type": "object",
+                            "description": "自定义键值对（值为字符串）",
+                            "additionalProperties": { "type": "string" }
+                        }
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 14. 查看会话信息
+            Tool {
+                name: "get_conversation".into(),
+                description: Some("查看会话信息（创建时间、元数据、当前 section）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" }
+                    },
+                    "required": ["conversation_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 15. 清除会话上下文
+            Tool {
+                name: "clear_conversation".into(),
+                description: Some("清除会话上下文，历史消息保留但后续对话不再携带".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" }
+                    },
+                    "required": ["conversation_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 16. 删除会话
+            Tool {
+                name: "delete_conversation".into(),
+                description: Some("删除会话及其全部消息，不可恢复".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" }
+                    },
+                    "required": ["conversation_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 17. 在会话中创建消息
+            Tool {
+                name: "create_message".into(),
+                description: Some("在会话中写入一条消息（不触发 Bot 回复），可用于补充上下文".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" },
+                        "role": { "type": "string", "enum": ["user", "assistant"], "description": "消息角色，默认 user" },
+                        "content": {
+                            "description": "字符串为文本消息；数组为多模态片段，格式同 chat 的 content",
+                            "oneOf": [
+                                { "type": "string" },
+                                { "type": "array", "items": { "type": "object" } }
+                            ]
+                        },
+                        "meta_data": { "type": "object", "description": "自定义键值对（值为字符串）", "additionalProperties": { "type": "string" } }
+                    },
+                    "required": ["conversation_id", "content"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 18. 查看会话消息列表 - 游标翻页
+            Tool {
+                name: "list_messages".into(),
+                description: Some("查看会话的消息列表，返回完整消息及 next_cursor 翻页游标，可自动翻页".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" },
+                        "order": { "type": "string", "enum": ["desc", "asc"], "description": "排序，默认 desc（从新到旧）" },
+                        "chat_id": { "type": "string", "description": "只看某次对话的消息（可选）" },
+                        "before_id": { "type": "string", "description": "返回此消息之前的消息（翻页，传上一页的 last_id）" },
+                        "after_id": { "type": "string", "description": "返回此消息之后的消息（翻页，传上一页的 first_id）；与 before_id 互斥" },
+                        "page_size": { "type": "integer", "minimum": 1, "maximum": 50, "description": "每页数量，默认取 limit，最大 50" },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 before_id / after_id）" },
+                        "all_pages": { "type": "boolean", "description": "沿翻页方向自动翻页直到没有更多消息（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数；未传 page_size 时也作为每页数量（最大 50）" }
+                    },
+                    "required": ["conversation_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 19. 查看单条消息
+            Tool {
+                name: "get_message".into(),
+                description: Some("查看会话中单条消息的详情".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" },
+                        "message_id": { "type": "string", "description": "消息 ID（必填）" }
+                    },
+                    "required": ["conversation_id", "message_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 20. 修改消息
+            Tool {
+                name: "update_message".into(),
+                description: Some("修改消息内容或元数据（至少提供其一）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" },
+                        "message_id": { "type": "string", "description": "消息 ID（必填）" },
+                        "content": {
+                            "description": "字符串为文本消息；数组为多模态片段，格式同 chat 的 content",
+                            "oneOf": [
+                                { "type": "string" },
+                                { "type": "array", "items": { "type": "object" } }
+                            ]
+                        },
+                        "meta_data": { "type": "object", "description": "自定义键值对（值为字符串）", "additionalProperties": { "type": "string" } }
+                    },
+                    "required": ["conversation_id", "message_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 21. 删除消息
+            Tool {
+                name: "delete_message".into(),
+                description: Some("删除会话中的一条消息，不可恢复".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "conversation_id": { "type": "string", "description": "会话 ID（必填）" },
+                        "message_id": { "type": "string", "description": "消息 ID（必填）" }
+                    },
+                    "required": ["conversation_id", "message_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 22. 工作流列表
+            Tool {
+                name: "list_workflows".into(),
+                description: Some("列出工作空间中的工作流 / 对话流".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "workspace_id": { "type": "string", "description": "工作空间 ID（可选，默认使用配置的 space_id）" },
+                        "workflow_mode": { "type": "string", "enum": ["workflow", "chatflow"], "description": "按类型筛选（可选）" },
+                        "app_id": { "type": "string", "description": "只列出该应用下的工作流（可选）" },
+                        "page": { "type": "integer", "minimum": 1, "description": "页码，默认 1" },
+                        "page_size": { "type": "integer", "minimum": 1, "maximum": 30, "description": "每页数量，默认 20" },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 page）" },
+                        "all_pages": { "type": "boolean", "description": "自动翻页直到最后一页（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数" }
+                    },
+                    "required": []
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 23. 执行工作流（同步）
+            Tool {
+                name: "run_workflow".into(),
+                description: Some("执行已发布的工作流并返回结束节点输出；is_async=true 时立即返回 execute_id".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "workflow_id": { "type": "string", "description": "工作流 ID（必填）" },
+                        "parameters": { "type": "object", "description": "开始节点的输入参数（键为参数名）" },
+                        "bot_id": { "type": "string", "description": "工作流包含数据库 / 变量等节点时需关联的 Bot（可选）" },
+                        "app_id": { "type": "string", "description": "应用中的工作流需指定应用 ID（可选）" },
+                        "ext": { "type": "object", "description": "额外字段，如 latitude / longitude / user_id（值为字符串）", "additionalProperties": { "type": "string" } },
+                        "is_async": { "type": "boolean", "description": "异步执行，立即返回 execute_id（用 get_workflow_run_history 查询结果），适合超过请求超时的长工作流；默认 false" }
+                    },
+                    "required": ["workflow_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 24. 流式执行工作流 - 支持进度通知与中断
+            Tool {
+                name: "run_workflow_stream".into(),
+                description: Some("流式执行工作流，节点输出以进度通知推送；遇到问答等中断时返回 event_id，用 resume_workflow 继续".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "workflow_id": { "type": "string", "description": "工作流 ID（必填）" },
+                        "parameters": { "type": "object", "description": "开始节点的输入参数（键为参数名）" },
+                        "bot_id": { "type": "string", "description": "工作流包含数据库 / 变量等节点时需关联的 Bot（可选）" },
+                        "app_id": { "type": "string", "description": "应用中的工作流需指定应用 ID（可选）" },
+                        "ext": { "type": "object", "description": "额外字段，如 latitude / longitude / user_id（值为字符串）", "additionalProperties": { "type": "string" } }
+                    },
+                    "required": ["workflow_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 25. 恢复中断的工作流
+            Tool {
+                name: "resume_workflow".into(),
+                description: Some("对中断（Interrupt）的工作流提交用户输入并继续流式执行".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "workflow_id": { "type": "string", "description": "工作流 ID（必填）" },
+                        "event_id": { "type": "string", "description": "中断返回的 event_id（必填）" },
+                        "resume_data": { "type": "string", "description": "用户对中断的回复（必填）" },
+                        "interrupt_type": { "type": "integer", "description": "中断返回的 interrupt_type（必填）" }
+                    },
+                    "required": ["workflow_id", "event_id", "resume_data", "interrupt_type"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 26. 查询工作流执行结果 - 可等待异步执行结束
+            Tool {
+                name: "get_workflow_run_history".into(),
+                description: Some("查询工作流执行状态与输出（Running / Success / Fail），wait=true 时轮询直到结束或超时".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "workflow_id": { "type": "string", "description": "工作流 ID（必填）" },
+                        "execute_id": { "type": "string", "description": "run_workflow 异步执行返回的 execute_id（必填）" },
+                        "wait": { "type": "boolean", "description": "执行中时是否等待结束，默认 false" },
+                        "timeout_secs": { "type": "integer", "minimum": 0, "maximum": 600, "description": "最长等待秒数，默认 60" },
+                        "poll_interval_secs": { "type": "integer", "minimum": 1, "maximum": 60, "description": "轮询间隔秒数，默认 2" }
+                    },
+                    "required": ["workflow_id", "execute_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 27. 查看 Bot 配置 - 已发布版本的人设、模型、插件、知识库、开场白
+            Tool {
+                name: "get_bot".into(),
+                description: Some("查看智能体已发布版本的详细配置：人设与回复逻辑、模型配置、插件、工作流、知识库、开场白".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "bot_id": { "type": "string", "description": "智能体 ID（必填）" }
+                    },
+                    "required": ["bot_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 28. 创建 Bot - 创建草稿，需发布后才能通过 API 对话
+            Tool {
+                name: "create_bot".into(),
+                description: Some("创建智能体（草稿），可同时设置人设、开场白、模型、插件和工作流；需调用 publish_bot 发布".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "space_id": { "type": "string", "description": "工作空间 ID（可选，默认使用配置的 space_id）" },
+                        "name": { "type": "string", "description": "智能体名称（必填）" },
+                        "description": { "type": "string", "description": "智能体描述" },
+                        "icon_file_id": { "type": "string", "description": "图标文件 ID（upload_file 返回）" },
+                        "prompt": { "type": "string", "description": "人设与回复逻辑（prompt_info.prompt 的简写）" },
+                        "prompt_info": { "type": "object", "description": "人设与回复逻辑 {prompt}" },
+                        "onboarding_info": { "type": "object", "description": "开场白 {prologue, suggested_questions}" },
+                        "model_info_config": { "type": "object", "description": "模型配置 {model_id, temperature, top_p, max_tokens, context_round, response_format}" },
+                        "plugin_id_list": { "type": "object", "description": "插件工具 {id_list: [{plugin_id, api_id}]}" },
+                        "workflow_id_list": { "type": "object", "description": "工作流 {ids: [{id}]}" }
+                    },
+                    "required": ["name"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 29. 更新 Bot - 更新草稿配置，只提交传入的字段
+            Tool {
+                name: "update_bot".into(),
+                description: Some("更新智能体草稿配置（未传入的字段保持不变），发布后对线上生效".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "bot_id": { "type": "string", "description": "智能体 ID（必填）" },
+                        "name": { "type": "string", "description": "新名称" },
+                        "description": { "type": "string", "description": "智能体描述" },
+                        "icon_file_id": { "type": "string", "description": "图标文件 ID（upload_file 返回）" },
+                        "prompt": { "type": "string", "description": "人设与回复逻辑（prompt_info.prompt 的简写）" },
+                        "prompt_info": { "type": "object", "description": "人设与回复逻辑 {prompt}" },
+                        "onboarding_info": { "type": "object", "description": "开场白 {prologue, suggested_questions}" },
+                        "model_info_config": { "type": "object", "description": "模型配置 {model_id, temperature, top_p, max_tokens, context_round, response_format}" },
+                        "plugin_id_list": { "type": "object", "description": "插件工具 {id_list: [{plugin_id, api_id}]}" },
+                        "workflow_id_list": { "type": "object", "description": "工作流 {ids: [{id}]}" },
+                        "knowledge": { "type": "object", "description": "知识库 {dataset_ids, auto_call, search_strategy}" }
+                    },
+                    "required": ["bot_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 30. 发布 Bot - 发布到指定渠道
+            Tool {
+                name: "publish_bot".into(),
+                description: Some("发布智能体到指定渠道，默认发布到 API 渠道（1024）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "bot_id": { "type": "string", "description": "智能体 ID（必填）" },
+                        "connector_ids": { "type": "array", "items": { "type": "string" }, "description": "发布渠道 ID 列表，默认 [\"1024\"]" }
+                    },
+                    "required": ["bot_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 31. 导出 Bot 配置 - 确定性 JSON，便于纳入 git
+            Tool {
+                name: "export_bot".into(),
+                description: Some("导出智能体已发布版本的配置（人设、模型、插件、工作流、知识库、变量）为确定性 JSON，可写入文件纳入版本管理".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "bot_id": { "type": "string", "description": "智能体 ID（必填）" },
+                        "output_path": { "type": "string", "description": "写入的服务器本地文件路径（HTTP / SSE 传输下仅限 --file-root 目录内）；不传时直接返回文件内容" }
+                    },
+                    "required": ["bot_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 32. 导入 Bot 配置 - 按 export_bot 的文件创建或更新 Bot
+            Tool {
+                name: "import_bot".into(),
+                description: Some("按 export_bot 导出的配置文件更新已有智能体（bot_id）或在工作空间中新建（space_id），可选随后发布".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "path": { "type": "string", "description": "服务器本地配置文件路径（与 config 二选一，HTTP / SSE 传输下仅限 --file-root 目录内）" },
+                        "config": { "type": "object", "description": "配置文件内容（与 path 二选一）" },
+                        "bot_id": { "type": "string", "description": "要更新的智能体 ID；不传时在 space_id 中新建" },
+                        "space_id": { "type": "string", "description": "新建时的工作空间 ID（可选，默认使用配置的 space_id）" },
+                        "publish": { "type": "boolean", "description": "导入后是否发布，默认 false" },
+                        "connector_ids": { "type": "array", "items": { "type": "string" }, "description": "publish=true 时的发布渠道，默认 [\"1024\"]" },
+                        "skip_variables": { "type": "boolean", "description": "文件含变量时接口无法设置，需传 true 才跳过变量继续导入，默认 false（报错）" }
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 33. 知识库文件列表 - 处理状态、分段数、命中次数
+            Tool {
+                name: "list_knowledge_documents".into(),
+                description: Some("查看知识库中的文件列表（名称、处理状态、分段数、命中次数等），支持自动翻页".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "dataset_id": { "type": "string", "description": "知识库ID" },
+                        "page": { "type": "integer", "minimum": 1, "description": "页码，默认 1" },
+                        "page_size": { "type": "integer", "minimum": 1, "maximum": 100, "description": "每页数量，默认 20" },
+                        "cursor": { "type": "object", "description": "上次返回的 next_cursor，从该位置继续（优先于 page）" },
+                        "all_pages": { "type": "boolean", "description": "自动翻页直到最后一页（受 limit 限制），默认只取一页" },
+                        "limit": { "type": "integer", "minimum": 1, "description": "最多返回的条数" }
+                    },
+                    "required": ["dataset_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 34. 修改知识库文件 - 重命名 / 在线网页更新规则
+            Tool {
+                name: "update_knowledge_document".into(),
+                description: Some("修改知识库文件：重命名，或设置在线网页的自动更新规则".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "document_id": { "type": "string", "description": "文件ID" },
+                        "document_name": { "type": "string", "description": "新的文件名称（可选）" },
+                        "auto_update": { "type": "boolean", "description": "在线网页是否自动更新（可选）；开启且未指定间隔时每 24 小时更新" },
+                        "update_interval": { "type": "integer", "minimum": 24, "description": "在线网页自动更新的间隔（小时，可选），设置后即开启自动更新" }
+                    },
+                    "required": ["document_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 35. 删除知识库文件 - 单次最多 100 个
+            Tool {
+                name: "delete_knowledge_documents".into(),
+                description: Some("删除知识库中的文件，单次最多 100 个；删除后不可恢复".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "document_ids": { "type": "array", "items": { "type": "string" }, "maxItems": 100, "description": "要删除的文件ID列表" },
+                        "document_id": { "type": "string", "description": "要删除的单个文件ID（与 document_ids 合并）" }
+                    }
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 36. 修改知识库 - 名称、描述、图标
+            Tool {
+                name: "update_dataset".into(),
+                description: Some("修改知识库的名称、描述与图标。接口为全量更新：未传的名称与描述沿用当前值；未传 file_id 时图标恢复默认".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "dataset_id": { "type": "string", "description": "知识库ID" },
+                        "name": { "type": "string", "maxLength": 100, "description": "新的知识库名称（可选）" },
+                        "description": { "type": "string", "description": "新的描述（可选）" },
+                        "file_id": { "type": "string", "description": "新的图标文件ID（可选），需通过 upload_file 获取" },
+                        "space_id": { "type": "string", "description": "知识库所在空间ID，用于读取当前名称与描述（可选，使用默认space_id）" }
+                    },
+                    "required": ["dataset_id"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 37. 删除知识库 - 需显式确认
+            Tool {
+                name: "delete_dataset".into(),
+                description: Some("删除知识库及其中的全部文件，不可恢复；必须传入 confirm=true".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "dataset_id": { "type": "string", "description": "知识库ID" },
+                        "confirm": { "type": "boolean", "description": "确认删除，必须为 true" }
+                    },
+                    "required": ["dataset_id", "confirm"]
+                }).as_object().unwrap().clone()),
+                annotations: None,
+                output_schema: None,
+            },
+            // 38. 文件处理进度 - 确认上传后切片是否完成
+            Tool {
+                name: "get_document_progress".into(),
+                description: Some("查看知识库文件的切片处理进度与状态（处理中 / 处理完毕 / 处理失败）".into()),
+                input_schema: Arc::new(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "dataset_id": { "type": "string", "description": "知识库ID" },
+                        "document_ids": { "type": "array", "items": { "type": "string" }, "description": "文件ID列表" },
+                        "document_id": { "type": "string", "description": "单个文件ID（可与 document_ids 同时使用）" }
+                    },
+                    "required": ["dataset_id"]
                 }).as_object().unwrap().clone()),
                 annotations: None,
                 output_schema: None,
@@ -277,69 +1052,94 @@ impl ServerHandler for CozeServer {
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tracing_subscriber::fmt::init();
-    // ---- CLI 参数解析（优先级: CLI > 环境变量 > 默认） ----
-    let args: Vec<String> = env::args().collect();
-    if args.iter().any(|a| a == "-h" || a == "--help") {
-        println!("Coze MCP Server\n\n用法: coze-mcp-server [--api-key <KEY>] [--space-id <SPACE>] [--base-url <URL>]\n\n优先级: CLI > 环境变量 > 默认\n\n环境变量: COZE_API_KEY / COZE_API_TOKEN, COZE_DEFAULT_SPACE_ID, COZE_API_BASE_URL\n");
+    // ---- 配置加载（优先级: CLI > 环境变量 > 配置文件 > 默认） ----
+    let cli = CliArgs::parse(env::args().skip(1));
+    if cli.help {
+        println!("Coze MCP Server\n\n用法: coze-mcp-server [--config <FILE>] [--profile <NAME>] [--api-key <KEY>] [--space-id <SPACE>] [--base-url <URL>] [--transport <stdio|http|sse>] [--listen-addr <ADDR>] [--file-root <DIR>] [--timeout <SECS>] [--retry-attempts <N>] [--log-level <LEVEL>] [--log-format <json|pretty|compact>] [--log-file <FILE>]\n       coze-mcp-server bot export <BOT_ID> [--output <FILE>]\n       coze-mcp-server bot import <FILE> [--bot-id <BOT_ID>] [--publish] [--skip-variables]\n\nbot export 将 Bot 已发布版本的配置导出为 JSON（默认输出到 stdout）；bot import 按文件更新 --bot-id 指定的 Bot，未指定时在 --space-id 工作空间中新建；文件含变量时需加 --skip-variables 才会跳过变量继续导入\n\n优先级: CLI > 环境变量 > 配置文件 > 默认\n\n配置文件: --config 或 COZE_MCP_CONFIG 指定，缺省读取当前目录 config.toml（可选）\n\n环境变量: COZE_PROFILE, COZE_API_KEY / COZE_API_TOKEN, COZE_DEFAULT_SPACE_ID / COZE_SPACE_ID, COZE_API_BASE_URL, COZE_TIMEOUT, COZE_RETRY_ATTEMPTS, TRANSPORT, LISTEN_ADDR, COZE_MCP_FILE_ROOT, LOG_LEVEL, LOG_FORMAT, LOG_FILE\n");
         return Ok(());
     }
-    let mut cli_api_key: Option<String> = None;
-    let mut cli_space_id: Option<String> = None;
-    let mut cli_base_url: Option<String> = None;
-    let mut iter = args.iter().skip(1);
-    while let Some(arg) = iter.next() {
-        match arg.as_str() {
-            "--api-key" => {
-                if let Some(v) = iter.next() {
-                    cli_api_key = Some(v.to_string());
-                }
-            }
-            s if s.starts_with("--api-key=") => {
-                cli_api_key = Some(s[10..].to_string());
-            }
-            "--space-id" => {
-                if let Some(v) = iter.next() {
-                    cli_space_id = Some(v.to_string());
-                }
-            }
-            s if s.starts_with("--space-id=") => {
-                cli_space_id = Some(s[11..].to_string());
-            }
-            "--base-url" => {
-                if let Some(v) = iter.next() {
-                    cli_base_url = Some(v.to_string());
-                }
-            }
-            s if s.starts_with("--base-url=") => {
-                cli_base_url = Some(s[11..].to_string());
-            }
-            _ => {}
-        }
+    let config = Config::load_with_cli(&cli)?;
+    init_logging(&config.logging)?;
+    if !cli.command.is_empty() {
+        return run_command(&cli, &config).await;
     }
 
-    let api_base_url = cli_base_url
-        .or_else(|| env::var("COZE_API_BASE_URL").ok())
-        .unwrap_or_else(|| COZE_BASE_URL.to_string());
-    let api_token = cli_api_key
-        .or_else(|| env::var("COZE_API_TOKEN").ok())
-        .or_else(|| env::var("COZE_API_KEY").ok())
-        .unwrap_or_default();
-    let default_space_id = cli_space_id
-        .or_else(|| env::var("COZE_DEFAULT_SPACE_ID").ok())
-        .unwrap_or_else(|| "default".to_string());
-
     info!("Starting Coze MCP Server...");
-    info!("API Base URL: {}", api_base_url);
-    info!("Default Space ID: {}", default_space_id);
-
-    let server = CozeServer::new(api_base_url, api_token, default_space_id)?;
+    if let Some(profile) = &config.profile {
+        info!("Profile: {}", profile);
+    }
+    let server = CozeServer::new(&config)?;
+    info!("API Base URL: {}", server.coze_config.base_url);
+    info!("Default Space ID: {}", server.coze_config.default_space_id);
+    let server_config = config.server;
 
     info!("Server initialized successfully");
 
-    // 使用 IO 传输特性：当前 rmcp crate 使用 transport-io 特征，提供 default io 传输构建器
-    // 使用 rmcp 提供的 stdio 传输实现（transport-io 特性）
-    let running_service = serve_server(server, rmcp::transport::stdio()).await?;
-    running_service.waiting().await?;
+    match server_config.transport {
+        TransportType::Stdio => {
+            // 使用 rmcp 提供的 stdio 传输实现（transport-io 特性）
+            let running_service = serve_server(server, rmcp::transport::stdio()).await?;
+            running_service.waiting().await?;
+        }
+        TransportType::Http => {
+            // 每个 HTTP 会话拥有独立的凭证上下文
+            transport::serve_streamable_http(&server_config, move || server.for_session()).await?;
+        }
+        TransportType::Sse => {
+            // 兼容仍使用旧版 HTTP+SSE 协议的客户端
+            transport::serve_sse(&server_config, move || server.for_session()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 执行命令行子命令后退出，不启动 MCP 服务
+async fn run_command(cli: &CliArgs, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let coze = config.active_coze()?;
+    let client = CozeApiClient::from_config(&coze)?;
+    let command: Vec<&str> = cli.command.iter().map(String::as_str).collect();
+    match command.as_slice() {
+        ["bot", "export", bot_id] => {
+            let content = bot_config::export_bot(&client, bot_id).await?.to_json()?;
+            match &cli.output {
+                Some(path) => {
+                    std::fs::write(path, content)?;
+                    eprintln!("已导出 Bot {bot_id} 的配置到 {path}");
+                }
+                None => print!("{content}"),
+            }
+        }
+        ["bot", "import", path] => {
+            let file = BotConfigFile::from_json(&std::fs::read_to_string(path)?)?;
+            let target = ImportTarget::resolve(cli.bot_id.as_deref(), &coze.default_space_id)
+                .ok_or("bot import 需要 --bot-id（更新）或 --space-id（新建）")?;
+            let connector_ids = cli.publish.then(|| vec![API_CONNECTOR_ID.to_string()]);
+            let outcome =
+                bot_config::import_bot(&client, &file, target, connector_ids, cli.skip_variables)
+                    .await?;
+            println!(
+                "已{} Bot {}（{}）",
+                if outcome.created { "创建" } else { "更新" },
+                file.name,
+                outcome.bot_id
+            );
+            if let Some(version) = outcome.published_version {
+                println!("已发布版本 {version}");
+            }
+            if !outcome.skipped.is_empty() {
+                eprintln!(
+                    "以下配置接口不支持设置，未导入: {}",
+                    outcome.skipped.join(", ")
+                );
+            }
+        }
+        _ => {
+            return Err(format!(
+                "未知命令: {}（支持 bot export / bot import，见 --help）",
+                cli.command.join(" ")
+            )
+            .into())
+        }
+    }
     Ok(())
 }
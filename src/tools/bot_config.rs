@@ -0,0 +1,119 @@
+//! Bot 配置导出 / 导入，供 export_bot / import_bot 工具与 `bot` 命令行子命令共用
+use crate::api::bot_models::{
+    BotConfigFile, CreateBotRequest, PublishBotRequest, UpdateBotRequest,
+};
+use crate::api::error::{ApiError, ApiErrorData};
+use crate::api::CozeApiClient;
+
+/// 导入目标
+#[derive(Debug, Clone)]
+pub enum ImportTarget {
+    /// 更新已有 Bot（可以是其他工作空间中的同一份配置）
+    Update { bot_id: String },
+    /// 在指定工作空间中创建新 Bot
+    Create { space_id: String },
+}
+
+impl ImportTarget {
+    /// 有 bot_id 时更新该 Bot，否则在 space_id 中新建；两者都没有时返回 None。
+    /// 未配置工作空间时 default_space_id 为占位值 "default"，同样视为未设置
+    pub fn resolve(bot_id: Option<&str>, space_id: &str) -> Option<Self> {
+        match bot_id.filter(|id| !id.is_empty()) {
+            Some(bot_id) => Some(ImportTarget::Update {
+                bot_id: bot_id.to_string(),
+            }),
+            None if !matches!(space_id, "" | "default") => Some(ImportTarget::Create {
+                space_id: space_id.to_string(),
+            }),
+            None => None,
+        }
+    }
+}
+
+/// 导入结果
+#[derive(Debug, Clone)]
+pub struct ImportOutcome {
+    pub bot_id: String,
+    /// 是否新建了 Bot
+    pub created: bool,
+    /// 发布后的版本号；未发布时为 None
+    pub published_version: Option<String>,
+    /// 接口不支持设置、因此未导入的配置项
+    pub skipped: Vec<String>,
+}
+
+/// 读取 Bot 已发布版本的配置并转换为配置文件
+pub async fn export_bot(client: &CozeApiClient, bot_id: &str) -> Result<BotConfigFile, ApiError> {
+    let detail = client.retrieve_bot(bot_id).await?;
+    Ok(BotConfigFile::from_detail(&detail))
+}
+
+/// 按配置文件创建或更新 Bot；`connector_ids` 为 Some 时随后发布到这些渠道。
+/// 文件含接口无法设置的变量时直接报错，除非 `skip_variables` 明确同意跳过
+pub async fn import_bot(
+    client: &CozeApiClient,
+    file: &BotConfigFile,
+    target: ImportTarget,
+    connector_ids: Option<Vec<String>>,
+    skip_variables: bool,
+) -> Result<ImportOutcome, ApiError> {
+    if !file.variables.is_empty() && !skip_variables {
+        return Err(ApiError::BadRequest(ApiErrorData::new(
+            "bad_request",
+            format!(
+                "the config file defines {} variable(s), which the Coze bot API cannot set; pass skip_variables (--skip-variables) to import without them",
+                file.variables.len()
+            ),
+            None,
+            None,
+        )));
+    }
+
+    let (bot_id, created) = match target {
+        ImportTarget::Update { bot_id } => {
+            let mut request = UpdateBotRequest::new(bot_id.clone())
+                .with_name(file.name.clone())
+                .with_config(file.to_bot_config());
+            if let Some(knowledge) = &file.knowledge {
+                request = request.with_knowledge(knowledge.clone());
+            }
+            client.update_bot(&request).await?;
+            (bot_id, false)
+        }
+        ImportTarget::Create { space_id } => {
+            let request = CreateBotRequest::new(space_id, file.name.clone())
+                .with_config(file.to_bot_config());
+            let bot_id = client.create_bot(&request).await?.bot_id;
+            // 创建接口不支持知识库，创建后再补一次更新
+            if let Some(knowledge) = &file.knowledge {
+                let request =
+                    UpdateBotRequest::new(bot_id.clone()).with_knowledge(knowledge.clone());
+                if let Err(e) = client.update_bot(&request).await {
+                    tracing::warn!(bot_id = %bot_id, "Bot 已创建，但设置知识库失败: {}", e);
+                    return Err(e);
+                }
+            }
+            (bot_id, true)
+        }
+    };
+
+    let published_version = match connector_ids {
+        Some(connector_ids) => {
+            let request = PublishBotRequest::new(bot_id.clone()).with_connector_ids(connector_ids);
+            client.publish_bot(&request).await?.version
+        }
+        None => None,
+    };
+
+    let mut skipped = Vec::new();
+    if !file.variables.is_empty() {
+        skipped.push("variables".to_string());
+    }
+
+    Ok(ImportOutcome {
+        bot_id,
+        created,
+        published_version,
+        skipped,
+    })
+}
@@ -47,6 +47,11 @@ impl ConfigTool {
         key.clone()
     }
 
+    pub async fn clear_api_key(&self) {
+        *self.api_key.write().await = None;
+        self.is_configured.store(false, Ordering::SeqCst);
+    }
+
     pub async fn is_configured(&self) -> bool {
         self.is_configured.load(Ordering::SeqCst)
     }
@@ -80,7 +85,7 @@ impl ConfigTool {
         Ok(CallToolResult {
             content: Some(vec![rmcp::model::Content::text(format!(
                 "API Key设置成功！\n已配置的Key: {}...",
-                &api_key[..8]
+                preview_key(api_key)
             ))]),
             is_error: Some(false),
             structured_content: None,
@@ -130,7 +135,7 @@ impl ConfigTool {
         Ok(CallToolResult {
             content: Some(vec![rmcp::model::Content::text(format!(
                 "API Key设置成功！\n已配置的Key: {}...",
-                &api_key[..8]
+                preview_key(&api_key)
             ))]),
             is_error: Some(false),
             structured_content: None,
@@ -144,7 +149,8 @@ impl ConfigTool {
         let is_configured = self.is_configured().await;
         let key_preview = if is_configured {
             let key = self.get_api_key().await;
-            key.map(|k| format!("{}...", &k[..8])).unwrap_or_default()
+            key.map(|k| format!("{}...", preview_key(&k)))
+                .unwrap_or_default()
         } else {
             "未配置".to_string()
         };
@@ -177,3 +183,8 @@ impl ConfigTool {
         })
     }
 }
+
+// 仅展示前 8 个字符，避免短 key 切片越界
+fn preview_key(api_key: &str) -> String {
+    api_key.chars().take(8).collect()
+}
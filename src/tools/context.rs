@@ -1,6 +1,16 @@
+use crate::api::error::ApiError;
+use crate::api::rate_limit::RateLimiter;
 use crate::api::CozeApiClient;
-use std::collections::HashMap;
+use crate::tools::config_tool::ConfigTool;
+use crate::tools::coze_tools::CozeTools;
+use crate::tools::local_files::LocalFiles;
+use crate::utils::config::{CozeConfig, ServerConfig, TransportType};
+use rmcp::model::CallToolResult;
+use rmcp::ErrorData as McpError;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use tokio::sync::RwLock;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -11,3 +21,266 @@ pub struct ToolContext {
     pub workspace_id: Option<String>,
     pub metadata: HashMap<String, String>,
 }
+
+/// 会话可使用的进程级能力：stdio 下调用方即运维者，全部允许；HTTP / SSE 的远程会话默认只能使用自己的凭证
+#[derive(Debug, Clone)]
+pub struct SessionAccess {
+    /// 会话未提供凭证时是否回退到当前 profile 的进程级 token
+    pub operator_token: bool,
+    /// 是否允许通过 `profiles` 工具切换 profile（切换后即使用该租户的 token）
+    pub switch_profile: bool,
+    /// `profiles` 工具是否展示各 profile 是否配置了 token
+    pub show_token_status: bool,
+}
+
+impl Default for SessionAccess {
+    fn default() -> Self {
+        Self {
+            operator_token: true,
+            switch_profile: true,
+            show_token_status: true,
+        }
+    }
+}
+
+impl SessionAccess {
+    /// 按传输方式与 `server.share_operator_token` / `server.allow_profile_switch` 确定远程会话的权限
+    pub fn for_server(server: &ServerConfig) -> Self {
+        match server.transport {
+            TransportType::Stdio => Self::default(),
+            _ => Self {
+                operator_token: server.share_operator_token,
+                switch_profile: server.allow_profile_switch,
+                show_token_status: false,
+            },
+        }
+    }
+}
+
+/// 单个 MCP 会话的 Coze 凭证与工具实例。
+///
+/// 共享部署下每个会话各持有一份，凭证来自请求的 `Authorization: Bearer` 头、
+/// `configure` 工具或当前 profile（远程会话需 `SessionAccess` 允许）；对应的 `CozeApiClient` 只在本会话内复用，不会被其他会话看到。
+#[derive(Debug)]
+pub struct SessionContext {
+    // 可切换的命名 profile（名称 -> 完整配置），所有会话共享同一份只读表
+    profiles: Arc<BTreeMap<String, CozeConfig>>,
+    // (当前 profile 名, 配置)：base_url、超时、重试、默认空间与进程级 token
+    active: RwLock<(Option<String>, CozeConfig)>,
+    config: ConfigTool,
+    space_id: RwLock<Option<String>>,
+    // (token, tools)：token 变化时重建客户端
+    cached: RwLock<Option<(String, Arc<CozeTools>)>>,
+    // 进程级共享的限流器；未设置时每个客户端按配置单独限流
+    limiter: Option<Arc<RateLimiter>>,
+    local_files: LocalFiles,
+    access: SessionAccess,
+}
+
+impl SessionContext {
+    pub fn new(coze_config: CozeConfig) -> Self {
+        Self::with_profiles(coze_config, None, Arc::new(BTreeMap::new()))
+    }
+
+    /// `coze_config` 为启动时生效的配置，`active` 为其对应的 profile 名（未使用 profile 时为 None）
+    pub fn with_profiles(
+        coze_config: CozeConfig,
+        active: Option<String>,
+        profiles: Arc<BTreeMap<String, CozeConfig>>,
+    ) -> Self {
+        Self {
+            profiles,
+            active: RwLock::new((active, coze_config)),
+            config: ConfigTool::new(),
+            space_id: RwLock::new(None),
+            cached: RwLock::new(None),
+            limiter: None,
+            local_files: LocalFiles::default(),
+            access: SessionAccess::default(),
+        }
+    }
+
+    /// 本会话创建的所有客户端使用 `limiter`，与其他会话共享限流预算
+    pub fn with_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// 本会话的工具按路径访问本地文件的范围
+    pub fn with_local_files(mut self, local_files: LocalFiles) -> Self {
+        self.local_files = local_files;
+        self
+    }
+
+    /// 本会话可使用的进程级能力（默认全部允许，即 stdio 的行为）
+    pub fn with_access(mut self, access: SessionAccess) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// 处理 `configure` 工具：设置本会话的 api_key（必填）与 space_id（可选）
+    pub async fn configure(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let space_id = args
+            .as_ref()
+            .and_then(|a| a.get("space_id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let result = self.config.set_api_key_from_mcp(args).await?;
+        if result.is_error != Some(true) {
+            if let Some(space_id) = space_id {
+                *self.space_id.write().await = Some(space_id);
+            }
+            // 强制下次调用按新凭证重建客户端
+            *self.cached.write().await = None;
+        }
+        Ok(result)
+    }
+
+    /// 当前会话使用的 profile 名称
+    pub async fn active_profile(&self) -> Option<String> {
+        self.active.read().await.0.clone()
+    }
+
+    /// 切换本会话的 profile；`configure` 设置的 key 与 space_id 属于旧租户，一并清除
+    pub async fn switch_profile(&self, name: &str) -> Result<(), String> {
+        if !self.access.switch_profile {
+            return Err(
+                "当前传输方式下不允许切换 profile（需运维者设置 server.allow_profile_switch）"
+                    .to_string(),
+            );
+        }
+        let coze_config = self.profiles.get(name).cloned().ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(|k| k.as_str()).collect();
+            format!("未知 profile: {name}（可用: {}）", known.join(", "))
+        })?;
+        *self.active.write().await = (Some(name.to_string()), coze_config);
+        self.config.clear_api_key().await;
+        *self.space_id.write().await = None;
+        *self.cached.write().await = None;
+        Ok(())
+    }
+
+    /// 处理 `profiles` 工具：列出可用 profile；传入 `name` 时先切换到该 profile
+    pub async fn profiles_tool(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        if let Some(name) = args
+            .as_ref()
+            .and_then(|a| a.get("name"))
+            .and_then(|v| v.as_str())
+        {
+            if let Err(msg) = self.switch_profile(name).await {
+                return Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(msg.clone())]),
+                    is_error: Some(true),
+                    structured_content: Some(serde_json::json!({"error": msg})),
+                });
+            }
+        }
+
+        let active = self.active_profile().await;
+        // 不回显 token 本身；远程会话连是否配置了 token 也不展示
+        let items: Vec<Value> = self
+            .profiles
+            .iter()
+            .map(|(name, c)| {
+                let mut item = serde_json::json!({
+                    "name": name,
+                    "active": active.as_deref() == Some(name.as_str()),
+                    "base_url": c.base_url,
+                    "default_space_id": c.default_space_id,
+                    "timeout": c.timeout,
+                    "retry_attempts": c.retry_attempts,
+                });
+                if self.access.show_token_status {
+                    item["has_token"] = Value::Bool(!c.api_token.is_empty());
+                }
+                item
+            })
+            .collect();
+
+        let mut text = format!(
+            "当前 profile: {}\n",
+            active.as_deref().unwrap_or("(未使用 profile)")
+        );
+        if items.is_empty() {
+            text.push_str("配置文件中未定义 profile");
+        }
+        for (name, c) in self.profiles.iter() {
+            let mark = if active.as_deref() == Some(name.as_str()) {
+                "*"
+            } else {
+                " "
+            };
+            text.push_str(&format!(
+                "{mark} {name}  {}  space={}\n",
+                c.base_url, c.default_space_id
+            ));
+        }
+
+        Ok(CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(text)]),
+            is_error: Some(false),
+            structured_content: Some(serde_json::json!({
+                "active": active,
+                "profiles": items,
+            })),
+        })
+    }
+
+    /// 按会话凭证解析工具实例：请求头中的 token > `configure` 设置的 token > 当前 profile 的 token
+    /// （仅 `SessionAccess::operator_token` 允许时）。都没有时返回 `None`。
+    pub async fn tools(
+        &self,
+        header_token: Option<&str>,
+    ) -> Result<Option<Arc<CozeTools>>, ApiError> {
+        let coze_config = self.active.read().await.1.clone();
+        let token = match header_token {
+            Some(t) => t.to_string(),
+            None => match self.config.get_api_key().await {
+                Some(t) => t,
+                None if self.access.operator_token && !coze_config.api_token.is_empty() => {
+                    coze_config.api_token.clone()
+                }
+                None => return Ok(None),
+            },
+        };
+
+        if let Some((cached_token, tools)) = self.cached.read().await.as_ref() {
+            if *cached_token == token {
+                return Ok(Some(tools.clone()));
+            }
+        }
+
+        let space_id = self
+            .space_id
+            .read()
+            .await
+            .clone()
+            .unwrap_or_else(|| coze_config.default_space_id.clone());
+        let config = CozeConfig {
+            api_token: token.clone(),
+            ..coze_config
+        };
+        let client = match &self.limiter {
+            Some(limiter) => CozeApiClient::with_limiter(&config, limiter.clone())?,
+            None => CozeApiClient::from_config(&config)?,
+        };
+        let tools = Arc::new(
+            CozeTools::new(Arc::new(client), space_id).with_local_files(self.local_files.clone()),
+        );
+        *self.cached.write().await = Some((token, tools.clone()));
+        Ok(Some(tools))
+    }
+}
+
+/// 从 HTTP 请求头中提取 `Authorization: Bearer <token>`
+pub fn bearer_token(headers: &axum::http::HeaderMap) -> Option<String> {
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| {
+            v.strip_prefix("Bearer ")
+                .or_else(|| v.strip_prefix("bearer "))
+        })
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
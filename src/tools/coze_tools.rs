@@ -1,15 +1,42 @@
+use crate::api::chat_models::ContentPart;
+use crate::api::pagination::{PageCursor, PageToken};
 use crate::api::CozeApiClient;
 use crate::models::{CozeApiRequest, HttpMethod};
+use crate::tools::local_files::LocalFiles;
+use crate::tools::progress::ProgressNotifier;
 use rmcp::model::CallToolResult;
 use rmcp::ErrorData as McpError;
 use serde_json::{json, Value};
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use uuid;
 
+/// Coze 文件上传接口的单文件大小上限
+const MAX_UPLOAD_FILE_SIZE: u64 = 512 * 1024 * 1024;
+
+/// get_workflow_run_history 等待执行结束的默认 / 最长时间（秒）
+const DEFAULT_WORKFLOW_WAIT_SECS: u64 = 60;
+const MAX_WORKFLOW_WAIT_SECS: u64 = 600;
+
+/// upload_document_to_knowledge_base 等待切片处理完成的默认 / 最长时间（秒）
+const DEFAULT_DOCUMENT_WAIT_SECS: u64 = 120;
+const MAX_DOCUMENT_WAIT_SECS: u64 = 600;
+
+/// 等待文件处理的结束方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentWait {
+    /// 所有文件都已处理完毕（成功或失败）
+    Finished,
+    TimedOut,
+    /// 客户端取消了请求，只停止等待，文件仍在后台处理
+    Cancelled,
+}
+
 #[derive(Debug, Clone)]
 pub struct CozeTools {
     coze_client: Arc<CozeApiClient>,
     default_space_id: String,
+    local_files: LocalFiles,
 }
 
 impl CozeTools {
@@ -17,9 +44,16 @@ impl CozeTools {
         Self {
             coze_client,
             default_space_id,
+            local_files: LocalFiles::default(),
         }
     }
 
+    /// 限制工具按路径读写本地文件的范围（默认不限制）
+    pub fn with_local_files(mut self, local_files: LocalFiles) -> Self {
+        self.local_files = local_files;
+        self
+    }
+
     // Helper: tolerant list + total extractor for various CN shapes
     fn extract_list_and_total(data: &Value) -> (Vec<Value>, usize) {
         let items = data
@@ -27,6 +61,7 @@ impl CozeTools {
             .or_else(|| data.get("dataset_list"))
             .or_else(|| data.get("list"))
             .or_else(|| data.get("items"))
+            .or_else(|| data.get("conversations"))
             .and_then(|v| v.as_array())
             .cloned()
             .unwrap_or_default();
@@ -42,6 +77,33 @@ impl CozeTools {
         obj.get(k).and_then(|v| v.as_str()).unwrap_or("")
     }
 
+    /// 解析列表工具共用的翻页参数，返回（起始游标, 条数上限, 页数上限）
+    ///
+    /// `cursor` 为上次返回的 `next_cursor`，缺省时从 `start` 开始；`all_pages` 为 true
+    /// 时一直翻到最后一页，否则只取一页；`limit` 限制返回的总条数。
+    fn parse_paging(
+        args: &Value,
+        start: PageCursor,
+    ) -> Result<(PageCursor, Option<usize>, Option<usize>), McpError> {
+        let cursor = match args.get("cursor") {
+            None | Some(Value::Null) => start,
+            Some(cursor) => serde_json::from_value(cursor.clone())
+                .map_err(|e| McpError::invalid_params(format!("Invalid cursor: {e}"), None))?,
+        };
+        let limit =
+            match args.get("limit") {
+                None | Some(Value::Null) => None,
+                Some(v) => Some(v.as_u64().filter(|n| *n >= 1).ok_or_else(|| {
+                    McpError::invalid_params("limit must be a positive integer", None)
+                })? as usize),
+            };
+        let all_pages = args
+            .get("all_pages")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        Ok((cursor, limit, (!all_pages).then_some(1)))
+    }
+
     // CN: write operations for datasets/files are not exposed via MCP tools by default.
 
     pub async fn list_knowledge_bases(
@@ -61,19 +123,40 @@ impl CozeTools {
             })
             .ok_or_else(|| McpError::invalid_params("Missing space_id parameter", None))?
             .to_string();
+        let name = args.get("name").and_then(|v| v.as_str());
+        let format_type = args
+            .get("format_type")
+            .and_then(|v| v.as_i64())
+            .map(|v| v as i32);
+        let page_num = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1) as u32;
+        let page_size = args
+            .get("page_size")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(100)
+            .clamp(1, 300) as u32;
+        let (cursor, limit, max_pages) =
+            Self::parse_paging(&args, PageCursor::new(PageToken::Page(page_num)))?;
         // 使用统一 /v1/datasets 接口
         match self
             .coze_client
-            .list_datasets(&space_id, None, None, None, None)
+            .list_datasets_paginated(&space_id, name, format_type, page_size, cursor)
+            .collect(limit, max_pages)
             .await
         {
-            Ok(mut result) => {
-                // Optional: refine document_count by fetching dataset detail (limited to first 50 to avoid many requests)
-                let content = if result.datasets.is_empty() {
+            Ok(result) => {
+                let total = result.total.unwrap_or(result.items.len() as u64);
+                let content = if result.items.is_empty() {
                     "没有找到知识库".to_string()
                 } else {
-                    let mut response = format!("找到 {} 个知识库:\n\n", result.total);
-                    for (i, kb) in result.datasets.iter().enumerate() {
+                    let mut response = format!(
+                        "找到 {total} 个知识库，本次返回 {} 个:\n\n",
+                        result.items.len()
+                    );
+                    for (i, kb) in result.items.iter().enumerate() {
                         response.push_str(&format!(
                             "{}. ID: {}\n   名称: {}\n   描述: {}\n   文档数量: {}\n   创建时间: {}\n\n",
                             i + 1,
@@ -87,7 +170,7 @@ impl CozeTools {
                     response
                 };
                 let sc_items: Vec<Value> = result
-                    .datasets
+                    .items
                     .iter()
                     .map(|kb| {
                         json!({
@@ -122,7 +205,13 @@ impl CozeTools {
                         })
                     })
                     .collect();
-                let structured = json!({ "total": result.total,  "items": sc_items });
+                let structured = json!({
+                    "total": total,
+                    "items": sc_items,
+                    "page_size": page_size,
+                    "next_cursor": result.next,
+                    "has_more": result.next.is_some(),
+                });
 
                 Ok(CallToolResult {
                     content: Some(vec![rmcp::model::Content::text(content)]),
@@ -199,14 +288,21 @@ impl CozeTools {
             .with_publish_status(publish_status)
             .with_connector_id(connector_id)
             .with_page(page_num, page_size);
+        let (cursor, limit, max_pages) =
+            Self::parse_paging(&args, PageCursor::new(PageToken::Page(page_num)))?;
 
-        match self.coze_client.list_bots_typed(&request).await {
-            Ok(response) => {
-                let total = response.data.total;
-                let mut out = format!("找到 {total} 个 Bot:\n\n");
+        match self
+            .coze_client
+            .list_bots_paginated(&request, cursor)
+            .collect(limit, max_pages)
+            .await
+        {
+            Ok(page) => {
+                let total = page.total.unwrap_or(page.items.len() as u64);
+                let mut out = format!("找到 {total} 个 Bot，本次返回 {} 个:\n\n", page.items.len());
                 let mut sc_items: Vec<Value> = Vec::new();
 
-                for (i, bot) in response.data.items.iter().take(5).enumerate() {
+                for (i, bot) in page.items.iter().enumerate() {
                     let status = if bot.is_published.unwrap_or(false) {
                         "published"
                     } else {
@@ -235,6 +331,8 @@ impl CozeTools {
                     "items": sc_items,
                     "page_num": page_num,
                     "page_size": page_size,
+                    "next_cursor": page.next,
+                    "has_more": page.next.is_some(),
                 });
 
                 Ok(CallToolResult {
@@ -257,6 +355,341 @@ impl CozeTools {
         }
     }
 
+    /// 查看 Bot 已发布版本的详细配置
+    pub async fn get_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let bot_id = Self::required_bot_id(args.as_ref())?;
+        match self.coze_client.retrieve_bot(&bot_id).await {
+            Ok(bot) => {
+                let mut out = format!(
+                    "Bot: {} (id: {}, version: {})\n",
+                    bot.name,
+                    bot.bot_id,
+                    bot.version.as_deref().unwrap_or("-")
+                );
+                if let Some(model) = &bot.model_info {
+                    out.push_str(&format!(
+                        "模型: {}\n",
+                        model
+                            .model_name
+                            .as_deref()
+                            .or(model.model_id.as_deref())
+                            .unwrap_or("-")
+                    ));
+                }
+                if let Some(prompt) = &bot.prompt_info {
+                    out.push_str(&format!("人设与回复逻辑:\n{}\n", prompt.prompt));
+                }
+                if let Some(prologue) = bot
+                    .onboarding_info
+                    .as_ref()
+                    .and_then(|o| o.prologue.as_deref())
+                {
+                    out.push_str(&format!("开场白: {prologue}\n"));
+                }
+                out.push_str(&format!(
+                    "插件: {} 个，工作流: {} 个，知识库: {} 个",
+                    bot.plugin_info_list.len(),
+                    bot.workflow_info_list.len(),
+                    bot.knowledge
+                        .as_ref()
+                        .map(|k| k.dataset_ids.len())
+                        .unwrap_or(0)
+                ));
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(out)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({"bot_id": bot.bot_id, "bot": bot})),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("Get Bot", &e)),
+        }
+    }
+
+    /// 创建 Bot；配置字段与 Coze 接口一致，另支持 `prompt` 字符串简写
+    pub async fn create_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::bot_models::CreateBotRequest;
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let space_id = args
+            .get("space_id")
+            .or_else(|| args.get("workspace_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(&self.default_space_id);
+        if space_id.is_empty() {
+            return Err(McpError::invalid_params(
+                "Missing space_id (or workspace_id) parameter",
+                None,
+            ));
+        }
+        let name = args
+            .get("name")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| McpError::invalid_params("Missing name parameter", None))?;
+
+        let request = CreateBotRequest::new(space_id.to_string(), name.to_string())
+            .with_config(Self::parse_bot_config(&args)?);
+        match self.coze_client.create_bot(&request).await {
+            Ok(created) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "Bot 创建成功（草稿，需 publish_bot 发布后才能通过 API 对话）\nbot_id: {}",
+                    created.bot_id
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "bot_id": created.bot_id,
+                    "space_id": space_id,
+                    "name": name
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Create Bot", &e)),
+        }
+    }
+
+    /// 更新 Bot 草稿配置；只提交传入的字段
+    pub async fn update_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::bot_models::{BotKnowledge, UpdateBotRequest};
+
+        let bot_id = Self::required_bot_id(args.as_ref())?;
+        let args = args.unwrap_or_default();
+        let mut request =
+            UpdateBotRequest::new(bot_id.clone()).with_config(Self::parse_bot_config(&args)?);
+        if let Some(name) = args.get("name").and_then(|v| v.as_str()) {
+            request = request.with_name(name.to_string());
+        }
+        if let Some(knowledge) = args.get("knowledge") {
+            let knowledge: BotKnowledge = serde_json::from_value(knowledge.clone())
+                .map_err(|e| McpError::invalid_params(format!("Invalid knowledge: {e}"), None))?;
+            request = request.with_knowledge(knowledge);
+        }
+        if request.is_empty() {
+            return Err(McpError::invalid_params(
+                "Nothing to update: provide at least one bot field",
+                None,
+            ));
+        }
+
+        match self.coze_client.update_bot(&request).await {
+            Ok(()) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "Bot {bot_id} 草稿已更新，调用 publish_bot 后对线上生效"
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({"bot_id": bot_id, "updated": true})),
+            }),
+            Err(e) => Ok(Self::api_error_result("Update Bot", &e)),
+        }
+    }
+
+    /// 发布 Bot 到指定渠道（默认 API 渠道 1024）
+    pub async fn publish_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::bot_models::PublishBotRequest;
+
+        let bot_id = Self::required_bot_id(args.as_ref())?;
+        let mut request = PublishBotRequest::new(bot_id);
+        if let Some(ids) = Self::parse_connector_ids(args.as_ref())? {
+            request = request.with_connector_ids(ids);
+        }
+
+        match self.coze_client.publish_bot(&request).await {
+            Ok(published) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "Bot {} 已发布到渠道 {}，版本: {}",
+                    published.bot_id,
+                    request.connector_ids.join(", "),
+                    published.version.as_deref().unwrap_or("-")
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "bot_id": published.bot_id,
+                    "version": published.version,
+                    "connector_ids": request.connector_ids
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Publish Bot", &e)),
+        }
+    }
+
+    /// 导出 Bot 配置为确定性的 JSON 文件内容；传入 output_path 时写入该文件
+    pub async fn export_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let bot_id = Self::required_bot_id(args.as_ref())?;
+        let output_path = args
+            .as_ref()
+            .and_then(|a| a.get("output_path"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty());
+        let write_path = output_path
+            .map(|path| self.local_files.writable(path))
+            .transpose()?;
+
+        let file = match crate::tools::bot_config::export_bot(&self.coze_client, &bot_id).await {
+            Ok(file) => file,
+            Err(e) => return Ok(Self::api_error_result("Export Bot", &e)),
+        };
+        let content = file.to_json().map_err(|e| {
+            McpError::internal_error(format!("Failed to serialize bot config: {e}"), None)
+        })?;
+        let text = match output_path.zip(write_path) {
+            Some((path, write_path)) => {
+                tokio::fs::write(&write_path, &content).await.map_err(|e| {
+                    McpError::invalid_params(format!("Failed to write {path}: {e}"), None)
+                })?;
+                format!("已导出 Bot {bot_id} 的配置到 {path}")
+            }
+            None => content,
+        };
+        Ok(CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(text)]),
+            is_error: Some(false),
+            structured_content: Some(json!({
+                "bot_id": bot_id,
+                "path": output_path,
+                "config": file
+            })),
+        })
+    }
+
+    /// 按配置文件创建（space_id）或更新（bot_id）Bot，可选随后发布
+    pub async fn import_bot(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::bot_models::{BotConfigFile, API_CONNECTOR_ID};
+        use crate::tools::bot_config::ImportTarget;
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let content = match (
+            args.get("path").and_then(|v| v.as_str()),
+            args.get("config"),
+        ) {
+            (Some(_), Some(_)) => {
+                return Err(McpError::invalid_params(
+                    "path and config are mutually exclusive",
+                    None,
+                ))
+            }
+            (Some(path), None) => {
+                let read_path = self.local_files.readable(path)?;
+                tokio::fs::read_to_string(&read_path).await.map_err(|e| {
+                    McpError::invalid_params(format!("Failed to read {path}: {e}"), None)
+                })?
+            }
+            (None, Some(config)) => config.to_string(),
+            (None, None) => {
+                return Err(McpError::invalid_params(
+                    "Missing path (or config) parameter",
+                    None,
+                ))
+            }
+        };
+        let file =
+            BotConfigFile::from_json(&content).map_err(|e| McpError::invalid_params(e, None))?;
+
+        let space_id = args
+            .get("space_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .unwrap_or(&self.default_space_id);
+        let target = ImportTarget::resolve(args.get("bot_id").and_then(|v| v.as_str()), space_id)
+            .ok_or_else(|| {
+            McpError::invalid_params(
+                "Provide bot_id to update a bot, or space_id to create one",
+                None,
+            )
+        })?;
+        let publish = args
+            .get("publish")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let connector_ids = Self::parse_connector_ids(Some(&args))?;
+        let connector_ids =
+            publish.then(|| connector_ids.unwrap_or_else(|| vec![API_CONNECTOR_ID.to_string()]));
+
+        let skip_variables = args
+            .get("skip_variables")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        match crate::tools::bot_config::import_bot(
+            &self.coze_client,
+            &file,
+            target,
+            connector_ids,
+            skip_variables,
+        )
+        .await
+        {
+            Ok(outcome) => {
+                let mut text = format!(
+                    "已{} Bot {}（{}）",
+                    if outcome.created { "创建" } else { "更新" },
+                    file.name,
+                    outcome.bot_id
+                );
+                if let Some(version) = &outcome.published_version {
+                    text.push_str(&format!("，并发布版本 {version}"));
+                }
+                if !outcome.skipped.is_empty() {
+                    text.push_str(&format!(
+                        "\n以下配置接口不支持设置，未导入: {}",
+                        outcome.skipped.join(", ")
+                    ));
+                }
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(text)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "bot_id": outcome.bot_id,
+                        "created": outcome.created,
+                        "published_version": outcome.published_version,
+                        "skipped": outcome.skipped
+                    })),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("Import Bot", &e)),
+        }
+    }
+
+    fn required_bot_id(args: Option<&Value>) -> Result<String, McpError> {
+        args.and_then(|a| a.get("bot_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .ok_or_else(|| McpError::invalid_params("Missing bot_id parameter", None))
+    }
+
+    /// 解析可选的发布渠道列表；传入时必须是非空字符串数组
+    fn parse_connector_ids(args: Option<&Value>) -> Result<Option<Vec<String>>, McpError> {
+        args.and_then(|a| a.get("connector_ids"))
+            .map(|ids| {
+                serde_json::from_value::<Vec<String>>(ids.clone())
+                    .ok()
+                    .filter(|ids| !ids.is_empty())
+                    .ok_or_else(|| {
+                        McpError::invalid_params(
+                            "connector_ids must be a non-empty array of strings",
+                            None,
+                        )
+                    })
+            })
+            .transpose()
+    }
+
+    /// 解析创建 / 更新共用的 Bot 配置；`prompt` 字符串优先于 `prompt_info`
+    fn parse_bot_config(args: &Value) -> Result<crate::api::bot_models::BotConfig, McpError> {
+        use crate::api::bot_models::{BotConfig, BotPromptInfo};
+
+        let mut config: BotConfig = serde_json::from_value(args.clone())
+            .map_err(|e| McpError::invalid_params(format!("Invalid bot config: {e}"), None))?;
+        if let Some(prompt) = args.get("prompt") {
+            let prompt = prompt
+                .as_str()
+                .ok_or_else(|| McpError::invalid_params("prompt must be a string", None))?;
+            config.prompt_info = Some(BotPromptInfo {
+                prompt: prompt.to_string(),
+            });
+        }
+        Ok(config)
+    }
+
     #[allow(dead_code)]
     pub async fn list_workspaces(&self, _args: Option<Value>) -> Result<CallToolResult, McpError> {
         let endpoint = "/v1/workspaces".to_string();
@@ -509,17 +942,160 @@ impl CozeTools {
                     is_error: Some(true),
                     structured_content: Some(json!({
                         "success": false,
-                        "error": error_msg
+                        "error": error_msg,
+                        "error_code": e.code(),
+                        "logid": e.logid()
                     })),
                 })
             }
         }
     }
 
+    /// 修改知识库名称、描述与图标
+    ///
+    /// 接口为全量更新：未传入的 name / description 先从知识库列表读取当前值补全；
+    /// 图标的 file_id 无法读取，未传入 file_id 时图标恢复默认。
+    pub async fn update_dataset(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::knowledge_models::UpdateDatasetRequest;
+
+        let dataset_id = Self::required_dataset_id(args.as_ref())?;
+        let args = args.unwrap_or(Value::Null);
+        let str_arg = |key: &str| args.get(key).and_then(|v| v.as_str());
+        let name = str_arg("name");
+        if let Some(name) = name {
+            if name.trim().is_empty() || name.chars().count() > 100 {
+                return Err(McpError::invalid_params(
+                    "name must be 1..=100 characters",
+                    None,
+                ));
+            }
+        }
+        let description = str_arg("description");
+        let file_id = str_arg("file_id").filter(|s| !s.is_empty());
+        if name.is_none() && description.is_none() && file_id.is_none() {
+            return Err(McpError::invalid_params(
+                "Nothing to update: provide name, description or file_id",
+                None,
+            ));
+        }
+
+        let (name, description) = match (name, description) {
+            (Some(name), Some(description)) => (name.to_string(), description.to_string()),
+            (name, description) => {
+                let space_id = self.space_id_arg(&args)?;
+                let current = match self.find_dataset(&space_id, &dataset_id).await {
+                    Ok(Some(current)) => current,
+                    Ok(None) => {
+                        return Err(McpError::invalid_params(
+                            format!("Dataset {dataset_id} not found in space {space_id}"),
+                            None,
+                        ))
+                    }
+                    Err(e) => return Ok(Self::api_error_result("Update Dataset", &e)),
+                };
+                (
+                    name.map(|s| s.to_string()).unwrap_or(current.name),
+                    description
+                        .map(|s| s.to_string())
+                        .unwrap_or(current.description),
+                )
+            }
+        };
+        let mut request = UpdateDatasetRequest::new(name).with_description(description);
+        if let Some(file_id) = file_id {
+            request = request.with_icon(file_id);
+        }
+
+        match self.coze_client.update_dataset(&dataset_id, &request).await {
+            Ok(()) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "知识库已更新\ndataset_id: {dataset_id}\n名称: {}\n描述: {}",
+                    request.name,
+                    request.description.as_deref().unwrap_or("")
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "dataset_id": dataset_id,
+                    "name": request.name,
+                    "description": request.description,
+                    "file_id": request.file_id,
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Update Dataset", &e)),
+        }
+    }
+
+    /// 删除知识库（连同其中的文件，不可恢复），必须显式传入 confirm=true
+    pub async fn delete_dataset(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let dataset_id = Self::required_dataset_id(args.as_ref())?;
+        let confirmed = args
+            .as_ref()
+            .and_then(|a| a.get("confirm"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        if !confirmed {
+            return Err(McpError::invalid_params(
+                format!(
+                    "Deleting dataset {dataset_id} also deletes all of its documents and cannot be undone; pass confirm=true to proceed"
+                ),
+                None,
+            ));
+        }
+
+        match self.coze_client.delete_dataset(&dataset_id).await {
+            Ok(()) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "知识库已删除\ndataset_id: {dataset_id}"
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "dataset_id": dataset_id,
+                    "deleted": true
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Delete Dataset", &e)),
+        }
+    }
+
+    fn space_id_arg(&self, args: &Value) -> Result<String, McpError> {
+        args.get("space_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .or_else(|| Some(self.default_space_id.as_str()).filter(|s| !s.is_empty()))
+            .map(|s| s.to_string())
+            .ok_or_else(|| McpError::invalid_params("Missing space_id parameter", None))
+    }
+
+    /// 在空间的知识库列表中查找指定知识库（没有按 ID 查看单个知识库的接口）
+    async fn find_dataset(
+        &self,
+        space_id: &str,
+        dataset_id: &str,
+    ) -> Result<Option<crate::api::KnowledgeBaseInfo>, crate::api::error::ApiError> {
+        use futures::TryStreamExt;
+
+        self.coze_client
+            .list_datasets_paginated(space_id, None, None, 300, PageCursor::default())
+            .items()
+            .try_filter(|kb| futures::future::ready(kb.dataset_id == dataset_id))
+            .try_next()
+            .await
+    }
+
     /// 上传文档到知识库（本地文件）
     pub async fn upload_document_to_knowledge_base(
         &self,
         args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        self.upload_document_to_knowledge_base_with_context(args, CancellationToken::new())
+            .await
+    }
+
+    /// 上传文档；`wait_until_processed` 时轮询切片进度，直到全部处理完毕 / 失败、超过 `timeout_secs` 或被取消
+    pub async fn upload_document_to_knowledge_base_with_context(
+        &self,
+        args: Option<Value>,
+        cancel: CancellationToken,
     ) -> Result<CallToolResult, McpError> {
         use crate::api::knowledge_models::{
             ChunkStrategyCn, DocumentBaseCn, KnowledgeDocumentUploadRequestCn, SourceInfo,
@@ -534,17 +1110,44 @@ impl CozeTools {
         let file_path = args
             .get("file_path")
             .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing file_path", None))?;
+            .filter(|s| !s.is_empty());
+        let web_url = args
+            .get("web_url")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty());
+        let update_rule = Self::update_rule_arg(&args)?;
+        match (file_path, web_url) {
+            (Some(_), Some(_)) => {
+                return Err(McpError::invalid_params(
+                    "Provide either file_path or web_url, not both",
+                    None,
+                ))
+            }
+            (None, None) => {
+                // 兼容原有提示；web_url 见工具参数说明
+                return Err(McpError::invalid_params("Missing file_path", None));
+            }
+            (Some(_), None) if update_rule.is_some() => {
+                return Err(McpError::invalid_params(
+                    "auto_update / update_interval only apply to web_url uploads",
+                    None,
+                ))
+            }
+            _ => {}
+        }
         let document_name = args
             .get("document_name")
             .and_then(|v| v.as_str())
+            .filter(|s| !s.trim().is_empty())
             .map(|s| s.to_string())
-            .unwrap_or_else(|| {
-                std::path::Path::new(file_path)
+            .unwrap_or_else(|| match file_path {
+                Some(file_path) => std::path::Path::new(file_path)
                     .file_name()
                     .and_then(|n| n.to_str())
                     .unwrap_or("document")
-                    .to_string()
+                    .to_string(),
+                // 在线网页默认以地址命名
+                None => web_url.unwrap_or_default().to_string(),
             });
         let chunk_size = args
             .get("chunk_size")
@@ -552,53 +1155,71 @@ impl CozeTools {
             .map(|v| v as usize)
             .unwrap_or(800);
 
-        let metadata = match fs::metadata(file_path).await {
-            Ok(metadata) => metadata,
-            Err(e) => {
-                return Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(format!(
-                        "Failed to read file metadata: {e}"
-                    ))]),
-                    is_error: Some(true),
-                    structured_content: Some(serde_json::json!({
-                        "error": "file_not_found",
-                        "message": format!("Failed to read file metadata: {}", e)
-                    })),
-                });
+        // CN spec: document_bases: [{ name, source_info{ file_base64, file_type } }]，在线网页为 source_info{ web_url, document_source: 1 }
+        let (source_info, file_size) = if let Some(web_url) = web_url {
+            match reqwest::Url::parse(web_url) {
+                Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+                _ => {
+                    return Err(McpError::invalid_params(
+                        format!("web_url must be an http(s) URL: {web_url}"),
+                        None,
+                    ))
+                }
+            }
+            (SourceInfo::web_page(web_url.to_string()), None)
+        } else {
+            let file_path = file_path.unwrap_or_default();
+            let read_path = self.local_files.readable(file_path)?;
+            let metadata = match fs::metadata(&read_path).await {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        content: Some(vec![rmcp::model::Content::text(format!(
+                            "Failed to read file metadata: {e}"
+                        ))]),
+                        is_error: Some(true),
+                        structured_content: Some(serde_json::json!({
+                            "error": "file_not_found",
+                            "message": format!("Failed to read file metadata: {}", e)
+                        })),
+                    });
+                }
+            };
+            let file_size = metadata.len();
+            if file_size == 0 {
+                return Err(McpError::invalid_params("File is empty", None));
+            }
+            if file_size > 10 * 1024 * 1024 {
+                return Err(McpError::invalid_params(
+                    "File exceeds 10MB size limit for this example",
+                    None,
+                ));
             }
-        };
-        let file_size = metadata.len();
-        if file_size == 0 {
-            return Err(McpError::invalid_params("File is empty", None));
-        }
-        if file_size > 10 * 1024 * 1024 {
-            return Err(McpError::invalid_params(
-                "File exceeds 10MB size limit for this example",
-                None,
-            ));
-        }
 
-        let bytes = fs::read(file_path)
-            .await
-            .map_err(|e| McpError::invalid_params(format!("Failed to read file: {e}"), None))?;
-        let ext = std::path::Path::new(file_path)
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-        // MIME 类型目前不直接发送（服务器依据 file_type 推断），保留扩展判断仅用于潜在后续扩展
-        // let mime_type = match ext.to_lowercase().as_str() { "txt" => "text/plain", "md" => "text/markdown", "pdf" => "application/pdf", "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document", _ => "application/octet-stream", };
-
-        let content_base64 = {
-            use base64::{engine::general_purpose, Engine as _};
-            general_purpose::STANDARD.encode(&bytes)
+            let bytes = fs::read(&read_path)
+                .await
+                .map_err(|e| McpError::invalid_params(format!("Failed to read file: {e}"), None))?;
+            let ext = std::path::Path::new(file_path)
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("");
+            // MIME 类型目前不直接发送（服务器依据 file_type 推断），保留扩展判断仅用于潜在后续扩展
+            // let mime_type = match ext.to_lowercase().as_str() { "txt" => "text/plain", "md" => "text/markdown", "pdf" => "application/pdf", "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document", _ => "application/octet-stream", };
+
+            let content_base64 = {
+                use base64::{engine::general_purpose, Engine as _};
+                general_purpose::STANDARD.encode(&bytes)
+            };
+            (
+                SourceInfo::file_base64(content_base64, ext.to_string()),
+                Some(file_size),
+            )
         };
-        // CN spec: document_bases: [{ name, source_info{ file_base64, file_type } }]
-        let source_info = SourceInfo::file_base64(content_base64, ext.to_string());
         let document_cn = DocumentBaseCn {
             name: document_name.clone(),
             source_info,
             caption: None,
-            update_rule: None,
+            update_rule,
         };
         // chunk_strategy: choose custom (chunk_type=1) with separator and max_tokens
         let separator = args
@@ -613,6 +1234,26 @@ impl CozeTools {
             .get("format_type")
             .and_then(|v| v.as_i64())
             .unwrap_or(0) as i32; // default 0 text
+        if web_url.is_some() && format_type != 0 {
+            return Err(McpError::invalid_params(
+                "web_url uploads only support text knowledge bases (format_type=0)",
+                None,
+            ));
+        }
+        let wait = args
+            .get("wait_until_processed")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let timeout = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_DOCUMENT_WAIT_SECS)
+            .min(MAX_DOCUMENT_WAIT_SECS);
+        let interval = args
+            .get("poll_interval_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(2)
+            .clamp(1, 60);
         let request = KnowledgeDocumentUploadRequestCn {
             dataset_id: dataset_id.to_string(),
             document_bases: vec![document_cn],
@@ -622,24 +1263,79 @@ impl CozeTools {
         match self.coze_client.upload_document_cn(request).await {
             Ok(resp) => {
                 let infos_len = resp.document_infos.as_ref().map(|v| v.len()).unwrap_or(0);
-                let content = format!(
-                    "文档上传成功: dataset_id={dataset_id}, 文件='{document_name}', size={file_size} bytes, documents_returned={infos_len}"
-                );
+                let mut content = match (web_url, file_size) {
+                    (Some(web_url), _) => format!(
+                        "网页上传成功: dataset_id={dataset_id}, 名称='{document_name}', url={web_url}, documents_returned={infos_len}"
+                    ),
+                    (None, size) => format!(
+                        "文档上传成功: dataset_id={dataset_id}, 文件='{document_name}', size={} bytes, documents_returned={infos_len}",
+                        size.unwrap_or_default()
+                    ),
+                };
+                if let Some(rule) = &update_rule {
+                    match rule.update_interval {
+                        Some(hours) if rule.update_type == 1 => {
+                            content.push_str(&format!("\n自动更新: 每 {hours} 小时"))
+                        }
+                        _ => content.push_str("\n自动更新: 关闭"),
+                    }
+                }
+                let mut structured = json!({
+                    "dataset_id": dataset_id,
+                    "file_name": document_name,
+                    "file_size": file_size,
+                    "web_url": web_url,
+                    "update_rule": update_rule,
+                    "returned_count": infos_len,
+                    "documents": resp.document_infos,
+                    "code": resp.code,
+                    "msg": resp.msg,
+                });
+                let document_ids: Vec<String> = resp
+                    .document_infos
+                    .iter()
+                    .flatten()
+                    .filter(|d| !d.document_id.is_empty())
+                    .map(|d| d.document_id.clone())
+                    .collect();
+                let mut failed = false;
+                if wait && !document_ids.is_empty() {
+                    let waited = self
+                        .wait_for_documents(dataset_id, &document_ids, timeout, interval, &cancel)
+                        .await;
+                    let obj = structured.as_object_mut().expect("object literal");
+                    match waited {
+                        Ok((progress, end)) => {
+                            failed = progress.iter().any(|p| p.is_failed());
+                            content.push('\n');
+                            content.push_str(&Self::document_progress_text(&progress, Some(end)));
+                            obj.insert("progress".to_string(), json!(progress));
+                            obj.insert(
+                                "processed".to_string(),
+                                json!(end == DocumentWait::Finished),
+                            );
+                            obj.insert("timeout".to_string(), json!(end == DocumentWait::TimedOut));
+                            obj.insert(
+                                "cancelled".to_string(),
+                                json!(end == DocumentWait::Cancelled),
+                            );
+                        }
+                        // 上传已成功，只是无法确认处理结果
+                        Err(e) => {
+                            content.push_str(&format!("\n查询处理进度失败: {e}"));
+                            obj.insert("processed".to_string(), json!(false));
+                            obj.insert("progress_error".to_string(), json!(e.to_string()));
+                        }
+                    }
+                }
                 Ok(CallToolResult {
                     content: Some(vec![rmcp::model::Content::text(content)]),
-                    is_error: Some(false),
-                    structured_content: Some(json!({
-                        "dataset_id": dataset_id,
-                        "file_name": document_name,
-                        "file_size": file_size,
-                        "returned_count": infos_len,
-                        "code": resp.code,
-                        "msg": resp.msg,
-                    })),
+                    is_error: Some(failed),
+                    structured_content: Some(structured),
                 })
             }
             Err(e) => {
-                println!("Failed to upload document: {e:?}");
+                tracing::warn!(dataset_id = %dataset_id, "上传知识库文件失败: {}", e);
                 let serialized =
                     serde_json::to_value(&e).unwrap_or(json!({"error": e.to_string()}));
                 Ok(CallToolResult {
@@ -653,72 +1349,953 @@ impl CozeTools {
         }
     }
 
-    /// 列出会话（最小实现）
-    pub async fn list_conversations(
+    /// 查看知识库文件的切片处理进度（上传后确认是否处理完毕）
+    pub async fn get_document_progress(
         &self,
         args: Option<Value>,
     ) -> Result<CallToolResult, McpError> {
-        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
-        let workspace_id = args
-            .get("workspace_id")
-            .or_else(|| args.get("space_id"))
-            .and_then(|v| v.as_str())
-            .or_else(|| {
-                if !self.default_space_id.is_empty() {
-                    Some(&self.default_space_id)
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(|| {
-                McpError::invalid_params("Missing workspace_id (or space_id) parameter", None)
-            })?;
-        let bot_id = args
-            .get("bot_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing bot_id parameter", None))?;
-        let page = args.get("page").and_then(|v| v.as_u64()).unwrap_or(1);
-        let page_size = args.get("page_size").and_then(|v| v.as_u64()).unwrap_or(20);
+        let dataset_id = Self::required_dataset_id(args.as_ref())?;
+        let document_ids = Self::document_ids_arg(&args.unwrap_or(Value::Null))?;
+
         match self
             .coze_client
-            .list_conversations_v1(
-                bot_id,
-                Some(workspace_id),
-                Some(page as u32),
-                Some(page_size as u32),
-            )
+            .document_progress(&dataset_id, &document_ids)
             .await
         {
-            Ok(body) => {
-                let data = body.get("data").cloned().unwrap_or(body);
-                let (items, total) = Self::extract_list_and_total(&data);
-                let mut out = format!("{total} 条会话，page={page}, page_size={page_size}:\n\n");
-                let mut sc: Vec<Value> = Vec::new();
-                for (i, it) in items.iter().take(5).enumerate() {
-                    if let Some(obj) = it.as_object() {
-                        let cid = Self::get_str(obj, "conversation_id");
-                        let title = Self::get_str(obj, "title");
-                        out.push_str(&format!("{}. {} (id: {})\n", i + 1, title, cid));
-                        sc.push(json!({"conversation_id": cid, "title": title}));
-                    }
-                }
-                Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(out)]),
-                    is_error: Some(false),
-                    structured_content: Some(json!({"total": total, "items": sc})),
-                })
-            }
-            Err(e) => {
-                let serialized =
-                    serde_json::to_value(&e).unwrap_or(json!({"error": e.to_string()}));
+            Ok(progress) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(
+                    Self::document_progress_text(&progress, None),
+                )]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "dataset_id": dataset_id,
+                    "documents": progress,
+                    "processing": progress.iter().filter(|p| p.is_processing()).count(),
+                    "failed": progress.iter().filter(|p| p.is_failed()).count(),
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Get Document Progress", &e)),
+        }
+    }
+
+    /// 轮询切片进度直到全部结束；返回最后一次进度与是否全部结束（超时或取消时为 false）
+    async fn wait_for_documents(
+        &self,
+        dataset_id: &str,
+        document_ids: &[String],
+        timeout: u64,
+        interval: u64,
+        cancel: &CancellationToken,
+    ) -> Result<
+        (
+            Vec<crate::api::knowledge_models::DocumentProgress>,
+            DocumentWait,
+        ),
+        crate::api::error::ApiError,
+    > {
+        let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(timeout);
+        loop {
+            let progress = self
+                .coze_client
+                .document_progress(dataset_id, document_ids)
+                .await?;
+            if !progress.iter().any(|p| p.is_processing()) {
+                return Ok((progress, DocumentWait::Finished));
+            }
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Ok((progress, DocumentWait::TimedOut));
+            }
+            tracing::debug!(
+                "等待文件处理完成... (剩余 {:?}，dataset_id: {dataset_id})",
+                deadline - now
+            );
+            let sleep = tokio::time::Duration::from_secs(interval).min(deadline - now);
+            tokio::select! {
+                _ = cancel.cancelled() => return Ok((progress, DocumentWait::Cancelled)),
+                _ = tokio::time::sleep(sleep) => {}
+            }
+        }
+    }
+
+    fn document_progress_text(
+        progress: &[crate::api::knowledge_models::DocumentProgress],
+        wait: Option<DocumentWait>,
+    ) -> String {
+        let mut text = format!(
+            "文件处理进度{}:",
+            match wait {
+                Some(DocumentWait::TimedOut) => "（等待超时，仍有文件在处理）",
+                Some(DocumentWait::Cancelled) => "（已取消等待，仍有文件在处理）",
+                _ => "",
+            }
+        );
+        for p in progress {
+            text.push_str(&format!(
+                "\n- {} ({}): {}",
+                p.document_name.as_deref().unwrap_or(""),
+                p.document_id,
+                p.status_text()
+            ));
+            if p.is_processing() {
+                if let Some(percent) = p.progress {
+                    text.push_str(&format!(" {percent}%"));
+                }
+                if let Some(remaining) = p.remaining_time {
+                    text.push_str(&format!("，预计剩余 {remaining} 秒"));
+                }
+            } else if p.is_failed() {
+                if let Some(reason) = p.status_descript.as_deref().filter(|s| !s.is_empty()) {
+                    text.push_str(&format!("，原因: {reason}"));
+                }
+            }
+        }
+        text
+    }
+
+    /// 查看知识库中的文件列表，含处理状态、分段数与命中次数
+    pub async fn list_knowledge_documents(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        let dataset_id = Self::required_dataset_id(args.as_ref())?;
+        let args = args.unwrap_or(Value::Null);
+        let page = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1) as u32;
+        let page_size = args
+            .get("page_size")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20)
+            .clamp(1, 100) as u32;
+        let (cursor, limit, max_pages) =
+            Self::parse_paging(&args, PageCursor::new(PageToken::Page(page)))?;
+        match self
+            .coze_client
+            .list_documents_paginated(&dataset_id, page_size, cursor)
+            .collect(limit, max_pages)
+            .await
+        {
+            Ok(result) => {
+                let total = result.total.unwrap_or(result.items.len() as u64);
+                let content = if result.items.is_empty() {
+                    format!("知识库 {dataset_id} 中没有文件")
+                } else {
+                    let mut out = format!(
+                        "知识库 {dataset_id} 共 {total} 个文件，本次返回 {} 个:\n\n",
+                        result.items.len()
+                    );
+                    for (i, doc) in result.items.iter().enumerate() {
+                        out.push_str(&format!(
+                            "{}. {} (document_id: {})\n   状态: {}  分段数: {}  命中次数: {}\n",
+                            i + 1,
+                            doc.name,
+                            doc.document_id,
+                            doc.status_text(),
+                            doc.slice_count.unwrap_or(0),
+                            doc.hit_count.unwrap_or(0)
+                        ));
+                    }
+                    out
+                };
                 Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(format!(
-                        "[List Conversations] 请求失败: {e}"
-                    ))]),
-                    is_error: Some(true),
-                    structured_content: Some(json!({"error": serialized})),
+                    content: Some(vec![rmcp::model::Content::text(content)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "dataset_id": dataset_id,
+                        "total": total,
+                        "items": result.items,
+                        "page_size": page_size,
+                        "next_cursor": result.next,
+                        "has_more": result.next.is_some()
+                    })),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("List Knowledge Documents", &e)),
+        }
+    }
+
+    /// 修改知识库文件：重命名，或设置在线网页的自动更新规则
+    pub async fn update_knowledge_document(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::api::knowledge_models::UpdateDocumentRequest;
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let document_id = args
+            .get("document_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| McpError::invalid_params("Missing document_id parameter", None))?;
+        let mut request = UpdateDocumentRequest::new(document_id);
+        if let Some(name) = args.get("document_name").and_then(|v| v.as_str()) {
+            if name.trim().is_empty() {
+                return Err(McpError::invalid_params(
+                    "document_name cannot be empty",
+                    None,
+                ));
+            }
+            request = request.with_name(name);
+        }
+        if let Some(rule) = Self::update_rule_arg(&args)? {
+            request = request.with_update_rule(rule);
+        }
+        if request.is_empty() {
+            return Err(McpError::invalid_params(
+                "Nothing to update: provide document_name, auto_update or update_interval",
+                None,
+            ));
+        }
+
+        match self.coze_client.update_document(&request).await {
+            Ok(()) => {
+                let mut out = format!("文件已更新\ndocument_id: {document_id}");
+                if let Some(name) = &request.document_name {
+                    out.push_str(&format!("\n名称: {name}"));
+                }
+                if let Some(rule) = &request.update_rule {
+                    match rule.update_interval {
+                        Some(hours) if rule.update_type == 1 => {
+                            out.push_str(&format!("\n自动更新: 每 {hours} 小时"))
+                        }
+                        _ => out.push_str("\n自动更新: 关闭"),
+                    }
+                }
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(out)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "document_id": document_id,
+                        "document_name": request.document_name,
+                        "update_rule": request.update_rule,
+                    })),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("Update Knowledge Document", &e)),
+        }
+    }
+
+    /// 删除知识库文件：document_ids 或单个 document_id，单次最多 100 个
+    pub async fn delete_knowledge_documents(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::api::knowledge_models::{DeleteDocumentsRequest, MAX_DELETE_DOCUMENTS};
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let document_ids = Self::document_ids_arg(&args)?;
+        if document_ids.len() > MAX_DELETE_DOCUMENTS {
+            return Err(McpError::invalid_params(
+                format!("At most {MAX_DELETE_DOCUMENTS} documents can be deleted per call"),
+                None,
+            ));
+        }
+
+        let request = DeleteDocumentsRequest::new(document_ids);
+        match self.coze_client.delete_documents(&request).await {
+            Ok(()) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "已删除 {} 个文件: {}",
+                    request.document_ids.len(),
+                    request.document_ids.join(", ")
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "deleted": request.document_ids,
+                    "count": request.document_ids.len()
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Delete Knowledge Documents", &e)),
+        }
+    }
+
+    /// 由 auto_update / update_interval 组成在线网页的更新规则；两者都未传时为 None
+    fn update_rule_arg(
+        args: &Value,
+    ) -> Result<Option<crate::api::knowledge_models::DocumentUpdateRule>, McpError> {
+        use crate::api::knowledge_models::DocumentUpdateRule;
+
+        let auto_update = args.get("auto_update").and_then(|v| v.as_bool());
+        let update_interval = match args.get("update_interval") {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(
+                v.as_i64()
+                    .filter(|h| (24..=i32::MAX as i64).contains(h))
+                    .ok_or_else(|| {
+                        McpError::invalid_params(
+                            "update_interval must be an integer number of hours >= 24",
+                            None,
+                        )
+                    })? as i32,
+            ),
+        };
+        match (auto_update, update_interval) {
+            (Some(false), Some(_)) => Err(McpError::invalid_params(
+                "update_interval cannot be used with auto_update=false",
+                None,
+            )),
+            (Some(false), None) => Ok(Some(DocumentUpdateRule::no_auto_update())),
+            (_, Some(hours)) => Ok(Some(DocumentUpdateRule::every_hours(hours))),
+            // 开启自动更新但未指定间隔时，按最小间隔 24 小时
+            (Some(true), None) => Ok(Some(DocumentUpdateRule::every_hours(24))),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// 读取 document_ids 数组与单个 document_id，保持顺序去重，至少一个
+    fn document_ids_arg(args: &Value) -> Result<Vec<String>, McpError> {
+        let mut document_ids: Vec<String> = match args.get("document_ids") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(ids)) => ids
+                .iter()
+                .map(|id| {
+                    id.as_str()
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                        .ok_or_else(|| {
+                            McpError::invalid_params(
+                                "document_ids must be an array of non-empty strings",
+                                None,
+                            )
+                        })
                 })
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(McpError::invalid_params(
+                    "document_ids must be an array of strings",
+                    None,
+                ))
+            }
+        };
+        if let Some(id) = args
+            .get("document_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+        {
+            document_ids.push(id.to_string());
+        }
+        // 保持顺序去重
+        let mut seen = std::collections::HashSet::new();
+        document_ids.retain(|id| seen.insert(id.clone()));
+        if document_ids.is_empty() {
+            return Err(McpError::invalid_params(
+                "Missing document_ids (or document_id) parameter",
+                None,
+            ));
+        }
+        Ok(document_ids)
+    }
+
+    fn required_dataset_id(args: Option<&Value>) -> Result<String, McpError> {
+        args.and_then(|a| a.get("dataset_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .ok_or_else(|| McpError::invalid_params("Missing dataset_id parameter", None))
+    }
+
+    /// 上传文件到 Coze，返回 file_id（用于多模态对话、知识库图片与图标）。
+    /// 内容来自本地路径 `file_path`（受 `LocalFiles` 范围限制）或 Base64 编码的 `file_base64`
+    pub async fn upload_file(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use tokio::fs;
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let file_path = args
+            .get("file_path")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty());
+        let file_base64 = args
+            .get("file_base64")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty());
+        let file_name = args
+            .get("file_name")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string());
+
+        let (file_name, bytes) = match (file_path, file_base64) {
+            (Some(_), Some(_)) => {
+                return Err(McpError::invalid_params(
+                    "Provide either file_path or file_base64, not both",
+                    None,
+                ))
             }
+            (None, None) => return Err(McpError::invalid_params("Missing file_path", None)),
+            (None, Some(encoded)) => {
+                let file_name = file_name.ok_or_else(|| {
+                    McpError::invalid_params("file_name is required with file_base64", None)
+                })?;
+                let bytes = {
+                    use base64::{engine::general_purpose, Engine as _};
+                    general_purpose::STANDARD.decode(encoded).map_err(|e| {
+                        McpError::invalid_params(format!("Invalid file_base64: {e}"), None)
+                    })?
+                };
+                (file_name, bytes)
+            }
+            (Some(file_path), None) => {
+                let read_path = self.local_files.readable(file_path)?;
+                let file_name = file_name.unwrap_or_else(|| {
+                    std::path::Path::new(file_path)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("file")
+                        .to_string()
+                });
+                let metadata = match fs::metadata(&read_path).await {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        return Ok(CallToolResult {
+                            content: Some(vec![rmcp::model::Content::text(format!(
+                                "Failed to read file metadata: {e}"
+                            ))]),
+                            is_error: Some(true),
+                            structured_content: Some(json!({
+                                "error": "file_not_found",
+                                "message": format!("Failed to read file metadata: {}", e)
+                            })),
+                        });
+                    }
+                };
+                if metadata.len() > MAX_UPLOAD_FILE_SIZE {
+                    return Err(McpError::invalid_params(
+                        "File exceeds Coze's 512MB upload limit",
+                        None,
+                    ));
+                }
+                let bytes = fs::read(&read_path).await.map_err(|e| {
+                    McpError::invalid_params(format!("Failed to read file: {e}"), None)
+                })?;
+                (file_name, bytes)
+            }
+        };
+        if bytes.is_empty() {
+            return Err(McpError::invalid_params("File is empty", None));
+        }
+        if bytes.len() as u64 > MAX_UPLOAD_FILE_SIZE {
+            return Err(McpError::invalid_params(
+                "File exceeds Coze's 512MB upload limit",
+                None,
+            ));
+        }
+        let size = bytes.len() as u64;
+
+        match self.coze_client.upload_file(file_name, bytes).await {
+            Ok(file) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "文件上传成功\nfile_id: {}\n文件名: {}\n大小: {} 字节",
+                    file.id,
+                    file.file_name.as_deref().unwrap_or(""),
+                    file.bytes.unwrap_or(size)
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "file_id": file.id,
+                    "file": file
+                })),
+            }),
+            Err(e) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "[Upload File] 上传失败: {e}"
+                ))]),
+                is_error: Some(true),
+                structured_content: Some(json!({
+                    "error": e.to_string(),
+                    "code": e.code(),
+                    "logid": e.logid()
+                })),
+            }),
+        }
+    }
+
+    /// 查看已上传文件的详情
+    pub async fn get_file_info(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let file_id = args
+            .get("file_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::invalid_params("Missing file_id", None))?;
+
+        match self.coze_client.retrieve_file(file_id).await {
+            Ok(file) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "file_id: {}\n文件名: {}\n大小: {} 字节\n上传时间: {}",
+                    file.id,
+                    file.file_name.as_deref().unwrap_or(""),
+                    file.bytes.map(|b| b.to_string()).unwrap_or_default(),
+                    file.created_at.map(|t| t.to_string()).unwrap_or_default()
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "file_id": file.id,
+                    "file": file
+                })),
+            }),
+            Err(e) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "[Get File Info] 查询失败: {e}"
+                ))]),
+                is_error: Some(true),
+                structured_content: Some(json!({
+                    "error": e.to_string(),
+                    "code": e.code(),
+                    "logid": e.logid()
+                })),
+            }),
+        }
+    }
+
+    /// 列出会话（最小实现）
+    pub async fn list_conversations(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let workspace_id = args
+            .get("workspace_id")
+            .or_else(|| args.get("space_id"))
+            .and_then(|v| v.as_str())
+            .or_else(|| {
+                if !self.default_space_id.is_empty() {
+                    Some(&self.default_space_id)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing workspace_id (or space_id) parameter", None)
+            })?;
+        let bot_id = args
+            .get("bot_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::invalid_params("Missing bot_id parameter", None))?;
+        let page = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1) as u32;
+        let page_size = args
+            .get("page_size")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20)
+            .clamp(1, 50) as u32;
+        let (cursor, limit, max_pages) =
+            Self::parse_paging(&args, PageCursor::new(PageToken::Page(page)))?;
+        match self
+            .coze_client
+            .list_conversations_paginated(bot_id, Some(workspace_id), page_size, cursor)
+            .collect(limit, max_pages)
+            .await
+        {
+            Ok(result) => {
+                let items = result.items;
+                let mut out = format!(
+                    "{} 条会话（page_size={page_size}, has_more: {}）:\n\n",
+                    items.len(),
+                    result.next.is_some()
+                );
+                for (i, it) in items.iter().enumerate() {
+                    if let Some(obj) = it.as_object() {
+                        // 官方返回 id，兼容旧字段 conversation_id
+                        let cid = Some(Self::get_str(obj, "id"))
+                            .filter(|id| !id.is_empty())
+                            .unwrap_or_else(|| Self::get_str(obj, "conversation_id"));
+                        let title = Self::get_str(obj, "title");
+                        out.push_str(&format!("{}. {} (id: {})\n", i + 1, title, cid));
+                    }
+                }
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(out)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "total": result.total.unwrap_or(items.len() as u64),
+                        "items": items,
+                        "page_size": page_size,
+                        "next_cursor": result.next,
+                        "has_more": result.next.is_some()
+                    })),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("List Conversations", &e)),
+        }
+    }
+
+    /// 创建会话：可绑定 Bot，附带初始消息（文本或多模态片段）与元数据
+    pub async fn create_conversation(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::api::conversation_models::CreateConversationRequest;
+
+        let args = args.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+        let mut request = CreateConversationRequest::new();
+        if let Some(bot_id) = args.get("bot_id").and_then(|v| v.as_str()) {
+            request = request.with_bot_id(bot_id.to_string());
+        }
+        if let Some(messages) = args.get("messages") {
+            let messages =
+                Self::parse_messages(messages).map_err(|e| McpError::invalid_params(e, None))?;
+            request = request.with_messages(messages);
+        }
+        if let Some(meta) = args.get("meta_data") {
+            let meta: std::collections::HashMap<String, String> =
+                serde_json::from_value(meta.clone()).map_err(|e| {
+                    McpError::invalid_params(
+                        format!("meta_data must be an object of strings: {e}"),
+                        None,
+                    )
+                })?;
+            request = request.with_meta_data(meta);
+        }
+
+        match self.coze_client.create_conversation(&request).await {
+            Ok(conversation) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "会话创建成功\nconversation_id: {}",
+                    conversation.id
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "conversation_id": conversation.id,
+                    "conversation": conversation
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Create Conversation", &e)),
+        }
+    }
+
+    /// 查看会话信息
+    pub async fn get_conversation(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let conversation_id = Self::required_conversation_id(args.as_ref())?;
+        match self
+            .coze_client
+            .retrieve_conversation(&conversation_id)
+            .await
+        {
+            Ok(conversation) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "conversation_id: {}\n创建时间: {}\nlast_section_id: {}\nmeta_data: {}",
+                    conversation.id,
+                    conversation
+                        .created_at
+                        .map(|t| t.to_string())
+                        .unwrap_or_default(),
+                    conversation.last_section_id.as_deref().unwrap_or(""),
+                    json!(conversation.meta_data)
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "conversation_id": conversation.id,
+                    "conversation": conversation
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Get Conversation", &e)),
+        }
+    }
+
+    /// 清除会话上下文（历史消息保留，后续对话不再携带）
+    pub async fn clear_conversation(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        let conversation_id = Self::required_conversation_id(args.as_ref())?;
+        match self.coze_client.clear_conversation(&conversation_id).await {
+            Ok(section) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "已清除会话 {} 的上下文，新 section_id: {}",
+                    section.conversation_id, section.id
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "conversation_id": section.conversation_id,
+                    "section_id": section.id
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Clear Conversation", &e)),
+        }
+    }
+
+    /// 删除会话
+    pub async fn delete_conversation(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        let conversation_id = Self::required_conversation_id(args.as_ref())?;
+        match self.coze_client.delete_conversation(&conversation_id).await {
+            Ok(()) => Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(format!(
+                    "会话 {conversation_id} 已删除"
+                ))]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "conversation_id": conversation_id,
+                    "deleted": true
+                })),
+            }),
+            Err(e) => Ok(Self::api_error_result("Delete Conversation", &e)),
+        }
+    }
+
+    /// 在会话中创建消息（仅写入历史，不触发 Bot 回复）
+    pub async fn create_message(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let conversation_id = Self::required_conversation_id(args.as_ref())?;
+        let message = Self::parse_message(args.as_ref().unwrap_or(&Value::Null))
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        match self
+            .coze_client
+            .create_message(&conversation_id, &message)
+            .await
+        {
+            Ok(message) => Ok(Self::message_result("消息创建成功", &message)),
+            Err(e) => Ok(Self::api_error_result("Create Message", &e)),
+        }
+    }
+
+    /// 列出会话消息，返回完整消息与翻页游标
+    pub async fn list_messages(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::message_models::ListMessagesRequest;
+
+        let conversation_id = Self::required_conversation_id(args.as_ref())?;
+        let args = args.unwrap_or(Value::Null);
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+        };
+        if str_arg("before_id").is_some() && str_arg("after_id").is_some() {
+            return Err(McpError::invalid_params(
+                "before_id and after_id cannot be used together",
+                None,
+            ));
+        }
+        let mut request = ListMessagesRequest::new();
+        if let Some(order) = str_arg("order") {
+            if order != "asc" && order != "desc" {
+                return Err(McpError::invalid_params(
+                    "order must be \"asc\" or \"desc\"",
+                    None,
+                ));
+            }
+            request = request.with_order(order);
+        }
+        if let Some(chat_id) = str_arg("chat_id") {
+            request = request.with_chat_id(chat_id);
+        }
+        if let Some(before_id) = str_arg("before_id") {
+            request = request.with_before_id(before_id);
+        }
+        if let Some(after_id) = str_arg("after_id") {
+            request = request.with_after_id(after_id);
+        }
+        let (cursor, limit, max_pages) = Self::parse_paging(&args, PageCursor::default())?;
+        // 每页条数：page_size，缺省时不超过 limit；接口上限 50
+        let page_size = args
+            .get("page_size")
+            .and_then(|v| v.as_u64())
+            .or(limit.map(|l| l as u64));
+        if let Some(page_size) = page_size {
+            request = request.with_limit(page_size.clamp(1, 50) as u32);
+        }
+
+        match self
+            .coze_client
+            .list_messages_paginated(&conversation_id, &request, cursor)
+            .collect(limit, max_pages)
+            .await
+        {
+            Ok(page) => {
+                let has_more = page.next.is_some();
+                let mut out = format!("{} 条消息（has_more: {has_more}）:\n\n", page.items.len());
+                for (i, m) in page.items.iter().enumerate() {
+                    out.push_str(&format!(
+                        "{}. [{}] {} (id: {})\n",
+                        i + 1,
+                        json!(m.message.role).as_str().unwrap_or_default(),
+                        m.message.content.as_deref().unwrap_or(""),
+                        m.id
+                    ));
+                }
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(out)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "conversation_id": conversation_id,
+                        "first_id": page.items.first().map(|m| &m.id),
+                        "last_id": page.items.last().map(|m| &m.id),
+                        "items": page.items,
+                        "next_cursor": page.next,
+                        "has_more": has_more
+                    })),
+                })
+            }
+            Err(e) => Ok(Self::api_error_result("List Messages", &e)),
+        }
+    }
+
+    /// 查看单条消息
+    pub async fn get_message(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let (conversation_id, message_id) = Self::required_message_ids(args.as_ref())?;
+        match self
+            .coze_client
+            .retrieve_message(&conversation_id, &message_id)
+            .await
+        {
+            Ok(message) => Ok(Self::message_result("消息详情", &message)),
+            Err(e) => Ok(Self::api_error_result("Get Message", &e)),
+        }
+    }
+
+    /// 修改消息内容与元数据
+    pub async fn update_message(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let (conversation_id, message_id) = Self::required_message_ids(args.as_ref())?;
+        let args = args.unwrap_or(Value::Null);
+        let message = if args.get("content").is_some() {
+            Self::parse_message(&args).map_err(|e| McpError::invalid_params(e, None))?
+        } else if let Some(meta) = args.get("meta_data") {
+            // 只修改元数据；修改接口不提交 role
+            let meta =
+                Self::parse_meta_data(meta).map_err(|e| McpError::invalid_params(e, None))?;
+            crate::api::chat_models::ChatMessage {
+                role: crate::api::chat_models::MessageRole::User,
+                content: None,
+                content_type: None,
+                object_string: None,
+                meta_data: Some(meta),
+            }
+        } else {
+            return Err(McpError::invalid_params(
+                "Nothing to update: provide content or meta_data",
+                None,
+            ));
+        };
+        match self
+            .coze_client
+            .update_message(&conversation_id, &message_id, &message)
+            .await
+        {
+            Ok(message) => Ok(Self::message_result("消息已更新", &message)),
+            Err(e) => Ok(Self::api_error_result("Update Message", &e)),
+        }
+    }
+
+    /// 删除消息
+    pub async fn delete_message(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        let (conversation_id, message_id) = Self::required_message_ids(args.as_ref())?;
+        match self
+            .coze_client
+            .delete_message(&conversation_id, &message_id)
+            .await
+        {
+            Ok(message) => Ok(Self::message_result("消息已删除", &message)),
+            Err(e) => Ok(Self::api_error_result("Delete Message", &e)),
+        }
+    }
+
+    fn required_message_ids(args: Option<&Value>) -> Result<(String, String), McpError> {
+        let conversation_id = Self::required_conversation_id(args)?;
+        let message_id = args
+            .and_then(|a| a.get("message_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .ok_or_else(|| McpError::invalid_params("Missing message_id parameter", None))?;
+        Ok((conversation_id, message_id))
+    }
+
+    fn message_result(
+        title: &str,
+        message: &crate::api::message_models::ConversationMessage,
+    ) -> CallToolResult {
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "{title}\nmessage_id: {}\n角色: {}\n内容: {}",
+                message.id,
+                json!(message.message.role).as_str().unwrap_or_default(),
+                message.message.content.as_deref().unwrap_or("")
+            ))]),
+            is_error: Some(false),
+            structured_content: Some(json!({
+                "message_id": message.id,
+                "message": message
+            })),
+        }
+    }
+
+    fn required_conversation_id(args: Option<&Value>) -> Result<String, McpError> {
+        args.and_then(|a| a.get("conversation_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .ok_or_else(|| McpError::invalid_params("Missing conversation_id parameter", None))
+    }
+
+    /// 解析初始消息：`[{role, content}]`，每条消息的格式见 `parse_message`
+    fn parse_messages(value: &Value) -> Result<Vec<crate::api::chat_models::ChatMessage>, String> {
+        value
+            .as_array()
+            .ok_or_else(|| "messages must be an array".to_string())?
+            .iter()
+            .map(Self::parse_message)
+            .collect()
+    }
+
+    /// 解析单条消息 `{role, content, meta_data}`：content 为字符串时是文本消息，为数组时按多模态片段处理
+    fn parse_message(item: &Value) -> Result<crate::api::chat_models::ChatMessage, String> {
+        use crate::api::chat_models::{ChatMessage, MessageRole};
+
+        let role: MessageRole = item
+            .get("role")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| format!("Invalid message role: {e}"))?
+            .unwrap_or(MessageRole::User);
+        let mut message = match item.get("content") {
+            Some(Value::String(text)) => ChatMessage::text(role, text.clone()),
+            Some(parts @ Value::Array(_)) => {
+                let parts: Vec<ContentPart> = serde_json::from_value(parts.clone())
+                    .map_err(|e| format!("Invalid message content: {e}"))?;
+                if parts.is_empty() {
+                    return Err("Message content must not be empty".to_string());
+                }
+                for part in &parts {
+                    part.validate()
+                        .map_err(|e| format!("Invalid message content: {e}"))?;
+                }
+                ChatMessage::object_string(role, &parts)
+            }
+            _ => return Err("Each message needs a string or array content".to_string()),
+        };
+        if let Some(meta) = item.get("meta_data") {
+            message.meta_data = Some(Self::parse_meta_data(meta)?);
+        }
+        Ok(message)
+    }
+
+    fn parse_meta_data(value: &Value) -> Result<crate::api::chat_models::MessageMetaData, String> {
+        if !value.is_object() {
+            return Err("meta_data must be an object".to_string());
+        }
+        serde_json::from_value(value.clone()).map_err(|e| format!("Invalid meta_data: {e}"))
+    }
+
+    /// Coze 接口错误统一转为工具错误结果，保留错误码与 logid
+    fn api_error_result(tool: &str, e: &crate::api::error::ApiError) -> CallToolResult {
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "[{tool}] 请求失败: {e}"
+            ))]),
+            is_error: Some(true),
+            structured_content: Some(json!({
+                "error": e.to_string(),
+                "code": e.code(),
+                "logid": e.logid()
+            })),
         }
     }
 
@@ -726,6 +2303,15 @@ impl CozeTools {
 
     /// 发送聊天消息（非流式）
     pub async fn chat(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        self.chat_with_context(args, CancellationToken::new()).await
+    }
+
+    /// 发送聊天消息；`cancel` 触发时停止轮询并取消 Coze 侧的对话
+    pub async fn chat_with_context(
+        &self,
+        args: Option<Value>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
         let args = args.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
 
         let bot_id = match args.get("bot_id").and_then(|v| v.as_str()) {
@@ -741,15 +2327,13 @@ impl CozeTools {
             }
         };
 
-        let message = match args.get("message").and_then(|v| v.as_str()) {
-            Some(msg) => msg.to_string(),
-            None => {
+        let (message, parts) = match Self::parse_user_input(&args) {
+            Ok(input) => input,
+            Err(e) => {
                 return Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(
-                        "错误: 缺少必需的 message 参数",
-                    )]),
+                    content: Some(vec![rmcp::model::Content::text(format!("错误: {e}"))]),
                     is_error: Some(true),
-                    structured_content: Some(json!({"error": "Missing message parameter"})),
+                    structured_content: Some(json!({"error": e})),
                 });
             }
         };
@@ -775,6 +2359,9 @@ impl CozeTools {
         if let Some(cid) = conversation_id {
             chat_request = chat_request.with_conversation_id(cid);
         }
+        if let Some(parts) = parts {
+            chat_request = chat_request.with_content_parts(&parts);
+        }
 
         // 处理自定义变量
         if let Some(variables_obj) = args.get("custom_variables") {
@@ -801,156 +2388,287 @@ impl CozeTools {
                     format!("user_id: {user_id} (用户提供)\n")
                 };
 
-                // 如果状态是in_progress，等待完成并获取最终消息
-                if response.status.as_deref() == Some("in_progress")
-                    || response.status.as_deref() == Some("created")
+                self.await_chat(
+                    response,
+                    user_id_info,
+                    json!({"user_id": user_id, "user_id_generated": was_user_id_generated}),
+                    &cancel,
+                )
+                .await
+            }
+            Err(e) => {
+                let error_msg = format!("[Chat] 聊天失败: {e}");
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(error_msg)]),
+                    is_error: Some(true),
+                    structured_content: Some(json!({
+                        "error": e.to_string(),
+                        "code": e.code(),
+                        "logid": e.logid()
+                    })),
+                })
+            }
+        }
+    }
+
+    /// 解析本轮用户输入：`message` 文本与 `content` 多模态片段至少提供其一；
+    /// 两者同时提供时 message 作为第一个文本片段
+    fn parse_user_input(args: &Value) -> Result<(String, Option<Vec<ContentPart>>), String> {
+        let message = args
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let parts = match args.get("content") {
+            None | Some(Value::Null) => None,
+            Some(content) => {
+                let mut parts: Vec<ContentPart> = serde_json::from_value(content.clone())
+                    .map_err(|e| format!("Invalid content parameter: {e}"))?;
+                for part in &parts {
+                    part.validate()
+                        .map_err(|e| format!("Invalid content parameter: {e}"))?;
+                }
+                if let Some(text) = message.as_deref().filter(|m| !m.is_empty()) {
+                    parts.insert(0, ContentPart::text(text));
+                }
+                Some(parts)
+            }
+        };
+        match (message, parts) {
+            (_, Some(parts)) if parts.is_empty() => {
+                Err("Invalid content parameter: empty content".to_string())
+            }
+            (message, Some(parts)) => Ok((message.unwrap_or_default(), Some(parts))),
+            (Some(message), None) => Ok((message, None)),
+            (None, None) => Err("Missing message parameter".to_string()),
+        }
+    }
+
+    /// 等待非流式对话结束：轮询详情直到完成、需要端插件结果（requires_action）、超时或被取消。
+    /// `prefix` 为输出文本的前缀，`extra` 合并进完成时的结构化结果
+    async fn await_chat(
+        &self,
+        response: crate::api::chat_models::ChatResponse,
+        prefix: String,
+        extra: Value,
+        cancel: &CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        // 如果状态是in_progress，等待完成并获取最终消息
+        if response.status.as_deref() == Some("in_progress")
+            || response.status.as_deref() == Some("created")
+        {
+            // 等待对话完成
+            let mut final_status = response.status.clone();
+            let mut attempts = 0;
+            const MAX_ATTEMPTS: u32 = 30; // 最多等待30次，每次2秒
+
+            while (final_status.as_deref() == Some("in_progress")
+                || final_status.as_deref() == Some("created"))
+                && attempts < MAX_ATTEMPTS
+            {
+                tokio::select! {
+                    _ = cancel.cancelled() => {
+                        return Ok(self
+                            .cancel_chat_run(&response.conversation_id, Some(&response.id))
+                            .await);
+                    }
+                    _ = tokio::time::sleep(tokio::time::Duration::from_secs(2)) => {}
+                }
+                attempts += 1;
+
+                match self
+                    .coze_client
+                    .get_chat_detail(&response.conversation_id, &response.id)
+                    .await
                 {
-                    // 等待对话完成
-                    let mut final_status = response.status.clone();
-                    let mut attempts = 0;
-                    const MAX_ATTEMPTS: u32 = 30; // 最多等待30次，每次2秒
-
-                    while (final_status.as_deref() == Some("in_progress")
-                        || final_status.as_deref() == Some("created"))
-                        && attempts < MAX_ATTEMPTS
-                    {
-                        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-                        attempts += 1;
-
-                        match self
-                            .coze_client
-                            .get_chat_detail(&response.conversation_id, &response.id)
-                            .await
-                        {
-                            Ok(detail) => {
-                                final_status = detail.status.clone();
-                                if final_status.as_deref() == Some("completed") {
-                                    // 获取对话消息
-                                    match self
-                                        .coze_client
-                                        .get_chat_messages(&response.conversation_id, &response.id)
-                                        .await
-                                    {
-                                        Ok(messages) => {
-                                            // 找到助手的回复
-                                            let assistant_reply = messages.iter()
-                                                .filter(|msg| msg.role == crate::api::chat_models::MessageRole::Assistant)
-                                                .filter_map(|msg| msg.content.as_ref())
-                                                .map(|s| s.as_str())
-                                                .collect::<Vec<_>>()
-                                                .join("\n");
-
-                                            let output = format!(
-                                                "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n🤖 助手回复:\n{}\n",
-                                                user_id_info,
-                                                response.conversation_id,
-                                                response.id,
-                                                final_status.as_deref().unwrap_or("completed"),
-                                                if assistant_reply.is_empty() { "暂无回复内容" } else { &assistant_reply }
-                                            );
-
-                                            return Ok(CallToolResult {
-                                                content: Some(vec![rmcp::model::Content::text(
-                                                    output,
-                                                )]),
-                                                is_error: Some(false),
-                                                structured_content: Some(json!({
-                                                    "conversation_id": response.conversation_id,
-                                                    "message_id": response.id,
-                                                    "status": final_status,
-                                                    "user_id": user_id,
-                                                    "user_id_generated": was_user_id_generated,
-                                                    "assistant_reply": assistant_reply,
-                                                    "messages": messages
-                                                })),
-                                            });
-                                        }
-                                        Err(e) => {
-                                            let output = format!(
-                                                "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n⚠️ 获取消息失败: {}",
-                                                user_id_info,
-                                                response.conversation_id,
-                                                response.id,
-                                                final_status.as_deref().unwrap_or("completed"),
-                                                e
-                                            );
-
-                                            return Ok(CallToolResult {
-                                                content: Some(vec![rmcp::model::Content::text(
-                                                    output,
-                                                )]),
-                                                is_error: Some(true),
-                                                structured_content: Some(json!({
-                                                    "conversation_id": response.conversation_id,
-                                                    "message_id": response.id,
-                                                    "status": final_status,
-                                                    "error": format!("Failed to get messages: {}", e)
-                                                })),
-                                            });
+                    Ok(detail) => {
+                        final_status = detail.status.clone();
+                        if final_status.as_deref() == Some("requires_action") {
+                            return Ok(Self::requires_action_result(
+                                &response.conversation_id,
+                                &response.id,
+                                detail.required_action.as_ref(),
+                            ));
+                        }
+                        if final_status.as_deref() == Some("completed") {
+                            // 获取对话消息
+                            match self
+                                .coze_client
+                                .get_chat_messages(&response.conversation_id, &response.id)
+                                .await
+                            {
+                                Ok(messages) => {
+                                    // 找到助手的回复
+                                    let assistant_reply = messages
+                                        .iter()
+                                        .filter(|msg| {
+                                            msg.role
+                                                == crate::api::chat_models::MessageRole::Assistant
+                                        })
+                                        .filter_map(|msg| msg.content.as_ref())
+                                        .map(|s| s.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join("\n");
+
+                                    let output = format!(
+                                        "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n🤖 助手回复:\n{}\n",
+                                        prefix,
+                                        response.conversation_id,
+                                        response.id,
+                                        final_status.as_deref().unwrap_or("completed"),
+                                        if assistant_reply.is_empty() {
+                                            "暂无回复内容"
+                                        } else {
+                                            &assistant_reply
                                         }
+                                    );
+
+                                    let mut structured = json!({
+                                        "conversation_id": response.conversation_id,
+                                        "message_id": response.id,
+                                        "status": final_status,
+                                        "assistant_reply": assistant_reply,
+                                        "messages": messages
+                                    });
+                                    if let (Some(obj), Some(extra)) =
+                                        (structured.as_object_mut(), extra.as_object())
+                                    {
+                                        obj.extend(extra.clone());
                                     }
+                                    return Ok(CallToolResult {
+                                        content: Some(vec![rmcp::model::Content::text(output)]),
+                                        is_error: Some(false),
+                                        structured_content: Some(structured),
+                                    });
+                                }
+                                Err(e) => {
+                                    let output = format!(
+                                        "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n⚠️ 获取消息失败: {}",
+                                        prefix,
+                                        response.conversation_id,
+                                        response.id,
+                                        final_status.as_deref().unwrap_or("completed"),
+                                        e
+                                    );
+
+                                    return Ok(CallToolResult {
+                                        content: Some(vec![rmcp::model::Content::text(output)]),
+                                        is_error: Some(true),
+                                        structured_content: Some(json!({
+                                            "conversation_id": response.conversation_id,
+                                            "message_id": response.id,
+                                            "status": final_status,
+                                            "error": format!("Failed to get messages: {}", e)
+                                        })),
+                                    });
                                 }
-                            }
-                            Err(e) => {
-                                // 无法获取详情，继续等待
-                                println!(
-                                    "等待对话完成... (尝试 {attempts}/{MAX_ATTEMPTS}，错误: {e})"
-                                );
                             }
                         }
-                    }
-
-                    // 如果超时或失败
-                    let output = format!(
-                        "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n⏰ 等待超时或对话未完成，请稍后手动查询结果",
-                        user_id_info,
-                        response.conversation_id,
-                        response.id,
-                        final_status.as_deref().unwrap_or("timeout")
-                    );
-
-                    Ok(CallToolResult {
-                        content: Some(vec![rmcp::model::Content::text(output)]),
-                        is_error: Some(false),
-                        structured_content: Some(json!({
-                            "conversation_id": response.conversation_id,
-                            "message_id": response.id,
-                            "status": final_status,
-                            "timeout": true
-                        })),
-                    })
-                } else {
-                    // 对话已经完成或有其他状态
-                    let output = format!(
-                        "{}对话ID: {}\n消息ID: {}\n状态: {}\n",
-                        user_id_info,
-                        response.conversation_id,
-                        response.id,
-                        response.status.as_deref().unwrap_or("unknown")
-                    );
-
-                    Ok(CallToolResult {
-                        content: Some(vec![rmcp::model::Content::text(output)]),
-                        is_error: Some(false),
-                        structured_content: Some(
-                            serde_json::to_value(&response).unwrap_or_default(),
-                        ),
-                    })
+                    }
+                    Err(e) => {
+                        // 无法获取详情，继续等待
+                        tracing::debug!(
+                            "等待对话完成... (尝试 {attempts}/{MAX_ATTEMPTS}，错误: {e})"
+                        );
+                    }
                 }
             }
-            Err(e) => {
-                let error_msg = format!("[Chat] 聊天失败: {e}");
-                Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(error_msg)]),
-                    is_error: Some(true),
-                    structured_content: Some(json!({"error": e.to_string()})),
-                })
-            }
+
+            // 如果超时或失败
+            let output = format!(
+                "{}对话ID: {}\n消息ID: {}\n状态: {}\n\n⏰ 等待超时或对话未完成，请稍后手动查询结果",
+                prefix,
+                response.conversation_id,
+                response.id,
+                final_status.as_deref().unwrap_or("timeout")
+            );
+
+            Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(output)]),
+                is_error: Some(false),
+                structured_content: Some(json!({
+                    "conversation_id": response.conversation_id,
+                    "message_id": response.id,
+                    "status": final_status,
+                    "timeout": true
+                })),
+            })
+        } else if response.status.as_deref() == Some("requires_action") {
+            Ok(Self::requires_action_result(
+                &response.conversation_id,
+                &response.id,
+                response.required_action.as_ref(),
+            ))
+        } else {
+            // 对话已经完成或有其他状态
+            let output = format!(
+                "{}对话ID: {}\n消息ID: {}\n状态: {}\n",
+                prefix,
+                response.conversation_id,
+                response.id,
+                response.status.as_deref().unwrap_or("unknown")
+            );
+
+            Ok(CallToolResult {
+                content: Some(vec![rmcp::model::Content::text(output)]),
+                is_error: Some(false),
+                structured_content: Some(serde_json::to_value(&response).unwrap_or_default()),
+            })
+        }
+    }
+
+    /// 对话进入 requires_action：列出待执行的端插件调用，调用方执行后通过 submit_tool_outputs 提交结果
+    fn requires_action_result(
+        conversation_id: &str,
+        chat_id: &str,
+        required_action: Option<&Value>,
+    ) -> CallToolResult {
+        let tool_calls = required_action
+            .and_then(|a| a.pointer("/submit_tool_outputs/tool_calls"))
+            .cloned()
+            .unwrap_or_else(|| json!([]));
+        let mut output = format!(
+            "对话ID: {conversation_id}\n消息ID: {chat_id}\n状态: requires_action\n\n🔧 Bot 等待端插件执行结果，请执行以下调用后使用 submit_tool_outputs 提交:\n"
+        );
+        for call in tool_calls.as_array().into_iter().flatten() {
+            output.push_str(&format!(
+                "- tool_call_id: {}，函数: {}，参数: {}\n",
+                call.get("id").and_then(|v| v.as_str()).unwrap_or(""),
+                call.pointer("/function/name")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(""),
+                call.pointer("/function/arguments")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+            ));
+        }
+
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(output)]),
+            is_error: Some(false),
+            structured_content: Some(json!({
+                "conversation_id": conversation_id,
+                "chat_id": chat_id,
+                "status": "requires_action",
+                "required_action": required_action,
+                "tool_calls": tool_calls
+            })),
         }
     }
 
     /// 发送流式聊天消息
     pub async fn chat_stream(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        self.chat_stream_with_context(args, None, CancellationToken::new())
+            .await
+    }
+
+    /// 发送流式聊天消息，并把每个 `ConversationMessageDelta` 增量作为进度通知转发给客户端；
+    /// `cancel` 触发时停止读取并取消 Coze 侧的对话
+    pub async fn chat_stream_with_context(
+        &self,
+        args: Option<Value>,
+        progress: Option<ProgressNotifier>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
         let args = args.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
 
         let bot_id = args
@@ -959,11 +2677,8 @@ impl CozeTools {
             .ok_or_else(|| McpError::invalid_params("Missing bot_id parameter", None))?
             .to_string();
 
-        let message = args
-            .get("message")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| McpError::invalid_params("Missing message parameter", None))?
-            .to_string();
+        let (message, parts) =
+            Self::parse_user_input(&args).map_err(|e| McpError::invalid_params(e, None))?;
 
         let user_id = args
             .get("user_id")
@@ -986,6 +2701,9 @@ impl CozeTools {
         if let Some(cid) = conversation_id {
             chat_request = chat_request.with_conversation_id(cid);
         }
+        if let Some(parts) = parts {
+            chat_request = chat_request.with_content_parts(&parts);
+        }
 
         // 处理自定义变量
         if let Some(variables_obj) = args.get("custom_variables") {
@@ -1003,105 +2721,795 @@ impl CozeTools {
         }
 
         match self.coze_client.chat_stream(chat_request).await {
-            Ok(stream) => {
-                use futures::StreamExt;
-
-                let mut full_content = String::new();
-                let mut conversation_id = String::new();
-                let mut message_id = String::new();
-                let mut final_usage: Option<crate::api::chat_models::ChatUsage> = None;
-                let mut events = Vec::new();
-
-                // Pin the stream to make it ready for iteration
-                tokio::pin!(stream);
-
-                // 收集流式响应
-                while let Some(result) = stream.next().await {
-                    match result {
-                        Ok(response) => {
-                            events.push(serde_json::to_value(&response).unwrap_or_default());
-
-                            // 更新会话信息
-                            if let Some(cid) = &response.conversation_id {
-                                conversation_id = cid.clone();
-                            }
-                            if let Some(mid) = &response.id {
-                                message_id = mid.clone();
-                            }
+            Ok(stream) => self.collect_chat_stream(stream, progress, &cancel).await,
+            Err(e) => {
+                let error_msg = format!("[Chat Stream] 流式聊天失败: {e}");
+                Ok(CallToolResult {
+                    content: Some(vec![rmcp::model::Content::text(error_msg)]),
+                    is_error: Some(true),
+                    structured_content: Some(json!({
+                        "error": e.to_string(),
+                        "code": e.code(),
+                        "logid": e.logid()
+                    })),
+                })
+            }
+        }
+    }
 
-                            // 累积内容
-                            if let Some(delta) = &response.delta {
-                                if let Some(content) = &delta.content {
-                                    full_content.push_str(content);
-                                }
+    /// 提交端插件执行结果并继续对话
+    pub async fn submit_tool_outputs(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        self.submit_tool_outputs_with_context(args, None, CancellationToken::new())
+            .await
+    }
+
+    /// 提交端插件执行结果；`stream=true` 时以流式继续对话并推送进度，否则轮询直到对话结束
+    pub async fn submit_tool_outputs_with_context(
+        &self,
+        args: Option<Value>,
+        progress: Option<ProgressNotifier>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        let args = args.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+
+        let conversation_id = args
+            .get("conversation_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::invalid_params("Missing conversation_id parameter", None))?
+            .to_string();
+        let chat_id = args
+            .get("chat_id")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| McpError::invalid_params("Missing chat_id parameter", None))?
+            .to_string();
+        let outputs = args
+            .get("tool_outputs")
+            .and_then(|v| v.as_array())
+            .filter(|a| !a.is_empty())
+            .ok_or_else(|| McpError::invalid_params("Missing tool_outputs parameter", None))?;
+        let stream = args
+            .get("stream")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let mut tool_outputs = Vec::with_capacity(outputs.len());
+        for item in outputs {
+            let tool_call_id = item
+                .get("tool_call_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| {
+                    McpError::invalid_params("tool_outputs[].tool_call_id is required", None)
+                })?;
+            // output 允许直接传 JSON，统一序列化为字符串
+            let output = match item.get("output") {
+                Some(Value::String(s)) => s.clone(),
+                Some(other) => other.to_string(),
+                None => {
+                    return Err(McpError::invalid_params(
+                        "tool_outputs[].output is required",
+                        None,
+                    ))
+                }
+            };
+            tool_outputs.push(crate::api::chat_models::ToolOutput {
+                tool_call_id: tool_call_id.to_string(),
+                output,
+            });
+        }
+
+        let request = crate::api::chat_models::SubmitToolOutputsRequest::new(
+            conversation_id,
+            chat_id,
+            tool_outputs,
+        );
+        let error = if stream {
+            match self.coze_client.submit_tool_outputs_stream(request).await {
+                Ok(stream) => return self.collect_chat_stream(stream, progress, &cancel).await,
+                Err(e) => e,
+            }
+        } else {
+            match self.coze_client.submit_tool_outputs(request).await {
+                Ok(response) => {
+                    return self
+                        .await_chat(response, String::new(), json!({}), &cancel)
+                        .await
+                }
+                Err(e) => e,
+            }
+        };
+
+        Ok(CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "[Submit Tool Outputs] 提交失败: {error}"
+            ))]),
+            is_error: Some(true),
+            structured_content: Some(json!({
+                "error": error.to_string(),
+                "code": error.code(),
+                "logid": error.logid()
+            })),
+        })
+    }
+
+    /// 读取流式对话事件直到结束、需要端插件结果、失败或被取消（chat_stream 与 submit_tool_outputs 共用）
+    async fn collect_chat_stream<S>(
+        &self,
+        stream: S,
+        mut progress: Option<ProgressNotifier>,
+        cancel: &CancellationToken,
+    ) -> Result<CallToolResult, McpError>
+    where
+        S: futures::Stream<
+            Item = Result<crate::api::chat_models::StreamChatResponse, crate::api::error::ApiError>,
+        >,
+    {
+        use futures::StreamExt;
+
+        let mut full_content = String::new();
+        let mut conversation_id = String::new();
+        let mut message_id = String::new();
+        let mut chat_id: Option<String> = None;
+        let mut final_usage: Option<crate::api::chat_models::ChatUsage> = None;
+        let mut events = Vec::new();
+
+        // Pin the stream to make it ready for iteration
+        tokio::pin!(stream);
+
+        // 收集流式响应
+        loop {
+            let result = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    return Ok(self
+                        .cancel_chat_run(&conversation_id, chat_id.as_deref())
+                        .await);
+                }
+                next = stream.next() => match next {
+                    Some(result) => result,
+                    None => break,
+                },
+            };
+            match result {
+                Ok(response) => {
+                    events.push(serde_json::to_value(&response).unwrap_or_default());
+
+                    // 更新会话信息
+                    if let Some(cid) = &response.conversation_id {
+                        conversation_id = cid.clone();
+                    }
+                    if let Some(mid) = &response.id {
+                        message_id = mid.clone();
+                    }
+                    if chat_id.is_none() {
+                        chat_id = response.chat_id.clone().or_else(|| match response.event {
+                            crate::api::chat_models::StreamEventType::ConversationChatCreated
+                            | crate::api::chat_models::StreamEventType::ConversationChatInProgress
+                            | crate::api::chat_models::StreamEventType::ConversationChatRequiresAction => {
+                                response.id.clone()
                             }
+                            _ => None,
+                        });
+                    }
 
-                            // 保存最终使用情况
-                            if let Some(usage) = &response.usage {
-                                final_usage = Some(usage.clone());
+                    // 累积内容
+                    if let Some(delta) = &response.delta {
+                        if let Some(content) = &delta.content {
+                            full_content.push_str(content);
+                            if let (
+                                Some(notifier),
+                                crate::api::chat_models::StreamEventType::ConversationMessageDelta,
+                            ) = (progress.as_mut(), &response.event)
+                            {
+                                notifier.notify(content.clone()).await;
                             }
+                        }
+                    }
 
-                            // 检查是否完成
-                            match response.event {
-                                crate::api::chat_models::StreamEventType::Done |
-                                crate::api::chat_models::StreamEventType::ConversationChatCompleted => {
-                                    break;
-                                }
-                                crate::api::chat_models::StreamEventType::ConversationChatFailed => {
-                                    return Ok(CallToolResult {
-                                        content: Some(vec![rmcp::model::Content::text(
-                                            format!("[Chat Stream] 聊天失败: {:?}", response.last_error)
-                                        )]),
-                                        is_error: Some(true),
-                                        structured_content: Some(json!({
-                                            "error": "chat_failed",
-                                            "last_error": response.last_error,
-                                            "events": events
-                                        })),
-                                    });
-                                }
-                                _ => continue,
+                    // 保存最终使用情况
+                    if let Some(usage) = &response.usage {
+                        final_usage = Some(usage.clone());
+                    }
+
+                    // 检查是否完成
+                    match response.event {
+                        crate::api::chat_models::StreamEventType::Done |
+                        crate::api::chat_models::StreamEventType::ConversationChatCompleted => {
+                            break;
+                        }
+                        crate::api::chat_models::StreamEventType::ConversationChatRequiresAction => {
+                            let mut result = Self::requires_action_result(
+                                &conversation_id,
+                                response.id.as_deref().unwrap_or_default(),
+                                response.required_action.as_ref(),
+                            );
+                            // 附带 requires_action 之前已生成的回复
+                            if let Some(obj) = result
+                                .structured_content
+                                .as_mut()
+                                .and_then(|v| v.as_object_mut())
+                            {
+                                obj.insert("content".to_string(), json!(full_content));
+                                obj.insert("events".to_string(), json!(events));
                             }
+                            return Ok(result);
                         }
-                        Err(e) => {
+                        crate::api::chat_models::StreamEventType::ConversationChatFailed => {
                             return Ok(CallToolResult {
-                                content: Some(vec![rmcp::model::Content::text(format!(
-                                    "[Chat Stream] 流式响应错误: {e}"
-                                ))]),
+                                content: Some(vec![rmcp::model::Content::text(
+                                    format!("[Chat Stream] 聊天失败: {:?}", response.last_error)
+                                )]),
                                 is_error: Some(true),
                                 structured_content: Some(json!({
-                                    "error": e.to_string(),
+                                    "error": "chat_failed",
+                                    "last_error": response.last_error,
                                     "events": events
                                 })),
                             });
                         }
+                        _ => continue,
                     }
                 }
+                Err(e) => {
+                    return Ok(CallToolResult {
+                        content: Some(vec![rmcp::model::Content::text(format!(
+                            "[Chat Stream] 流式响应错误: {e}"
+                        ))]),
+                        is_error: Some(true),
+                        structured_content: Some(json!({
+                            "error": e.to_string(),
+                            "code": e.code(),
+                            "logid": e.logid(),
+                            "events": events
+                        })),
+                    });
+                }
+            }
+        }
 
-                let output = format!(
-                    "对话ID: {conversation_id}\n消息ID: {message_id}\n完整回复:\n{full_content}\n\n使用情况: {final_usage:?}"
-                );
+        let output = format!(
+            "对话ID: {conversation_id}\n消息ID: {message_id}\n完整回复:\n{full_content}\n\n使用情况: {final_usage:?}"
+        );
+
+        Ok(CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(output)]),
+            is_error: Some(false),
+            structured_content: Some(json!({
+                "conversation_id": conversation_id,
+                "message_id": message_id,
+                "content": full_content,
+                "usage": final_usage,
+                "events": events
+            })),
+        })
+    }
+
+    // ===== 工作流 =====
+
+    /// 列出工作空间中的工作流
+    pub async fn list_workflows(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        use crate::api::workflow_models::{ListWorkflowsRequest, WorkflowMode};
+
+        let args = args.unwrap_or_else(|| Value::Object(serde_json::Map::new()));
+        let workspace_id = args
+            .get("workspace_id")
+            .or_else(|| args.get("space_id"))
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .or_else(|| Some(self.default_space_id.as_str()).filter(|s| !s.is_empty()))
+            .ok_or_else(|| {
+                McpError::invalid_params("Missing workspace_id (or space_id) parameter", None)
+            })?;
+        let page = args
+            .get("page")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1)
+            .max(1);
+        let page_size = args
+            .get("page_size")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20)
+            .clamp(1, 30);
+        let (cursor, limit, max_pages) =
+            Self::parse_paging(&args, PageCursor::new(PageToken::Page(page as u32)))?;
+        let mut request = ListWorkflowsRequest::new(workspace_id.to_string())
+            .with_page(page as u32, page_size as u32);
+        if let Some(mode) = args.get("workflow_mode") {
+            let mode: WorkflowMode = serde_json::from_value(mode.clone()).map_err(|_| {
+                McpError::invalid_params("workflow_mode must be \"workflow\" or \"chatflow\"", None)
+            })?;
+            request = request.with_workflow_mode(mode);
+        }
+        if let Some(app_id) = args.get("app_id").and_then(|v| v.as_str()) {
+            request = request.with_app_id(app_id.to_string());
+        }
 
+        match self
+            .coze_client
+            .list_workflows_paginated(&request, cursor)
+            .collect(limit, max_pages)
+            .await
+        {
+            Ok(data) => {
+                let has_more = data.next.is_some();
+                let mut out = format!("{} 个工作流（has_more: {has_more}）:\n\n", data.items.len());
+                for (i, wf) in data.items.iter().enumerate() {
+                    out.push_str(&format!(
+                        "{}. {} (id: {})\n",
+                        i + 1,
+                        wf.workflow_name,
+                        wf.workflow_id
+                    ));
+                }
                 Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(output)]),
+                    content: Some(vec![rmcp::model::Content::text(out)]),
                     is_error: Some(false),
                     structured_content: Some(json!({
-                        "conversation_id": conversation_id,
-                        "message_id": message_id,
-                        "content": full_content,
-                        "usage": final_usage,
-                        "events": events
+                        "items": data.items,
+                        "has_more": has_more,
+                        "next_cursor": data.next,
+                        "page": page,
+                        "page_size": page_size
                     })),
                 })
             }
-            Err(e) => {
-                let error_msg = format!("[Chat Stream] 流式聊天失败: {e}");
+            Err(e) => Ok(Self::api_error_result("List Workflows", &e)),
+        }
+    }
+
+    /// 同步执行工作流
+    pub async fn run_workflow(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        self.run_workflow_with_context(args, CancellationToken::new())
+            .await
+    }
+
+    /// 同步执行工作流；客户端取消时停止等待
+    pub async fn run_workflow_with_context(
+        &self,
+        args: Option<Value>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        let args = args.unwrap_or(Value::Null);
+        let mut request = Self::parse_workflow_request(&args)?;
+        if let Some(is_async) = args.get("is_async").and_then(|v| v.as_bool()) {
+            request = request.with_async(is_async);
+        }
+        let workflow_id = request.workflow_id.clone();
+
+        let result = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => return Ok(Self::workflow_cancelled(&workflow_id)),
+            result = self.coze_client.run_workflow(&request) => result,
+        };
+        match result {
+            Ok(response) => {
+                let output = response.data.as_deref().map(Self::parse_workflow_output);
+                let text = match &response.execute_id {
+                    Some(execute_id) if response.data.as_deref().unwrap_or("").is_empty() => {
+                        format!(
+                            "工作流已异步执行\nexecute_id: {execute_id}\n使用 get_workflow_run_history 查询执行结果"
+                        )
+                    }
+                    _ => format!(
+                        "工作流执行完成\n输出: {}",
+                        response.data.as_deref().unwrap_or("")
+                    ),
+                };
                 Ok(CallToolResult {
-                    content: Some(vec![rmcp::model::Content::text(error_msg)]),
-                    is_error: Some(true),
-                    structured_content: Some(json!({"error": e.to_string()})),
+                    content: Some(vec![rmcp::model::Content::text(text)]),
+                    is_error: Some(false),
+                    structured_content: Some(json!({
+                        "workflow_id": workflow_id,
+                        "output": output,
+                        "execute_id": response.execute_id,
+                        "debug_url": response.debug_url,
+                        "usage": response.usage
+                    })),
                 })
             }
+            Err(e) => Ok(Self::api_error_result("Run Workflow", &e)),
+        }
+    }
+
+    /// 查询工作流执行结果
+    pub async fn get_workflow_run_history(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        self.get_workflow_run_history_with_context(args, CancellationToken::new())
+            .await
+    }
+
+    /// 查询异步执行的状态与输出；`wait` 时每隔 `poll_interval_secs` 轮询，直到结束、超过 `timeout_secs` 或被取消
+    pub async fn get_workflow_run_history_with_context(
+        &self,
+        args: Option<Value>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .ok_or_else(|| McpError::invalid_params(format!("Missing {key} parameter"), None))
+        };
+        let workflow_id = str_arg("workflow_id")?;
+        let execute_id = str_arg("execute_id")?;
+        let wait = args.get("wait").and_then(|v| v.as_bool()).unwrap_or(false);
+        let timeout = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_WORKFLOW_WAIT_SECS)
+            .min(MAX_WORKFLOW_WAIT_SECS);
+        let interval = args
+            .get("poll_interval_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(2)
+            .clamp(1, 60);
+        let deadline = tokio::time::Instant::now() + tokio::time::Duration::from_secs(timeout);
+
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let history = match self
+                .coze_client
+                .get_workflow_run_history(&workflow_id, &execute_id)
+                .await
+            {
+                Ok(history) => history,
+                Err(e) => return Ok(Self::api_error_result("Get Workflow Run History", &e)),
+            };
+            let now = tokio::time::Instant::now();
+            if !history.is_running() || !wait || now >= deadline {
+                return Ok(Self::workflow_history_result(
+                    &workflow_id,
+                    &history,
+                    wait && history.is_running(),
+                ));
+            }
+
+            tracing::debug!(
+                "等待工作流完成... (尝试 {attempts}，剩余 {:?}，execute_id: {execute_id})",
+                deadline - now
+            );
+            let sleep = tokio::time::Duration::from_secs(interval).min(deadline - now);
+            tokio::select! {
+                _ = cancel.cancelled() => {
+                    // 异步执行不受影响，只停止等待
+                    let mut result = Self::workflow_cancelled(&workflow_id);
+                    if let Some(obj) = result
+                        .structured_content
+                        .as_mut()
+                        .and_then(|v| v.as_object_mut())
+                    {
+                        obj.insert("execute_id".to_string(), json!(execute_id));
+                        obj.insert("execute_status".to_string(), json!(history.execute_status));
+                    }
+                    return Ok(result);
+                }
+                _ = tokio::time::sleep(sleep) => {}
+            }
+        }
+    }
+
+    fn workflow_history_result(
+        workflow_id: &str,
+        history: &crate::api::workflow_models::WorkflowRunHistory,
+        timed_out: bool,
+    ) -> CallToolResult {
+        use crate::api::workflow_models::execute_status;
+
+        let output = history
+            .output
+            .as_deref()
+            .filter(|o| !o.is_empty())
+            .map(Self::parse_workflow_output);
+        let failed = history.execute_status == execute_status::FAIL;
+        let text = if failed {
+            format!(
+                "工作流执行失败\nexecute_id: {}\n错误码: {}\n错误信息: {}",
+                history.execute_id,
+                history
+                    .error_code
+                    .map(|c| c.to_string())
+                    .unwrap_or_default(),
+                history.error_message.as_deref().unwrap_or("")
+            )
+        } else if history.is_running() {
+            format!(
+                "工作流仍在执行{}\nexecute_id: {}\n请稍后再次查询",
+                if timed_out { "（等待超时）" } else { "" },
+                history.execute_id
+            )
+        } else {
+            format!(
+                "工作流执行完成\nexecute_id: {}\n输出: {}",
+                history.execute_id,
+                history.output.as_deref().unwrap_or("")
+            )
+        };
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(text)]),
+            is_error: Some(failed),
+            structured_content: Some(json!({
+                "workflow_id": workflow_id,
+                "execute_id": history.execute_id,
+                "execute_status": history.execute_status,
+                "output": output,
+                "timeout": timed_out,
+                "code": history.error_code,
+                "error": history.error_message,
+                "history": history
+            })),
+        }
+    }
+
+    /// 工作流输出为 JSON 字符串，能解析时以结构化形式返回
+    fn parse_workflow_output(data: &str) -> Value {
+        serde_json::from_str::<Value>(data).unwrap_or_else(|_| json!(data))
+    }
+
+    /// 流式执行工作流
+    pub async fn run_workflow_stream(
+        &self,
+        args: Option<Value>,
+    ) -> Result<CallToolResult, McpError> {
+        self.run_workflow_stream_with_context(args, None, CancellationToken::new())
+            .await
+    }
+
+    /// 流式执行工作流：节点输出推送为进度通知，遇到中断时返回恢复所需的 event_id
+    pub async fn run_workflow_stream_with_context(
+        &self,
+        args: Option<Value>,
+        progress: Option<ProgressNotifier>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        let request = Self::parse_workflow_request(&args.unwrap_or(Value::Null))?;
+        let workflow_id = request.workflow_id.clone();
+        match self.coze_client.stream_workflow(&request).await {
+            Ok(stream) => {
+                Self::collect_workflow_stream(&workflow_id, stream, progress, &cancel).await
+            }
+            Err(e) => Ok(Self::api_error_result("Run Workflow Stream", &e)),
+        }
+    }
+
+    /// 以用户输入恢复中断的工作流
+    pub async fn resume_workflow(&self, args: Option<Value>) -> Result<CallToolResult, McpError> {
+        self.resume_workflow_with_context(args, None, CancellationToken::new())
+            .await
+    }
+
+    pub async fn resume_workflow_with_context(
+        &self,
+        args: Option<Value>,
+        progress: Option<ProgressNotifier>,
+        cancel: CancellationToken,
+    ) -> Result<CallToolResult, McpError> {
+        use crate::api::workflow_models::ResumeWorkflowRequest;
+
+        let args = args.ok_or_else(|| McpError::invalid_params("Missing arguments", None))?;
+        let str_arg = |key: &str| {
+            args.get(key)
+                .and_then(|v| v.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .ok_or_else(|| McpError::invalid_params(format!("Missing {key} parameter"), None))
+        };
+        let workflow_id = str_arg("workflow_id")?;
+        let event_id = str_arg("event_id")?;
+        let resume_data = str_arg("resume_data")?;
+        let interrupt_type = args
+            .get("interrupt_type")
+            .and_then(|v| v.as_i64())
+            .ok_or_else(|| McpError::invalid_params("Missing interrupt_type parameter", None))?;
+
+        let request =
+            ResumeWorkflowRequest::new(workflow_id.clone(), event_id, resume_data, interrupt_type);
+        match self.coze_client.resume_workflow(&request).await {
+            Ok(stream) => {
+                Self::collect_workflow_stream(&workflow_id, stream, progress, &cancel).await
+            }
+            Err(e) => Ok(Self::api_error_result("Resume Workflow", &e)),
+        }
+    }
+
+    fn parse_workflow_request(
+        args: &Value,
+    ) -> Result<crate::api::workflow_models::RunWorkflowRequest, McpError> {
+        use crate::api::workflow_models::RunWorkflowRequest;
+
+        let workflow_id = args
+            .get("workflow_id")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| McpError::invalid_params("Missing workflow_id parameter", None))?;
+        let mut request = RunWorkflowRequest::new(workflow_id.to_string());
+        match args.get("parameters") {
+            None | Some(Value::Null) => {}
+            Some(Value::Object(map)) => {
+                request = request.with_parameters(map.clone().into_iter().collect());
+            }
+            Some(_) => {
+                return Err(McpError::invalid_params(
+                    "parameters must be an object",
+                    None,
+                ))
+            }
+        }
+        if let Some(bot_id) = args.get("bot_id").and_then(|v| v.as_str()) {
+            request = request.with_bot_id(bot_id.to_string());
+        }
+        if let Some(app_id) = args.get("app_id").and_then(|v| v.as_str()) {
+            request = request.with_app_id(app_id.to_string());
+        }
+        if let Some(ext) = args.get("ext") {
+            let ext = serde_json::from_value(ext.clone()).map_err(|e| {
+                McpError::invalid_params(format!("ext must be an object of strings: {e}"), None)
+            })?;
+            request = request.with_ext(ext);
+        }
+        Ok(request)
+    }
+
+    /// 读取工作流事件直到结束、中断、出错或被取消（run_workflow_stream 与 resume_workflow 共用）
+    async fn collect_workflow_stream<S>(
+        workflow_id: &str,
+        stream: S,
+        mut progress: Option<ProgressNotifier>,
+        cancel: &CancellationToken,
+    ) -> Result<CallToolResult, McpError>
+    where
+        S: futures::Stream<
+            Item = Result<
+                crate::api::workflow_models::WorkflowStreamEvent,
+                crate::api::error::ApiError,
+            >,
+        >,
+    {
+        use crate::api::workflow_models::WorkflowEventType;
+        use futures::StreamExt;
+
+        let mut content = String::new();
+        let mut messages = Vec::new();
+        let mut usage = None;
+        let mut debug_url = None;
+
+        tokio::pin!(stream);
+        loop {
+            let result = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => return Ok(Self::workflow_cancelled(workflow_id)),
+                next = stream.next() => match next {
+                    Some(result) => result,
+                    None => break,
+                },
+            };
+            let event = match result {
+                Ok(event) => event,
+                Err(e) => {
+                    let mut result = Self::api_error_result("Workflow Stream", &e);
+                    if let Some(obj) = result
+                        .structured_content
+                        .as_mut()
+                        .and_then(|v| v.as_object_mut())
+                    {
+                        obj.insert("workflow_id".to_string(), json!(workflow_id));
+                        obj.insert("messages".to_string(), json!(messages));
+                    }
+                    return Ok(result);
+                }
+            };
+            if event.usage.is_some() {
+                usage = event.usage.clone();
+            }
+            match event.event {
+                WorkflowEventType::Message => {
+                    if let Some(text) = &event.content {
+                        content.push_str(text);
+                        if let Some(notifier) = progress.as_mut() {
+                            notifier.notify(text.clone()).await;
+                        }
+                    }
+                    messages.push(json!({
+                        "node_title": event.node_title,
+                        "content": event.content,
+                        "node_is_finish": event.node_is_finish
+                    }));
+                }
+                WorkflowEventType::Interrupt => {
+                    let interrupt = event.interrupt_data.as_ref();
+                    return Ok(CallToolResult {
+                        content: Some(vec![rmcp::model::Content::text(format!(
+                            "工作流在节点「{}」等待输入，请使用 resume_workflow 继续\nevent_id: {}\ninterrupt_type: {}\n已输出:\n{content}",
+                            event.node_title.as_deref().unwrap_or(""),
+                            interrupt.map(|i| i.event_id.as_str()).unwrap_or(""),
+                            interrupt.map(|i| i.interrupt_type).unwrap_or_default()
+                        ))]),
+                        is_error: Some(false),
+                        structured_content: Some(json!({
+                            "status": "interrupted",
+                            "workflow_id": workflow_id,
+                            "event_id": interrupt.map(|i| &i.event_id),
+                            "interrupt_type": interrupt.map(|i| i.interrupt_type),
+                            "node_title": event.node_title,
+                            "content": content,
+                            "messages": messages
+                        })),
+                    });
+                }
+                WorkflowEventType::Done => {
+                    debug_url = event.debug_url.clone();
+                    break;
+                }
+                _ => continue,
+            }
+        }
+
+        Ok(CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "工作流执行完成\n输出:\n{content}"
+            ))]),
+            is_error: Some(false),
+            structured_content: Some(json!({
+                "status": "completed",
+                "workflow_id": workflow_id,
+                "content": content,
+                "messages": messages,
+                "usage": usage,
+                "debug_url": debug_url
+            })),
+        })
+    }
+
+    fn workflow_cancelled(workflow_id: &str) -> CallToolResult {
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "工作流执行已取消: workflow_id={workflow_id}"
+            ))]),
+            is_error: Some(true),
+            structured_content: Some(json!({
+                "error": "cancelled",
+                "workflow_id": workflow_id
+            })),
+        }
+    }
+
+    /// MCP 客户端取消请求后通知 Coze 停止对话，避免 Bot 继续运行消耗 token
+    async fn cancel_chat_run(
+        &self,
+        conversation_id: &str,
+        chat_id: Option<&str>,
+    ) -> CallToolResult {
+        let status = match chat_id {
+            Some(chat_id) if !conversation_id.is_empty() => {
+                match self.coze_client.cancel_chat(conversation_id, chat_id).await {
+                    Ok(detail) => detail.status,
+                    Err(e) => {
+                        tracing::warn!("failed to cancel chat {}: {}", chat_id, e);
+                        None
+                    }
+                }
+            }
+            // 尚未收到对话 ID，无从取消
+            _ => None,
+        };
+        CallToolResult {
+            content: Some(vec![rmcp::model::Content::text(format!(
+                "对话已取消: conversation_id={conversation_id}, chat_id={}",
+                chat_id.unwrap_or("")
+            ))]),
+            is_error: Some(true),
+            structured_content: Some(json!({
+                "error": "cancelled",
+                "conversation_id": conversation_id,
+                "chat_id": chat_id,
+                "status": status
+            })),
         }
     }
 }
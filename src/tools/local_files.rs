@@ -0,0 +1,88 @@
+// 工具按路径访问本地文件的范围：远程传输（HTTP / SSE）的客户端不应能读写服务器上的任意文件
+use crate::utils::config::{ServerConfig, TransportType};
+use rmcp::ErrorData as McpError;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default)]
+pub enum LocalFiles {
+    /// 不限制（stdio：调用方即本机用户）
+    #[default]
+    Unrestricted,
+    /// 禁止按路径访问本地文件
+    Disabled,
+    /// 只允许访问该目录（已规范化）内的文件；相对路径相对该目录解析
+    Within(PathBuf),
+}
+
+impl LocalFiles {
+    /// 按服务配置确定范围：设置了 `file_root` 时限定在该目录；否则 stdio 不限制，HTTP / SSE 禁止
+    pub fn for_server(server: &ServerConfig) -> Result<Self, String> {
+        match (&server.file_root, &server.transport) {
+            (Some(root), _) => std::fs::canonicalize(root)
+                .map(LocalFiles::Within)
+                .map_err(|e| format!("invalid server.file_root {root}: {e}")),
+            (None, TransportType::Stdio) => Ok(LocalFiles::Unrestricted),
+            (None, _) => Ok(LocalFiles::Disabled),
+        }
+    }
+
+    /// 校验待读取的路径，返回实际读取的位置（符号链接已解析）
+    pub fn readable(&self, path: &str) -> Result<PathBuf, McpError> {
+        match self {
+            LocalFiles::Unrestricted => Ok(PathBuf::from(path)),
+            LocalFiles::Disabled => Err(disabled()),
+            LocalFiles::Within(root) => {
+                let resolved = std::fs::canonicalize(root.join(path)).map_err(|e| {
+                    McpError::invalid_params(format!("Cannot access {path}: {e}"), None)
+                })?;
+                Self::inside(root, resolved, path)
+            }
+        }
+    }
+
+    /// 校验待写入的路径：所在目录必须存在且位于允许范围内，已存在的目标同样解析符号链接后检查
+    pub fn writable(&self, path: &str) -> Result<PathBuf, McpError> {
+        match self {
+            LocalFiles::Unrestricted => Ok(PathBuf::from(path)),
+            LocalFiles::Disabled => Err(disabled()),
+            LocalFiles::Within(root) => {
+                let target = root.join(path);
+                if let Ok(existing) = std::fs::canonicalize(&target) {
+                    return Self::inside(root, existing, path);
+                }
+                let name = target.file_name().ok_or_else(|| {
+                    McpError::invalid_params(format!("Invalid file path: {path}"), None)
+                })?;
+                let parent = target.parent().unwrap_or(root);
+                let dir = std::fs::canonicalize(parent).map_err(|e| {
+                    McpError::invalid_params(
+                        format!("Cannot access {}: {e}", parent.display()),
+                        None,
+                    )
+                })?;
+                Self::inside(root, dir.join(name), path)
+            }
+        }
+    }
+
+    fn inside(root: &Path, resolved: PathBuf, original: &str) -> Result<PathBuf, McpError> {
+        if resolved.starts_with(root) {
+            Ok(resolved)
+        } else {
+            Err(McpError::invalid_params(
+                format!(
+                    "{original} is outside the allowed directory {}",
+                    root.display()
+                ),
+                None,
+            ))
+        }
+    }
+}
+
+fn disabled() -> McpError {
+    McpError::invalid_params(
+        "Local file paths are disabled for remote transports; set server.file_root (--file-root) to allow a directory",
+        None,
+    )
+}
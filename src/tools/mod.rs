@@ -1,3 +1,6 @@
+pub mod bot_config;
 pub mod config_tool;
 pub mod context;
 pub mod coze_tools;
+pub mod local_files;
+pub mod progress;
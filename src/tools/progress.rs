@@ -0,0 +1,37 @@
+// 长耗时工具的 MCP 进度通知：客户端在请求 _meta 中携带 progressToken 时才发送
+use rmcp::model::{ProgressNotificationParam, ProgressToken};
+use rmcp::service::{Peer, RequestContext, RoleServer};
+
+/// 向发起请求的客户端推送 `notifications/progress`
+#[derive(Debug, Clone)]
+pub struct ProgressNotifier {
+    peer: Peer<RoleServer>,
+    token: ProgressToken,
+    progress: f64,
+}
+
+impl ProgressNotifier {
+    /// 请求未携带 progressToken 时返回 None（客户端不需要进度）
+    pub fn from_context(context: &RequestContext<RoleServer>) -> Option<Self> {
+        let token = context.meta.get_progress_token()?;
+        Some(Self {
+            peer: context.peer.clone(),
+            token,
+            progress: 0.0,
+        })
+    }
+
+    /// 发送一条进度，progress 每次递增 1；发送失败（客户端已断开等）只记录日志，不影响工具结果
+    pub async fn notify(&mut self, message: impl Into<String>) {
+        self.progress += 1.0;
+        let param = ProgressNotificationParam {
+            progress_token: self.token.clone(),
+            progress: self.progress,
+            total: None,
+            message: Some(message.into()),
+        };
+        if let Err(e) = self.peer.notify_progress(param).await {
+            tracing::debug!("failed to send progress notification: {}", e);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! MCP 传输层：stdio 之外的网络部署方式。
+//!
+//! Streamable HTTP 与旧版 HTTP+SSE 模式下，每个 MCP 会话都会通过 `service_factory`
+//! 获得独立的 handler 实例，因此同一进程可以同时服务多个客户端。
+//! 两种模式都会应用 `ServerConfig` 中的 `cors_origins` 与 `max_connections` 限制。
+
+use crate::utils::config::ServerConfig;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, Method, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use futures::StreamExt;
+use rmcp::transport::sse_server::{SseServer, SseServerConfig};
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::{StreamableHttpServerConfig, StreamableHttpService};
+use rmcp::{RoleServer, Service};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing::{info, warn};
+
+/// Streamable HTTP 端点路径
+pub const MCP_HTTP_PATH: &str = "/mcp";
+/// 旧版 SSE 传输：建立事件流的路径
+pub const SSE_PATH: &str = "/sse";
+/// 旧版 SSE 传输：客户端投递消息的路径（需携带 `?sessionId=`）
+pub const SSE_POST_PATH: &str = "/message";
+
+/// 构建 Streamable HTTP 路由（可嵌入其他 axum 应用，也便于测试）
+pub fn streamable_http_router<S, F>(service_factory: F) -> axum::Router
+where
+    S: Service<RoleServer> + Send + 'static,
+    F: Fn() -> S + Send + Sync + 'static,
+{
+    let service = StreamableHttpService::new(
+        move || Ok(service_factory()),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    axum::Router::new().nest_service(MCP_HTTP_PATH, service)
+}
+
+/// 构建旧版 HTTP+SSE 路由。
+///
+/// 每个 `GET /sse` 连接对应一个会话，服务端通过 `endpoint` 事件下发带 `sessionId` 的投递地址，
+/// 之后的 `POST /message?sessionId=...` 会被路由到对应会话。返回的取消令牌用于关闭所有会话。
+pub fn sse_router<S, F>(
+    config: &ServerConfig,
+    service_factory: F,
+) -> Result<(axum::Router, CancellationToken), Box<dyn std::error::Error>>
+where
+    S: Service<RoleServer> + Send + 'static,
+    F: Fn() -> S + Send + 'static,
+{
+    let (sse_server, router) = SseServer::new(SseServerConfig {
+        bind: config.listen_addr.parse()?,
+        sse_path: SSE_PATH.to_string(),
+        post_path: SSE_POST_PATH.to_string(),
+        ct: CancellationToken::new(),
+        sse_keep_alive: None,
+    });
+    let ct = sse_server.with_service(service_factory);
+    Ok((apply_server_limits(router, config), ct))
+}
+
+/// 在 `config.listen_addr` 上运行 Streamable HTTP 服务，收到 Ctrl+C 后优雅退出
+pub async fn serve_streamable_http<S, F>(
+    config: &ServerConfig,
+    service_factory: F,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: Service<RoleServer> + Send + 'static,
+    F: Fn() -> S + Send + Sync + 'static,
+{
+    let router = apply_server_limits(streamable_http_router(service_factory), config);
+    let listener = tokio::net::TcpListener::bind(&config.listen_addr).await?;
+    info!(
+        "Streamable HTTP transport listening on http://{}{}",
+        listener.local_addr()?,
+        MCP_HTTP_PATH
+    );
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+/// 在 `config.listen_addr` 上运行旧版 HTTP+SSE 服务，收到 Ctrl+C 后关闭所有会话
+pub async fn serve_sse<S, F>(
+    config: &ServerConfig,
+    service_factory: F,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: Service<RoleServer> + Send + 'static,
+    F: Fn() -> S + Send + 'static,
+{
+    let (router, ct) = sse_router(config, service_factory)?;
+    let listener = tokio::net::TcpListener::bind(&config.listen_addr).await?;
+    info!(
+        "SSE transport listening on http://{}{} (post: {})",
+        listener.local_addr()?,
+        SSE_PATH,
+        SSE_POST_PATH
+    );
+    axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    ct.cancel();
+    Ok(())
+}
+
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+    info!("Shutting down network transport");
+}
+
+/// 为路由附加 CORS 与连接数限制
+pub fn apply_server_limits(router: axum::Router, config: &ServerConfig) -> axum::Router {
+    let allow_any = config.cors_origins.iter().any(|o| o == "*");
+    let origins: Arc<Vec<HeaderValue>> = Arc::new(
+        config
+            .cors_origins
+            .iter()
+            .filter(|o| o.as_str() != "*")
+            .filter_map(|o| HeaderValue::from_str(o).ok())
+            .collect(),
+    );
+    let cors = CorsLayer::new()
+        .allow_methods([Method::GET, Method::POST, Method::DELETE])
+        .allow_headers(Any)
+        .expose_headers(Any)
+        .allow_origin(if allow_any {
+            AllowOrigin::any()
+        } else {
+            AllowOrigin::list(origins.iter().cloned())
+        });
+    let connection_slots = Arc::new(Semaphore::new(config.max_connections as usize));
+
+    router
+        .layer(middleware::from_fn_with_state(
+            connection_slots,
+            limit_stream_connections,
+        ))
+        .layer(middleware::from_fn_with_state(
+            (allow_any, origins),
+            reject_disallowed_origin,
+        ))
+        .layer(cors)
+}
+
+/// 拒绝来自 `cors_origins` 之外来源的浏览器请求（无 Origin 头的本地客户端不受影响）
+async fn reject_disallowed_origin(
+    State((allow_any, origins)): State<(bool, Arc<Vec<HeaderValue>>)>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !allow_any {
+        if let Some(origin) = request.headers().get(header::ORIGIN) {
+            if !origins.iter().any(|o| o == origin) {
+                warn!("Rejected request from disallowed origin: {:?}", origin);
+                return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+            }
+        }
+    }
+    next.run(request).await
+}
+
+/// 限制同时打开的事件流数量；连接关闭（响应体被释放）时归还名额。
+///
+/// GET 总是建立事件流，处理前即占用名额；其他请求（如 Streamable HTTP 的 POST）
+/// 只有响应为 `text/event-stream` 时才占用，名额已满时丢弃该响应并返回 503
+async fn limit_stream_connections(
+    State(slots): State<Arc<Semaphore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let permit = if request.method() == Method::GET {
+        let Ok(permit) = slots.clone().try_acquire_owned() else {
+            return too_many_connections();
+        };
+        Some(permit)
+    } else {
+        None
+    };
+    let response = next.run(request).await;
+    let permit = match permit {
+        Some(permit) => permit,
+        None if is_event_stream(&response) => match slots.try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => return too_many_connections(),
+        },
+        None => return response,
+    };
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _held = &permit;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"))
+}
+
+fn too_many_connections() -> Response {
+    warn!("Rejected connection: max_connections reached");
+    (StatusCode::SERVICE_UNAVAILABLE, "Too many connections").into_response()
+}
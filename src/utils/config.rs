@@ -1,37 +1,122 @@
+use crate::api::endpoints::COZE_BASE_URL;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct Config {
+    /// 启动时选中的 profile（可被 `--profile` / `COZE_PROFILE` 覆盖）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
     pub coze: CozeConfig,
     pub server: ServerConfig,
     pub logging: LoggingConfig,
+    /// 命名 profile：每个 Coze 租户 / 区域一份，未设置的字段继承 `[coze]`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, ProfileConfig>,
+    /// 环境变量与命令行对 `[coze]` 的覆盖，叠加启动时选中的 profile 后重新应用，保证 CLI > 环境变量 > 配置文件
+    #[serde(skip)]
+    overrides: ProfileConfig,
+    /// 叠加覆盖前配置文件中的 `[coze]`，其他 profile 以此为底，不受进程级覆盖影响
+    #[serde(skip)]
+    file_coze: Option<CozeConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CozeConfig {
     pub api_token: String,
     pub base_url: String,
+    pub default_space_id: String,
+    /// 单次 HTTP 请求超时（秒）
     pub timeout: u64,
     pub retry_attempts: u32,
+    /// 按端点分组（见 `api::endpoints::ENDPOINT_GROUPS`）的客户端限流；`default` 作用于未单独配置的分组
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub rate_limits: BTreeMap<String, RateLimitConfig>,
+}
+
+/// 单个端点分组的限流：令牌桶控制速率，信号量控制并发；字段缺省表示不限制
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// 每秒补充的令牌数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_per_second: Option<f64>,
+    /// 令牌桶容量（允许的突发请求数），缺省为 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+    /// 同时在途的请求上限
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
 }
 
 impl Default for CozeConfig {
     fn default() -> Self {
         Self {
             api_token: String::new(),
-            base_url: "https://api.coze.com".to_string(),
+            base_url: COZE_BASE_URL.to_string(),
+            default_space_id: "default".to_string(),
             timeout: 30,
             retry_attempts: 3,
+            rate_limits: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[profiles.<name>]` 段：只覆盖显式设置的字段
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ProfileConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_space_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_attempts: Option<u32>,
+}
+
+impl ProfileConfig {
+    /// 以 `base` 为底，叠加本 profile 显式设置的字段
+    pub fn apply_to(&self, base: &CozeConfig) -> CozeConfig {
+        CozeConfig {
+            api_token: self
+                .api_token
+                .clone()
+                .unwrap_or_else(|| base.api_token.clone()),
+            base_url: self
+                .base_url
+                .clone()
+                .unwrap_or_else(|| base.base_url.clone()),
+            default_space_id: self
+                .default_space_id
+                .clone()
+                .unwrap_or_else(|| base.default_space_id.clone()),
+            timeout: self.timeout.unwrap_or(base.timeout),
+            retry_attempts: self.retry_attempts.unwrap_or(base.retry_attempts),
+            rate_limits: base.rate_limits.clone(),
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ServerConfig {
     pub transport: TransportType,
     pub listen_addr: String,
     pub max_connections: u32,
     pub cors_origins: Vec<String>,
+    /// 工具可按路径读写本地文件的目录；未设置时 stdio 不限制，HTTP / SSE 禁止按路径访问本地文件
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_root: Option<String>,
+    /// HTTP / SSE 会话未提供自己的凭证时，是否使用进程级（当前 profile）token；开启后远程调用方将以该 token 的身份访问 Coze
+    pub share_operator_token: bool,
+    /// HTTP / SSE 会话是否可以通过 `profiles` 工具切换 profile（切换后即使用该 profile 的 token）
+    pub allow_profile_switch: bool,
 }
 
 impl Default for ServerConfig {
@@ -41,6 +126,9 @@ impl Default for ServerConfig {
             listen_addr: "127.0.0.1:8080".to_string(),
             max_connections: 100,
             cors_origins: vec!["*".to_string()],
+            file_root: None,
+            share_operator_token: false,
+            allow_profile_switch: false,
         }
     }
 }
@@ -67,6 +155,7 @@ impl std::str::FromStr for TransportType {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct LoggingConfig {
     pub level: String,
     pub format: LogFormat,
@@ -91,48 +180,270 @@ pub enum LogFormat {
     Compact,
 }
 
-impl Config {
-    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        // 尝试从配置文件加载
-        let config_path = std::env::var("COZE_MCP_CONFIG")
-            .unwrap_or_else(|_| "config.toml".to_string());
-
-        if std::path::Path::new(&config_path).exists() {
-            let config_str = std::fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&config_str)?;
-            Ok(config)
-        } else {
-            // 使用环境变量或默认值
-            let mut config = Config::default();
-            
-            if let Ok(token) = std::env::var("COZE_API_TOKEN") {
-                config.coze.api_token = token;
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            "compact" => Ok(LogFormat::Compact),
+            _ => Err(format!("invalid log format: {}", s)),
+        }
+    }
+}
+
+/// 命令行参数（优先级最高）；未出现的选项为 None
+#[derive(Debug, Clone, Default)]
+pub struct CliArgs {
+    pub help: bool,
+    pub config_path: Option<String>,
+    pub profile: Option<String>,
+    pub api_key: Option<String>,
+    pub space_id: Option<String>,
+    pub base_url: Option<String>,
+    pub transport: Option<String>,
+    pub listen_addr: Option<String>,
+    pub file_root: Option<String>,
+    pub timeout: Option<String>,
+    pub retry_attempts: Option<String>,
+    pub log_level: Option<String>,
+    pub log_format: Option<String>,
+    pub log_file: Option<String>,
+    /// 子命令及其位置参数（如 `bot export <BOT_ID>`）；为空时启动 MCP 服务
+    pub command: Vec<String>,
+    /// `bot export` 的输出文件
+    pub output: Option<String>,
+    /// `bot import` 更新的目标 Bot
+    pub bot_id: Option<String>,
+    /// `bot import` 后发布到 API 渠道
+    pub publish: bool,
+    /// `bot import` 时跳过接口无法设置的变量，否则文件含变量即报错
+    pub skip_variables: bool,
+}
+
+/// 可作为子命令的首个位置参数
+const COMMANDS: &[&str] = &["bot"];
+
+impl CliArgs {
+    /// 解析 `--flag value` 与 `--flag=value` 两种写法；首个位置参数是已知子命令（`bot`）时收集子命令及其后的位置参数，
+    /// 其他位置参数与未知选项一样忽略
+    pub fn parse<I>(args: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut cli = CliArgs::default();
+        let mut first_positional = true;
+        let mut iter = args.into_iter();
+        while let Some(arg) = iter.next() {
+            if arg == "-h" || arg == "--help" {
+                cli.help = true;
+                continue;
             }
-            
-            if let Ok(base_url) = std::env::var("COZE_API_BASE_URL") {
-                config.coze.base_url = base_url;
+            if arg == "--publish" {
+                cli.publish = true;
+                continue;
             }
-            
-            if let Ok(listen_addr) = std::env::var("LISTEN_ADDR") {
-                config.server.listen_addr = listen_addr;
+            if arg == "--skip-variables" {
+                cli.skip_variables = true;
+                continue;
             }
-            
-            if let Ok(transport) = std::env::var("TRANSPORT") {
-                config.server.transport = transport.parse()?;
+            if !arg.starts_with('-') {
+                if !cli.command.is_empty() || (first_positional && COMMANDS.contains(&arg.as_str()))
+                {
+                    cli.command.push(arg);
+                }
+                first_positional = false;
+                continue;
             }
-            
-            if let Ok(log_level) = std::env::var("LOG_LEVEL") {
-                config.logging.level = log_level;
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let slot = match flag.as_str() {
+                "--config" => &mut cli.config_path,
+                "--profile" => &mut cli.profile,
+                "--api-key" => &mut cli.api_key,
+                "--space-id" => &mut cli.space_id,
+                "--base-url" => &mut cli.base_url,
+                "--transport" => &mut cli.transport,
+                "--listen-addr" => &mut cli.listen_addr,
+                "--file-root" => &mut cli.file_root,
+                "--timeout" => &mut cli.timeout,
+                "--retry-attempts" => &mut cli.retry_attempts,
+                "--log-level" => &mut cli.log_level,
+                "--log-format" => &mut cli.log_format,
+                "--log-file" => &mut cli.log_file,
+                "--output" => &mut cli.output,
+                "--bot-id" => &mut cli.bot_id,
+                _ => continue,
+            };
+            if let Some(v) = inline_value.or_else(|| iter.next()) {
+                *slot = Some(v);
             }
+        }
+        cli
+    }
+}
+
+impl Config {
+    /// 使用默认来源加载：环境变量 > 配置文件 > 默认值
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::load_with_cli(&CliArgs::default())
+    }
 
-            Ok(config)
+    /// 按 CLI > 环境变量 > 配置文件 > 默认值 的优先级加载配置
+    pub fn load_with_cli(cli: &CliArgs) -> Result<Self, Box<dyn std::error::Error>> {
+        let explicit_path = cli
+            .config_path
+            .clone()
+            .or_else(|| std::env::var("COZE_MCP_CONFIG").ok());
+        let mut config = match explicit_path {
+            // 显式指定的配置文件必须存在
+            Some(path) => Self::from_file(&path)?,
+            None if std::path::Path::new("config.toml").exists() => Self::from_file("config.toml")?,
+            None => Config::default(),
+        };
+        config.apply_env(|key| std::env::var(key).ok())?;
+        config.apply_cli(cli)?;
+        // 提前校验 profile 名称，避免启动后才发现拼写错误
+        config.active_coze()?;
+        Ok(config)
+    }
+
+    /// 指定 profile 的完整配置：`[coze]` 之上叠加该 profile；
+    /// 环境变量与命令行只叠加到启动时选中的 profile，切换到其他 profile 时使用其自身的 token 与地址
+    pub fn profile_config(&self, name: &str) -> Option<CozeConfig> {
+        self.profiles.get(name).map(|p| self.resolve(name, p))
+    }
+
+    /// 所有 profile 的完整配置，按名称排序
+    pub fn resolved_profiles(&self) -> BTreeMap<String, CozeConfig> {
+        self.profiles
+            .iter()
+            .map(|(name, p)| (name.clone(), self.resolve(name, p)))
+            .collect()
+    }
+
+    fn resolve(&self, name: &str, profile: &ProfileConfig) -> CozeConfig {
+        if self.profile.as_deref() == Some(name) {
+            self.overrides.apply_to(&profile.apply_to(&self.coze))
+        } else {
+            profile.apply_to(self.file_coze.as_ref().unwrap_or(&self.coze))
         }
     }
 
+    /// 当前生效的 Coze 配置：选中 profile 时叠加该 profile，否则即 `[coze]`
+    pub fn active_coze(&self) -> Result<CozeConfig, Box<dyn std::error::Error>> {
+        match &self.profile {
+            None => Ok(self.coze.clone()),
+            Some(name) => self.profile_config(name).ok_or_else(|| {
+                let known: Vec<&str> = self.profiles.keys().map(|k| k.as_str()).collect();
+                format!("unknown profile: {name} (available: {})", known.join(", ")).into()
+            }),
+        }
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_str = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {path}: {e}"))?;
+        let config: Config = toml::from_str(&config_str)?;
+        Ok(config)
+    }
+
+    /// 叠加环境变量；`lookup` 便于测试时替换进程环境
+    pub fn apply_env<F>(&mut self, lookup: F) -> Result<(), Box<dyn std::error::Error>>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(profile) = lookup("COZE_PROFILE") {
+            self.profile = Some(profile);
+        }
+        if let Some(token) = lookup("COZE_API_TOKEN").or_else(|| lookup("COZE_API_KEY")) {
+            self.overrides.api_token = Some(token);
+        }
+        if let Some(base_url) = lookup("COZE_API_BASE_URL") {
+            self.overrides.base_url = Some(base_url);
+        }
+        if let Some(space_id) = lookup("COZE_DEFAULT_SPACE_ID").or_else(|| lookup("COZE_SPACE_ID"))
+        {
+            self.overrides.default_space_id = Some(space_id);
+        }
+        if let Some(timeout) = lookup("COZE_TIMEOUT") {
+            self.overrides.timeout = Some(timeout.parse()?);
+        }
+        if let Some(retries) = lookup("COZE_RETRY_ATTEMPTS") {
+            self.overrides.retry_attempts = Some(retries.parse()?);
+        }
+        self.file_coze.get_or_insert_with(|| self.coze.clone());
+        self.coze = self.overrides.apply_to(&self.coze);
+        if let Some(listen_addr) = lookup("LISTEN_ADDR") {
+            self.server.listen_addr = listen_addr;
+        }
+        if let Some(file_root) = lookup("COZE_MCP_FILE_ROOT") {
+            self.server.file_root = Some(file_root);
+        }
+        if let Some(transport) = lookup("TRANSPORT") {
+            self.server.transport = transport.parse()?;
+        }
+        if let Some(log_level) = lookup("LOG_LEVEL") {
+            self.logging.level = log_level;
+        }
+        if let Some(log_format) = lookup("LOG_FORMAT") {
+            self.logging.format = log_format.parse()?;
+        }
+        if let Some(log_file) = lookup("LOG_FILE") {
+            self.logging.file_path = Some(log_file);
+        }
+        Ok(())
+    }
+
+    /// 叠加命令行参数
+    pub fn apply_cli(&mut self, cli: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(v) = &cli.profile {
+            self.profile = Some(v.clone());
+        }
+        if let Some(v) = &cli.api_key {
+            self.overrides.api_token = Some(v.clone());
+        }
+        if let Some(v) = &cli.base_url {
+            self.overrides.base_url = Some(v.clone());
+        }
+        if let Some(v) = &cli.space_id {
+            self.overrides.default_space_id = Some(v.clone());
+        }
+        if let Some(v) = &cli.timeout {
+            self.overrides.timeout = Some(v.parse()?);
+        }
+        if let Some(v) = &cli.retry_attempts {
+            self.overrides.retry_attempts = Some(v.parse()?);
+        }
+        self.file_coze.get_or_insert_with(|| self.coze.clone());
+        self.coze = self.overrides.apply_to(&self.coze);
+        if let Some(v) = &cli.listen_addr {
+            self.server.listen_addr = v.clone();
+        }
+        if let Some(v) = &cli.file_root {
+            self.server.file_root = Some(v.clone());
+        }
+        if let Some(v) = &cli.transport {
+            self.server.transport = v.parse()?;
+        }
+        if let Some(v) = &cli.log_level {
+            self.logging.level = v.clone();
+        }
+        if let Some(v) = &cli.log_format {
+            self.logging.format = v.parse()?;
+        }
+        if let Some(v) = &cli.log_file {
+            self.logging.file_path = Some(v.clone());
+        }
+        Ok(())
+    }
+
     pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let config_str = toml::to_string_pretty(self)?;
         std::fs::write(path, config_str)?;
         Ok(())
     }
 }
-
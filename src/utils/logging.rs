@@ -1,8 +1,14 @@
+use crate::utils::config::{LogFormat, LoggingConfig};
+use std::sync::Arc;
 use tracing::Level;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-pub fn init_logging(level: &str, format: crate::utils::config::LogFormat) {
-    let log_level = match level.to_lowercase().as_str() {
+/// 按配置初始化日志。
+///
+/// 日志写入 `file_path`（追加）或 stderr；stdout 保留给 stdio 传输的 JSON-RPC 消息。
+pub fn init_logging(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let log_level = match config.level.to_lowercase().as_str() {
         "trace" => Level::TRACE,
         "debug" => Level::DEBUG,
         "info" => Level::INFO,
@@ -10,29 +16,45 @@ pub fn init_logging(level: &str, format: crate::utils::config::LogFormat) {
         "error" => Level::ERROR,
         _ => Level::INFO,
     };
+    let filter = tracing_subscriber::EnvFilter::from_default_env().add_directive(log_level.into());
 
-    match format {
-        crate::utils::config::LogFormat::Json => {
-            tracing_subscriber::registry()
-                .with(tracing_subscriber::fmt::layer())
-                .with(tracing_subscriber::EnvFilter::from_default_env()
-                    .add_directive(log_level.into()))
-                .init();
-        }
-        crate::utils::config::LogFormat::Pretty => {
-            tracing_subscriber::registry()
-                .with(tracing_subscriber::fmt::layer().pretty())
-                .with(tracing_subscriber::EnvFilter::from_default_env()
-                    .add_directive(log_level.into()))
-                .init();
-        }
-        crate::utils::config::LogFormat::Compact => {
-            tracing_subscriber::registry()
-                .with(tracing_subscriber::fmt::layer().compact())
-                .with(tracing_subscriber::EnvFilter::from_default_env()
-                    .add_directive(log_level.into()))
-                .init();
+    let (writer, ansi) = match &config.file_path {
+        Some(path) => {
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?;
+            (BoxMakeWriter::new(Arc::new(file)), false)
         }
+        None => (BoxMakeWriter::new(std::io::stderr), true),
+    };
+
+    let registry = tracing_subscriber::registry().with(filter);
+    match config.format {
+        LogFormat::Json => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_writer(writer)
+                    .with_ansi(false),
+            )
+            .try_init()?,
+        LogFormat::Pretty => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .pretty()
+                    .with_writer(writer)
+                    .with_ansi(ansi),
+            )
+            .try_init()?,
+        LogFormat::Compact => registry
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .compact()
+                    .with_writer(writer)
+                    .with_ansi(ansi),
+            )
+            .try_init()?,
     }
+    Ok(())
 }
-
@@ -1,2 +1,2 @@
 pub mod config;
-pub mod logging;
\ No newline at end of file
+pub mod logging;
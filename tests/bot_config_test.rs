@@ -0,0 +1,419 @@
+// Bot 配置导出 / 导入测试 - 确定性 JSON、按文件新建 / 更新 / 发布，经本地模拟端点验证
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::http::Uri;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::bot_models::{BotConfigFile, BotDetail};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::bot_config::{import_bot, ImportTarget};
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use coze_mcp_server::tools::local_files::LocalFiles;
+use coze_mcp_server::utils::config::CliArgs;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn detail() -> Value {
+    json!({
+        "bot_id": "bot_1",
+        "name": "客服助手",
+        "description": "售后问答",
+        "create_time": 1718289297,
+        "update_time": 1718289400,
+        "version": "1718289400000",
+        "prompt_info": {"prompt": "你是一名客服"},
+        "onboarding_info": {"prologue": "你好", "suggested_questions": ["怎么退货？"]},
+        "model_info": {
+            "model_id": "1706077826",
+            "model_name": "豆包",
+            "temperature": 0.7,
+            "top_p": 1.0,
+            "frequency_penalty": 0,
+            "presence_penalty": 0,
+            "cache_type": "closed"
+        },
+        "plugin_info_list": [
+            {"plugin_id": "p2", "name": "搜索", "api_info_list": [{"api_id": "a3", "name": "search"}]},
+            {"plugin_id": "p1", "name": "天气", "api_info_list": [
+                {"api_id": "a2", "name": "forecast"},
+                {"api_id": "a1", "name": "now"}
+            ]}
+        ],
+        "workflow_info_list": [{"id": "wf_2", "name": "退款"}, {"id": "wf_1", "name": "查单"}],
+        "knowledge": {"dataset_ids": ["ds_2", "ds_1"], "auto_call": true, "search_strategy": 1},
+        "variables": [
+            {"keyword": "user_level", "default_value": "normal", "enable": true},
+            {"keyword": "city", "description": "所在城市", "enable": true, "channel": "custom"}
+        ]
+    })
+}
+
+async fn reply(uri: Uri, Query(query): Query<HashMap<String, String>>, body: Bytes) -> Json<Value> {
+    let path = uri.path().to_string();
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    Json(match path.as_str() {
+        "/v1/bot/get_online_info" => {
+            assert_eq!(query["bot_id"], "bot_1");
+            json!({"code": 0, "data": detail()})
+        }
+        "/v1/bot/create" => json!({"code": 0, "data": {"bot_id": "bot_new"}}),
+        "/v1/bot/update" => json!({"code": 0}),
+        "/v1/bot/publish" => {
+            json!({"code": 0, "data": {"bot_id": body["bot_id"], "version": "v2"}})
+        }
+        _ => json!({"code": 4000, "msg": "unexpected path"}),
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/bot/get_online_info", get(reply))
+        .route("/v1/bot/create", post(reply))
+        .route("/v1/bot/update", post(reply))
+        .route("/v1/bot/publish", post(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[test]
+fn test_config_file_is_deterministic() {
+    let detail: BotDetail = serde_json::from_value(detail()).unwrap();
+    let file = BotConfigFile::from_detail(&detail);
+    let content = file.to_json().unwrap();
+
+    // 多次序列化结果一致（model 的透传字段存于 HashMap）
+    for _ in 0..10 {
+        assert_eq!(
+            BotConfigFile::from_detail(&detail).to_json().unwrap(),
+            content
+        );
+    }
+    assert!(content.ends_with("}\n"));
+
+    let value: Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(value["format_version"], 1);
+    assert_eq!(value["prompt"], "你是一名客服");
+    assert_eq!(
+        value["plugins"],
+        json!([
+            {"plugin_id": "p1", "api_id": "a1"},
+            {"plugin_id": "p1", "api_id": "a2"},
+            {"plugin_id": "p2", "api_id": "a3"}
+        ])
+    );
+    assert_eq!(value["workflow_ids"], json!(["wf_1", "wf_2"]));
+    assert_eq!(value["knowledge"]["dataset_ids"], json!(["ds_1", "ds_2"]));
+    assert_eq!(value["variables"][0]["keyword"], "city");
+    assert_eq!(value["variables"][0]["channel"], "custom");
+    assert_eq!(value["model"]["cache_type"], "closed");
+    // 随环境变化的字段不导出
+    for key in ["bot_id", "version", "create_time", "update_time"] {
+        assert!(value.get(key).is_none(), "{key} should not be exported");
+    }
+    assert!(value["model"].get("model_name").is_none());
+
+    // 键按字母序输出
+    let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+    let positions: Vec<usize> = keys
+        .iter()
+        .map(|k| content.find(&format!("\n  \"{k}\"")).unwrap())
+        .collect();
+    assert!(positions.windows(2).all(|w| w[0] < w[1]));
+
+    let parsed = BotConfigFile::from_json(&content).unwrap();
+    assert_eq!(parsed.to_json().unwrap(), content);
+
+    assert!(BotConfigFile::from_json(r#"{"format_version": 2, "name": "x"}"#).is_err());
+    assert!(BotConfigFile::from_json(r#"{"format_version": 1, "name": ""}"#).is_err());
+    assert!(BotConfigFile::from_json("not json").is_err());
+}
+
+#[tokio::test]
+async fn test_import_creates_then_sets_knowledge() {
+    let (client, mock) = spawn_mock().await;
+    let detail: BotDetail = serde_json::from_value(detail()).unwrap();
+    let file = BotConfigFile::from_detail(&detail);
+
+    let target = ImportTarget::Create {
+        space_id: "space_prod".to_string(),
+    };
+    // 文件含变量而未同意跳过时，不发出任何请求直接报错
+    let err = import_bot(&client, &file, target.clone(), None, false)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("skip_variables"));
+    assert!(mock.requests().is_empty());
+
+    let outcome = import_bot(&client, &file, target, None, true)
+        .await
+        .unwrap();
+    assert_eq!(outcome.bot_id, "bot_new");
+    assert!(outcome.created);
+    assert_eq!(outcome.published_version, None);
+    assert_eq!(outcome.skipped, vec!["variables"]);
+
+    let requests = mock.path_bodies();
+    assert_eq!(requests.len(), 2);
+    let (path, body) = &requests[0];
+    assert_eq!(path, "/v1/bot/create");
+    assert_eq!(body["space_id"], "space_prod");
+    assert_eq!(body["name"], "客服助手");
+    assert_eq!(body["prompt_info"]["prompt"], "你是一名客服");
+    assert_eq!(body["model_info_config"]["model_id"], "1706077826");
+    assert!(body["model_info_config"].get("model_name").is_none());
+    assert_eq!(body["plugin_id_list"]["id_list"][0]["api_id"], "a1");
+    assert_eq!(
+        body["workflow_id_list"]["ids"],
+        json!([{"id": "wf_1"}, {"id": "wf_2"}])
+    );
+    assert!(body.get("knowledge").is_none());
+    assert!(body.get("variables").is_none());
+    // 创建接口不支持知识库，随后单独更新
+    assert_eq!(
+        requests[1].1,
+        json!({
+            "bot_id": "bot_new",
+            "knowledge": {"dataset_ids": ["ds_1", "ds_2"], "auto_call": true, "search_strategy": 1}
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_export_and_import_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+    let path = std::env::temp_dir().join(format!("coze_bot_{}.json", uuid::Uuid::new_v4()));
+    let path_str = path.to_str().unwrap();
+
+    let result = tools
+        .export_bot(Some(json!({"bot_id": "bot_1", "output_path": path_str})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Value>(&written).unwrap(),
+        result.structured_content.unwrap()["config"]
+    );
+
+    // 不传 output_path 时直接返回文件内容
+    let result = tools
+        .export_bot(Some(json!({"bot_id": "bot_1"})))
+        .await
+        .unwrap();
+    let text = serde_json::to_value(&result.content.unwrap()[0]).unwrap()["text"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(text, written);
+
+    let result = tools
+        .import_bot(Some(
+            json!({"path": path_str, "bot_id": "bot_staging", "publish": true, "skip_variables": true}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["bot_id"], "bot_staging");
+    assert_eq!(structured["created"], false);
+    assert_eq!(structured["published_version"], "v2");
+    assert_eq!(structured["skipped"], json!(["variables"]));
+
+    // 未传 skip_variables 时以错误结果返回，不修改目标 Bot
+    let result = tools
+        .import_bot(Some(json!({"path": path_str, "bot_id": "bot_staging"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let result = tools
+        .import_bot(Some(json!({
+            "config": {"format_version": 1, "name": "空白助手"},
+            "space_id": "space_2"
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["created"], true);
+    std::fs::remove_file(&path).ok();
+
+    let requests = mock.path_bodies();
+    let paths: Vec<&str> = requests.iter().map(|(p, _)| p.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "/v1/bot/get_online_info",
+            "/v1/bot/get_online_info",
+            "/v1/bot/update",
+            "/v1/bot/publish",
+            "/v1/bot/create",
+        ]
+    );
+    let update = &requests[2].1;
+    assert_eq!(update["bot_id"], "bot_staging");
+    assert_eq!(update["name"], "客服助手");
+    assert_eq!(update["knowledge"]["dataset_ids"], json!(["ds_1", "ds_2"]));
+    assert_eq!(
+        requests[3].1,
+        json!({"bot_id": "bot_staging", "connector_ids": ["1024"]})
+    );
+    // 文件中没有插件 / 工作流时提交空列表，清空目标 Bot 上的绑定
+    let create = &requests[4].1;
+    assert_eq!(create["space_id"], "space_2");
+    assert_eq!(create["plugin_id_list"], json!({"id_list": []}));
+    assert_eq!(create["workflow_id_list"], json!({"ids": []}));
+}
+
+#[tokio::test]
+async fn test_import_tool_validates_arguments() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, String::new());
+
+    let config = json!({"format_version": 1, "name": "助手"});
+    for args in [
+        json!({}),
+        json!({"path": "/nonexistent/bot.json", "bot_id": "bot_1"}),
+        json!({"path": "a.json", "config": config, "bot_id": "bot_1"}),
+        json!({"config": {"format_version": 1}, "bot_id": "bot_1"}),
+        // 既没有 bot_id 也没有 space_id；占位的 "default" 不是有效工作空间
+        json!({"config": config}),
+        json!({"config": config, "space_id": "default"}),
+        json!({"config": config, "bot_id": "bot_1", "publish": true, "connector_ids": []}),
+    ] {
+        assert!(tools.import_bot(Some(args)).await.is_err());
+    }
+    assert!(tools.export_bot(Some(json!({}))).await.is_err());
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_export_import_paths_respect_local_files() {
+    let (client, mock) = spawn_mock().await;
+    let root = std::env::temp_dir().join(format!("coze_bot_root_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&root).unwrap();
+    let inside = root.join("bot.json");
+    let outside = std::env::temp_dir().join(format!("coze_bot_{}.json", uuid::Uuid::new_v4()));
+
+    // 远程传输默认禁止按路径读写，导出内容仍可直接返回
+    let tools =
+        CozeTools::new(client.clone(), "space".to_string()).with_local_files(LocalFiles::Disabled);
+    let err = tools
+        .export_bot(Some(
+            json!({"bot_id": "bot_1", "output_path": inside.to_str().unwrap()}),
+        ))
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("--file-root"));
+    assert!(!inside.exists());
+    let result = tools
+        .export_bot(Some(json!({"bot_id": "bot_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert!(tools
+        .import_bot(Some(
+            json!({"path": inside.to_str().unwrap(), "bot_id": "bot_1"}),
+        ))
+        .await
+        .is_err());
+
+    // 限定目录时只能读写目录内的文件
+    let tools = CozeTools::new(client, "space".to_string())
+        .with_local_files(LocalFiles::Within(std::fs::canonicalize(&root).unwrap()));
+    assert!(tools
+        .export_bot(Some(
+            json!({"bot_id": "bot_1", "output_path": outside.to_str().unwrap()}),
+        ))
+        .await
+        .is_err());
+    assert!(!outside.exists());
+    tools
+        .export_bot(Some(
+            json!({"bot_id": "bot_1", "output_path": inside.to_str().unwrap()}),
+        ))
+        .await
+        .unwrap();
+    assert!(inside.exists());
+    std::fs::copy(&inside, &outside).unwrap();
+    assert!(tools
+        .import_bot(Some(
+            json!({"path": outside.to_str().unwrap(), "bot_id": "bot_1"}),
+        ))
+        .await
+        .is_err());
+    std::fs::remove_file(&outside).ok();
+    std::fs::remove_dir_all(&root).ok();
+
+    // 只有两次成功的导出到达服务端
+    assert_eq!(
+        mock.paths(),
+        vec!["/v1/bot/get_online_info", "/v1/bot/get_online_info"]
+    );
+}
+
+#[test]
+fn test_cli_bot_subcommand_parsing() {
+    let cli = CliArgs::parse(
+        [
+            "--profile",
+            "prod",
+            "bot",
+            "import",
+            "bots/support.json",
+            "--bot-id=bot_1",
+            "--publish",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    assert_eq!(cli.command, vec!["bot", "import", "bots/support.json"]);
+    assert_eq!(cli.profile.as_deref(), Some("prod"));
+    assert_eq!(cli.bot_id.as_deref(), Some("bot_1"));
+    assert!(cli.publish);
+
+    let cli = CliArgs::parse(
+        ["bot", "export", "bot_1", "--output", "out.json"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    assert_eq!(cli.command, vec!["bot", "export", "bot_1"]);
+    assert_eq!(cli.output.as_deref(), Some("out.json"));
+    assert!(!cli.publish);
+    assert!(!cli.skip_variables);
+
+    // 只有首个位置参数是已知子命令时才进入命令模式，其他位置参数忽略
+    let cli = CliArgs::parse(
+        ["stray", "--transport", "http", "bot", "export", "bot_1"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    assert!(cli.command.is_empty());
+    assert_eq!(cli.transport.as_deref(), Some("http"));
+
+    let cli = CliArgs::parse(
+        ["bot", "import", "a.json", "--skip-variables"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    assert_eq!(cli.command, vec!["bot", "import", "a.json"]);
+    assert!(cli.skip_variables);
+}
+
+#[test]
+fn test_import_target_resolution() {
+    assert!(matches!(
+        ImportTarget::resolve(Some("bot_1"), "default"),
+        Some(ImportTarget::Update { bot_id }) if bot_id == "bot_1"
+    ));
+    assert!(matches!(
+        ImportTarget::resolve(None, "space_1"),
+        Some(ImportTarget::Create { space_id }) if space_id == "space_1"
+    ));
+    // 未配置工作空间时的默认值 "default" 视为未设置
+    assert!(ImportTarget::resolve(None, "default").is_none());
+    assert!(ImportTarget::resolve(Some(""), "").is_none());
+}
@@ -0,0 +1,251 @@
+// Bot 配置管理测试 - 查看 / 创建 / 更新 / 发布，经本地模拟端点验证请求格式
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::http::Uri;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::bot_models::{
+    BotKnowledge, CreateBotRequest, PublishBotRequest, UpdateBotRequest,
+};
+use coze_mcp_server::api::endpoints::endpoint_group;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+async fn reply(uri: Uri, Query(query): Query<HashMap<String, String>>, body: Bytes) -> Json<Value> {
+    let path = uri.path().to_string();
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    Json(match path.as_str() {
+        "/v1/bot/get_online_info" if query["bot_id"] == "missing" => {
+            json!({"code": 4004, "msg": "bot not found"})
+        }
+        "/v1/bot/get_online_info" => json!({
+            "code": 0,
+            "data": {
+                "bot_id": query["bot_id"],
+                "name": "客服助手",
+                "description": "售后问答",
+                "create_time": "1718289297",
+                "update_time": 1718289400,
+                "version": "1718289400000",
+                "bot_mode": 0,
+                "prompt_info": {"prompt": "你是一名客服"},
+                "onboarding_info": {"prologue": "你好", "suggested_questions": ["怎么退货？"]},
+                "model_info": {"model_id": "1706077826", "model_name": "豆包", "temperature": 0.7, "frequency_penalty": 0},
+                "plugin_info_list": [{
+                    "plugin_id": "p1",
+                    "name": "天气",
+                    "api_info_list": [{"api_id": "a1", "name": "get_weather"}]
+                }],
+                "knowledge": {"dataset_ids": ["ds_1"], "auto_call": true, "search_strategy": 1}
+            }
+        }),
+        "/v1/bot/create" => json!({"code": 0, "data": {"bot_id": "bot_new"}}),
+        "/v1/bot/update" if body["bot_id"] == "locked" => {
+            json!({"code": 4100, "msg": "no permission"})
+        }
+        "/v1/bot/update" => json!({"code": 0, "msg": ""}),
+        "/v1/bot/publish" => {
+            json!({"code": 0, "data": {"bot_id": body["bot_id"], "version": "1718289500000"}})
+        }
+        _ => json!({"code": 4000, "msg": "unexpected path"}),
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/bot/get_online_info", get(reply))
+        .route("/v1/bot/create", post(reply))
+        .route("/v1/bot/update", post(reply))
+        .route("/v1/bot/publish", post(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[tokio::test]
+async fn test_bot_client_methods() {
+    let (client, mock) = spawn_mock().await;
+
+    let bot = client.retrieve_bot("bot_1").await.unwrap();
+    assert_eq!(bot.name, "客服助手");
+    assert_eq!(bot.create_time, Some(1718289297));
+    assert_eq!(bot.prompt_info.unwrap().prompt, "你是一名客服");
+    let model = bot.model_info.unwrap();
+    assert_eq!(model.model_id.as_deref(), Some("1706077826"));
+    assert_eq!(model.extra["frequency_penalty"], 0);
+    assert_eq!(bot.plugin_info_list[0].api_info_list[0].api_id, "a1");
+    assert_eq!(bot.knowledge.unwrap().dataset_ids, vec!["ds_1"]);
+    assert!(bot.workflow_info_list.is_empty());
+
+    let err = client.retrieve_bot("missing").await.unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+
+    let created = client
+        .create_bot(
+            &CreateBotRequest::new("space".to_string(), "新助手".to_string())
+                .with_prompt("你是助手".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.bot_id, "bot_new");
+
+    client
+        .update_bot(
+            &UpdateBotRequest::new("bot_new".to_string()).with_knowledge(BotKnowledge {
+                dataset_ids: vec!["ds_2".to_string()],
+                auto_call: Some(false),
+                search_strategy: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+    let published = client
+        .publish_bot(&PublishBotRequest::new("bot_new".to_string()))
+        .await
+        .unwrap();
+    assert_eq!(published.version.as_deref(), Some("1718289500000"));
+
+    let requests = mock.bodies();
+    assert_eq!(
+        requests[2],
+        json!({"space_id": "space", "name": "新助手", "prompt_info": {"prompt": "你是助手"}})
+    );
+    assert_eq!(
+        requests[3],
+        json!({"bot_id": "bot_new", "knowledge": {"dataset_ids": ["ds_2"], "auto_call": false}})
+    );
+    assert_eq!(
+        requests[4],
+        json!({"bot_id": "bot_new", "connector_ids": ["1024"]})
+    );
+}
+
+#[tokio::test]
+async fn test_bot_management_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .get_bot(Some(json!({"bot_id": "bot_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(
+        structured["bot"]["onboarding_info"]["suggested_questions"][0],
+        "怎么退货？"
+    );
+    assert_eq!(structured["bot"]["knowledge"]["search_strategy"], 1);
+
+    let result = tools
+        .create_bot(Some(json!({
+            "name": "新助手",
+            "prompt": "你是助手",
+            "onboarding_info": {"prologue": "欢迎"},
+            "model_info_config": {"model_id": "1706077826", "temperature": 0.5},
+            "workflow_id_list": {"ids": [{"id": "wf_1"}]}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["bot_id"], "bot_new");
+
+    let result = tools
+        .update_bot(Some(json!({
+            "bot_id": "bot_new",
+            "name": "改名助手",
+            "plugin_id_list": {"id_list": [{"plugin_id": "p1", "api_id": "a1"}]}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+
+    let result = tools
+        .update_bot(Some(json!({"bot_id": "locked", "description": "x"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4100);
+
+    let result = tools
+        .publish_bot(Some(
+            json!({"bot_id": "bot_new", "connector_ids": ["1024", "999"]}),
+        ))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["version"], "1718289500000");
+    assert_eq!(structured["connector_ids"], json!(["1024", "999"]));
+
+    let requests = mock.bodies();
+    assert_eq!(
+        requests[1],
+        json!({
+            "space_id": "space",
+            "name": "新助手",
+            "prompt_info": {"prompt": "你是助手"},
+            "onboarding_info": {"prologue": "欢迎"},
+            "model_info_config": {"model_id": "1706077826", "temperature": 0.5},
+            "workflow_id_list": {"ids": [{"id": "wf_1"}]}
+        })
+    );
+    assert_eq!(
+        requests[2],
+        json!({
+            "bot_id": "bot_new",
+            "name": "改名助手",
+            "plugin_id_list": {"id_list": [{"plugin_id": "p1", "api_id": "a1"}]}
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_bot_tools_validate_arguments() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    assert!(tools.get_bot(Some(json!({}))).await.is_err());
+    for args in [
+        json!({"prompt": "你是助手"}),
+        json!({"name": "助手", "prompt": 1}),
+        json!({"name": "助手", "onboarding_info": "欢迎"}),
+    ] {
+        assert!(tools.create_bot(Some(args)).await.is_err());
+    }
+    // 没有可更新的字段
+    assert!(tools
+        .update_bot(Some(json!({"bot_id": "bot_1"})))
+        .await
+        .is_err());
+    assert!(tools
+        .update_bot(Some(json!({"bot_id": "bot_1", "knowledge": ["ds_1"]})))
+        .await
+        .is_err());
+    assert!(tools
+        .publish_bot(Some(json!({"bot_id": "bot_1", "connector_ids": []})))
+        .await
+        .is_err());
+    assert!(mock.requests().is_empty());
+
+    let tools = CozeTools::new(
+        Arc::new(CozeApiClient::new("http://127.0.0.1:9".to_string(), "pat".to_string()).unwrap()),
+        String::new(),
+    );
+    assert!(tools
+        .create_bot(Some(json!({"name": "助手"})))
+        .await
+        .is_err());
+}
+
+#[test]
+fn test_bot_endpoints_group() {
+    assert_eq!(endpoint_group("/v1/bot/get_online_info"), "bots");
+    assert_eq!(endpoint_group("/v1/bot/publish"), "bots");
+}
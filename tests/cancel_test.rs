@@ -0,0 +1,143 @@
+// 取消测试 - MCP 请求取消后，chat / chat_stream 立即停止并调用 /v3/chat/cancel
+mod common;
+
+use axum::body::{Body, Bytes};
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+const CHAT_CREATED: &str = "event: conversation.chat.created\ndata: {\"id\":\"chat_1\",\"conversation_id\":\"conv_1\"}\n\n";
+
+const STREAM_HEAD: &str = "event: conversation.chat.created\ndata: {\"id\":\"chat_1\",\"conversation_id\":\"conv_1\"}\n\n\
+event: conversation.message.delta\ndata: {\"id\":\"msg_1\",\"chat_id\":\"chat_1\",\"conversation_id\":\"conv_1\",\"delta\":{\"content\":\"partial\"}}\n\n";
+
+async fn create_chat(body: Json<Value>) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+        // 先发两条事件（bot_id 为 created_only 时只发 created），然后一直挂起，模拟 Bot 长时间生成
+        let head = if body["bot_id"] == "created_only" {
+            CHAT_CREATED
+        } else {
+            STREAM_HEAD
+        };
+        let head =
+            futures::stream::iter([Ok::<_, Infallible>(Bytes::from_static(head.as_bytes()))]);
+        let body = Body::from_stream(futures::StreamExt::chain(head, futures::stream::pending()));
+        ([(CONTENT_TYPE, "text/event-stream")], body).into_response()
+    } else {
+        Json(json!({
+            "code": 0,
+            "data": {"id": "chat_1", "conversation_id": "conv_1", "status": "in_progress"}
+        }))
+        .into_response()
+    }
+}
+
+async fn spawn_mock() -> (CozeTools, MockCoze) {
+    let app = Router::new()
+        .route("/v3/chat", post(create_chat))
+        .route(
+            "/v3/chat/retrieve",
+            get(|| async {
+                Json(json!({
+                    "code": 0,
+                    "data": {"id": "chat_1", "conversation_id": "conv_1", "status": "in_progress"}
+                }))
+            }),
+        )
+        .route(
+            "/v3/chat/cancel",
+            post(|| async {
+                Json(json!({
+                    "code": 0,
+                    "data": {"id": "chat_1", "conversation_id": "conv_1", "status": "canceled"}
+                }))
+            }),
+        );
+    let mock = MockCoze::start(app).await;
+    (mock.tools(), mock)
+}
+
+fn cancel_after(delay: Duration) -> CancellationToken {
+    let ct = CancellationToken::new();
+    let trigger = ct.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        trigger.cancel();
+    });
+    ct
+}
+
+fn assert_cancelled(result: &rmcp::model::CallToolResult, mock: &MockCoze) {
+    assert_eq!(result.is_error, Some(true));
+    let structured = result.structured_content.as_ref().unwrap();
+    assert_eq!(structured["error"], "cancelled");
+    assert_eq!(structured["status"], "canceled");
+    let cancelled: Vec<Value> = mock
+        .requests_to("/v3/chat/cancel")
+        .iter()
+        .map(|r| r.json())
+        .collect();
+    assert_eq!(
+        cancelled,
+        [json!({"conversation_id": "conv_1", "chat_id": "chat_1"})]
+    );
+}
+
+#[tokio::test]
+async fn test_chat_polling_stops_on_cancel() {
+    let (tools, mock) = spawn_mock().await;
+    let started = Instant::now();
+    let result = tools
+        .chat_with_context(
+            Some(json!({"bot_id": "bot", "message": "hi"})),
+            cancel_after(Duration::from_millis(200)),
+        )
+        .await
+        .unwrap();
+    // 不必等完 2 秒的轮询间隔
+    assert!(started.elapsed() < Duration::from_millis(1500));
+    assert_cancelled(&result, &mock);
+}
+
+#[tokio::test]
+async fn test_chat_stream_stops_on_cancel() {
+    let (tools, mock) = spawn_mock().await;
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        tools.chat_stream_with_context(
+            Some(json!({"bot_id": "bot", "message": "hi"})),
+            None,
+            cancel_after(Duration::from_millis(200)),
+        ),
+    )
+    .await
+    .expect("stream should stop on cancel")
+    .unwrap();
+    assert_cancelled(&result, &mock);
+    assert_eq!(result.structured_content.unwrap()["chat_id"], "chat_1");
+}
+
+#[tokio::test]
+async fn test_chat_stream_cancel_right_after_created() {
+    // 只收到 conversation.chat.created 就被取消，也要用其中的 chat id 取消对话
+    let (tools, mock) = spawn_mock().await;
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        tools.chat_stream_with_context(
+            Some(json!({"bot_id": "created_only", "message": "hi"})),
+            None,
+            cancel_after(Duration::from_millis(200)),
+        ),
+    )
+    .await
+    .expect("stream should stop on cancel")
+    .unwrap();
+    assert_cancelled(&result, &mock);
+}
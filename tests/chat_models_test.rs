@@ -38,6 +38,7 @@ async fn test_stream_event_serialization() {
         event: StreamEventType::ConversationMessageDelta,
         conversation_id: Some("conv_123".to_string()),
         id: Some("msg_456".to_string()),
+        chat_id: None,
         created_at: Some(1234567890),
         delta: Some(StreamDelta {
             content: Some("Hello".to_string()),
@@ -46,6 +47,7 @@ async fn test_stream_event_serialization() {
         }),
         usage: None,
         last_error: None,
+        required_action: None,
     };
 
     let json_value = serde_json::to_value(&stream_response).unwrap();
@@ -0,0 +1,118 @@
+// 集成测试公共设施 - 本地模拟 Coze 服务，记录收到的每个请求
+#![allow(dead_code)]
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Query, Request};
+use axum::http::{HeaderMap, Method};
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::Router;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// 模拟服务收到的一次请求
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: Method,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl Recorded {
+    /// 请求体按 JSON 解析，非 JSON 时为 Null
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+}
+
+/// 监听随机端口的模拟 Coze 服务，路由由各测试提供
+pub struct MockCoze {
+    pub base_url: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl MockCoze {
+    /// 启动服务；命中路由的请求在交给处理函数前被记录
+    pub async fn start(routes: Router) -> Self {
+        let requests: Arc<Mutex<Vec<Recorded>>> = Arc::default();
+        let recorder = requests.clone();
+        let app = routes.layer(middleware::from_fn(move |request: Request, next: Next| {
+            let recorder = recorder.clone();
+            async move { record(recorder, request, next).await }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        Self {
+            base_url: format!("http://{addr}"),
+            requests,
+        }
+    }
+
+    pub fn client(&self) -> Arc<CozeApiClient> {
+        Arc::new(CozeApiClient::new(self.base_url.clone(), "pat_test".to_string()).unwrap())
+    }
+
+    pub fn tools(&self) -> CozeTools {
+        CozeTools::new(self.client(), "space".to_string())
+    }
+
+    /// 按到达顺序返回已记录的请求
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// 只返回发往指定路径的请求
+    pub fn requests_to(&self, path: &str) -> Vec<Recorded> {
+        self.requests()
+            .into_iter()
+            .filter(|r| r.path == path)
+            .collect()
+    }
+
+    pub fn paths(&self) -> Vec<String> {
+        self.requests().into_iter().map(|r| r.path).collect()
+    }
+
+    pub fn bodies(&self) -> Vec<Value> {
+        self.requests().iter().map(Recorded::json).collect()
+    }
+
+    /// (路径, 请求体) 序列
+    pub fn path_bodies(&self) -> Vec<(String, Value)> {
+        self.requests()
+            .iter()
+            .map(|r| (r.path.clone(), r.json()))
+            .collect()
+    }
+}
+
+async fn record(requests: Arc<Mutex<Vec<Recorded>>>, request: Request, next: Next) -> Response {
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, usize::MAX).await.unwrap_or_default();
+    let query = Query::<HashMap<String, String>>::try_from_uri(&parts.uri)
+        .map(|Query(query)| query)
+        .unwrap_or_default();
+    requests.lock().unwrap().push(Recorded {
+        method: parts.method.clone(),
+        path: parts.uri.path().to_string(),
+        query,
+        headers: parts.headers.clone(),
+        body: body.clone(),
+    });
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
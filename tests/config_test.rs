@@ -0,0 +1,186 @@
+// 配置加载测试 - 覆盖 CLI > 环境变量 > 配置文件 > 默认 的分层与 timeout/retry 的实际生效
+mod common;
+
+use axum::routing::any;
+use axum::Router;
+use common::MockCoze;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::models::{CozeApiRequest, HttpMethod};
+use coze_mcp_server::utils::config::{CliArgs, Config, CozeConfig, LogFormat, TransportType};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| s.to_string()).collect()
+}
+
+fn write_config(name: &str, content: &str) -> String {
+    let path = std::env::temp_dir().join(format!("coze_mcp_{}_{}.toml", name, std::process::id()));
+    std::fs::write(&path, content).unwrap();
+    path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_partial_file_keeps_defaults() {
+    let path = write_config(
+        "partial",
+        r#"
+[coze]
+api_token = "pat_from_file"
+timeout = 5
+
+[logging]
+format = "compact"
+"#,
+    );
+    let config = Config::from_file(&path).unwrap();
+    assert_eq!(config.coze.api_token, "pat_from_file");
+    assert_eq!(config.coze.timeout, 5);
+    assert_eq!(
+        config.coze.retry_attempts,
+        CozeConfig::default().retry_attempts
+    );
+    assert_eq!(config.coze.base_url, CozeConfig::default().base_url);
+    assert!(matches!(config.logging.format, LogFormat::Compact));
+    assert!(matches!(config.server.transport, TransportType::Stdio));
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_layering_cli_over_env_over_file() {
+    let path = write_config(
+        "layering",
+        r#"
+[coze]
+api_token = "pat_file"
+base_url = "https://file.example"
+timeout = 10
+retry_attempts = 1
+
+[server]
+transport = "sse"
+"#,
+    );
+    let mut config = Config::from_file(&path).unwrap();
+    let env: HashMap<&str, &str> = [
+        ("COZE_API_TOKEN", "pat_env"),
+        ("COZE_TIMEOUT", "20"),
+        ("TRANSPORT", "http"),
+    ]
+    .into_iter()
+    .collect();
+    config
+        .apply_env(|k| env.get(k).map(|v| v.to_string()))
+        .unwrap();
+    let cli = CliArgs::parse(args(&["--api-key", "pat_cli", "--log-level=debug"]));
+    config.apply_cli(&cli).unwrap();
+
+    assert_eq!(config.coze.api_token, "pat_cli");
+    assert_eq!(config.coze.timeout, 20);
+    assert_eq!(config.coze.base_url, "https://file.example");
+    assert_eq!(config.coze.retry_attempts, 1);
+    assert!(matches!(config.server.transport, TransportType::Http));
+    assert_eq!(config.logging.level, "debug");
+    std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_cli_parsing_and_invalid_values() {
+    let cli = CliArgs::parse(args(&[
+        "--config",
+        "custom.toml",
+        "--unknown",
+        "--timeout=7",
+        "--retry-attempts",
+        "0",
+        "--transport",
+        "bogus",
+    ]));
+    assert_eq!(cli.config_path.as_deref(), Some("custom.toml"));
+    assert_eq!(cli.timeout.as_deref(), Some("7"));
+    assert_eq!(cli.retry_attempts.as_deref(), Some("0"));
+    assert!(!cli.help);
+
+    let mut config = Config::default();
+    assert!(config.apply_cli(&cli).is_err());
+    assert!(CliArgs::parse(args(&["-h"])).help);
+
+    let mut config = Config::default();
+    assert!(config
+        .apply_env(|k| (k == "COZE_RETRY_ATTEMPTS").then(|| "many".to_string()))
+        .is_err());
+}
+
+#[test]
+fn test_explicit_missing_config_file_is_error() {
+    let cli = CliArgs::parse(args(&["--config", "/nonexistent/coze_mcp.toml"]));
+    assert!(Config::load_with_cli(&cli).is_err());
+}
+
+// 模拟一个始终超时的端点，记录命中次数
+async fn spawn_slow_server() -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let app = Router::new().route(
+        "/slow",
+        any(move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "late"
+            }
+        }),
+    );
+    (MockCoze::start(app).await.base_url, hits)
+}
+
+fn request(method: HttpMethod) -> CozeApiRequest {
+    CozeApiRequest {
+        endpoint: "/slow".to_string(),
+        method,
+        headers: HashMap::new(),
+        params: HashMap::new(),
+        body: None,
+    }
+}
+
+#[tokio::test]
+async fn test_timeout_and_retry_attempts_take_effect() {
+    let (base_url, hits) = spawn_slow_server().await;
+    let client = CozeApiClient::from_config(&CozeConfig {
+        base_url,
+        api_token: "pat_test".to_string(),
+        timeout: 1,
+        retry_attempts: 1,
+        ..CozeConfig::default()
+    })
+    .unwrap();
+    assert_eq!(client.retry_attempts(), 1);
+
+    // GET：首次超时后重试一次
+    let started = std::time::Instant::now();
+    assert!(client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+    // POST：非幂等，不重试
+    assert!(client
+        .execute_request(request(HttpMethod::Post))
+        .await
+        .is_err());
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_example_config_parses() {
+    let config = Config::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/config.example.toml"))
+        .expect("config.example.toml should stay valid");
+    assert_eq!(config.coze.timeout, 30);
+    assert!(config.active_coze().is_ok());
+}
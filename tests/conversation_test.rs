@@ -0,0 +1,185 @@
+// 会话生命周期测试 - 创建 / 查看 / 清除上下文 / 删除，经本地模拟端点验证请求格式
+mod common;
+
+use axum::extract::{Path, Query};
+use axum::http::Method;
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use common::{MockCoze, Recorded};
+use coze_mcp_server::api::conversation_models::CreateConversationRequest;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+async fn create() -> Json<Value> {
+    Json(json!({
+        "code": 0,
+        "data": {"id": "conv_1", "created_at": 1718289297, "meta_data": {"uid": "u1"}, "last_section_id": "sec_1"}
+    }))
+}
+
+async fn retrieve(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+    if query.get("conversation_id").map(String::as_str) == Some("conv_1") {
+        Json(json!({
+            "code": 0,
+            "data": {"id": "conv_1", "created_at": 1718289297, "last_section_id": "sec_1"}
+        }))
+    } else {
+        Json(json!({"code": 4004, "msg": "conversation not found"}))
+    }
+}
+
+async fn clear(Path(id): Path<String>) -> Json<Value> {
+    Json(json!({"code": 0, "data": {"id": "sec_2", "conversation_id": id}}))
+}
+
+async fn remove() -> Json<Value> {
+    Json(json!({"code": 0, "msg": ""}))
+}
+
+async fn list() -> Json<Value> {
+    let conversations: Vec<Value> = (1..=7)
+        .map(|i| json!({"id": format!("conv_{i}"), "created_at": 1718289297, "last_section_id": format!("sec_{i}")}))
+        .collect();
+    Json(json!({"code": 0, "data": {"conversations": conversations, "has_more": false}}))
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/conversation/create", post(create))
+        .route("/v1/conversation/retrieve", get(retrieve))
+        .route("/v1/conversations", get(list))
+        .route("/v1/conversations/{id}/clear", post(clear))
+        .route("/v1/conversations/{id}", delete(remove));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+// 只看写操作（创建 / 清除 / 删除）
+fn writes(mock: &MockCoze) -> Vec<Recorded> {
+    mock.requests()
+        .into_iter()
+        .filter(|r| r.method != Method::GET)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_conversation_lifecycle_client() {
+    let (client, mock) = spawn_mock().await;
+
+    let request = CreateConversationRequest::new()
+        .with_bot_id("bot".to_string())
+        .with_meta_data(HashMap::from([("uid".to_string(), "u1".to_string())]));
+    let conversation = client.create_conversation(&request).await.unwrap();
+    assert_eq!(conversation.id, "conv_1");
+    assert_eq!(conversation.last_section_id.as_deref(), Some("sec_1"));
+
+    let conversation = client.retrieve_conversation("conv_1").await.unwrap();
+    assert_eq!(conversation.created_at, Some(1718289297));
+    let err = client.retrieve_conversation("missing").await.unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+
+    let section = client.clear_conversation("conv_1").await.unwrap();
+    assert_eq!(section.id, "sec_2");
+    client.delete_conversation("conv_1").await.unwrap();
+
+    let requests = writes(&mock);
+    assert_eq!(
+        requests[0].json(),
+        json!({"bot_id": "bot", "meta_data": {"uid": "u1"}})
+    );
+    assert_eq!(requests[1].path, "/v1/conversations/conv_1/clear");
+    assert_eq!(requests[2].method, Method::DELETE);
+}
+
+#[tokio::test]
+async fn test_create_conversation_tool_messages() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .create_conversation(Some(json!({
+            "bot_id": "bot",
+            "messages": [
+                {"role": "user", "content": "你好"},
+                {"role": "assistant", "content": "你好，有什么可以帮你？"},
+                {"content": [{"type": "image", "file_id": "file_1"}]}
+            ]
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(
+        result.structured_content.unwrap()["conversation_id"],
+        "conv_1"
+    );
+
+    // 非法片段在发送前拒绝
+    let bad = json!({"messages": [{"content": [{"type": "image"}]}]});
+    assert!(tools.create_conversation(Some(bad)).await.is_err());
+    let bad = json!({"messages": [{"role": "user"}]});
+    assert!(tools.create_conversation(Some(bad)).await.is_err());
+
+    let requests = writes(&mock);
+    assert_eq!(requests.len(), 1);
+    let body = requests[0].json();
+    let messages = body["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0]["content"], "你好");
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[2]["role"], "user");
+    assert_eq!(messages[2]["content_type"], "object_string");
+}
+
+#[tokio::test]
+async fn test_conversation_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .get_conversation(Some(json!({"conversation_id": "conv_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let result = tools
+        .get_conversation(Some(json!({"conversation_id": "missing"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4004);
+
+    let result = tools
+        .clear_conversation(Some(json!({"conversation_id": "conv_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["section_id"], "sec_2");
+    let result = tools
+        .delete_conversation(Some(json!({"conversation_id": "conv_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["deleted"], true);
+
+    for args in [None, Some(json!({})), Some(json!({"conversation_id": ""}))] {
+        assert!(tools.delete_conversation(args).await.is_err());
+    }
+    assert_eq!(writes(&mock).len(), 2);
+}
+
+#[tokio::test]
+async fn test_list_conversations_returns_all_items() {
+    let (client, _) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+    let result = tools
+        .list_conversations(Some(json!({"bot_id": "bot"})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    let items = structured["items"].as_array().unwrap();
+    assert_eq!(items.len(), 7);
+    assert_eq!(items[6]["id"], "conv_7");
+    assert_eq!(items[6]["last_section_id"], "sec_7");
+    assert_eq!(items[6]["created_at"], 1718289297);
+}
@@ -0,0 +1,214 @@
+// 知识库修改 / 删除测试 - 经本地模拟端点验证请求格式与删除确认
+mod common;
+
+use axum::http::{Method, Uri};
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::knowledge_models::UpdateDatasetRequest;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::knowledge::{KnowledgeConfig, KnowledgeManager};
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+async fn reply(method: Method, uri: Uri) -> Json<Value> {
+    let path = uri.path().to_string();
+    Json(match (method.as_str(), path.as_str()) {
+        ("GET", "/v1/datasets") => json!({
+            "code": 0,
+            "data": {
+                "total_count": 2,
+                "dataset_list": [
+                    {"dataset_id": "ds_1", "name": "产品手册", "description": "旧描述", "create_time": 1, "doc_count": 3},
+                    {"dataset_id": "ds_2", "name": "FAQ", "description": "", "create_time": 2, "doc_count": 0}
+                ]
+            }
+        }),
+        (_, "/v1/datasets/locked") => json!({"code": 4100, "msg": "no permission"}),
+        ("PUT", _) | ("DELETE", _) => json!({"code": 0, "msg": ""}),
+        _ => json!({"code": 4000, "msg": "unexpected request"}),
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/datasets", get(reply))
+        .route("/v1/datasets/{dataset_id}", put(reply).delete(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+// (方法, 路径, 请求体)
+fn calls(mock: &MockCoze) -> Vec<(String, String, Value)> {
+    mock.requests()
+        .iter()
+        .map(|r| (r.method.to_string(), r.path.clone(), r.json()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_client_update_and_delete_dataset() {
+    let (client, mock) = spawn_mock().await;
+
+    let request = UpdateDatasetRequest::new("新名称")
+        .with_description("新描述")
+        .with_icon("file_9");
+    client.update_dataset("ds_1", &request).await.unwrap();
+    client.delete_dataset("ds_2").await.unwrap();
+    let err = client.delete_dataset("locked").await.unwrap_err();
+    assert_eq!(err.code(), Some(4100));
+
+    let manager = KnowledgeManager::new((*client).clone(), KnowledgeConfig::default());
+    manager
+        .update_dataset("ds_1", &UpdateDatasetRequest::new("仅名称"))
+        .await
+        .unwrap();
+
+    let requests = calls(&mock);
+    assert_eq!(
+        requests[0],
+        (
+            "PUT".to_string(),
+            "/v1/datasets/ds_1".to_string(),
+            json!({"name": "新名称", "description": "新描述", "file_id": "file_9"})
+        )
+    );
+    assert_eq!(
+        requests[1],
+        (
+            "DELETE".to_string(),
+            "/v1/datasets/ds_2".to_string(),
+            Value::Null
+        )
+    );
+    assert_eq!(requests[3].2, json!({"name": "仅名称"}));
+}
+
+#[tokio::test]
+async fn test_permission_is_rejected_instead_of_ignored() {
+    let (client, mock) = spawn_mock().await;
+    let err = client
+        .create_knowledge_base_with_permission(
+            "kb".to_string(),
+            None,
+            Some("space".to_string()),
+            Some(1),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiError::BadRequest(_)));
+    assert!(err.to_string().contains("permission"));
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_update_dataset_tool_fills_current_values() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space_1".to_string());
+
+    // 只改描述：名称从列表读取
+    let result = tools
+        .update_dataset(Some(json!({"dataset_id": "ds_1", "description": "新描述"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["name"], "产品手册");
+
+    // 名称与描述都给出时不查询列表
+    tools
+        .update_dataset(Some(
+            json!({"dataset_id": "ds_2", "name": "常见问题", "description": "", "file_id": "file_1"}),
+        ))
+        .await
+        .unwrap();
+
+    // 只换图标
+    tools
+        .update_dataset(Some(json!({"dataset_id": "ds_2", "file_id": "file_2"})))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        calls(&mock),
+        vec![
+            ("GET".to_string(), "/v1/datasets".to_string(), Value::Null),
+            (
+                "PUT".to_string(),
+                "/v1/datasets/ds_1".to_string(),
+                json!({"name": "产品手册", "description": "新描述"})
+            ),
+            (
+                "PUT".to_string(),
+                "/v1/datasets/ds_2".to_string(),
+                json!({"name": "常见问题", "description": "", "file_id": "file_1"})
+            ),
+            ("GET".to_string(), "/v1/datasets".to_string(), Value::Null),
+            (
+                "PUT".to_string(),
+                "/v1/datasets/ds_2".to_string(),
+                json!({"name": "FAQ", "description": "", "file_id": "file_2"})
+            ),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_update_dataset_tool_validation() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space_1".to_string());
+
+    for bad in [
+        json!({"name": "x"}),
+        json!({"dataset_id": "ds_1"}),
+        json!({"dataset_id": "ds_1", "name": " "}),
+        json!({"dataset_id": "ds_1", "name": "名".repeat(101)}),
+    ] {
+        assert!(tools.update_dataset(Some(bad)).await.is_err());
+    }
+    assert!(mock.requests().is_empty());
+
+    // 列表中找不到该知识库
+    assert!(tools
+        .update_dataset(Some(json!({"dataset_id": "ds_x", "name": "x"})))
+        .await
+        .is_err());
+    let requests = calls(&mock);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, "GET");
+}
+
+#[tokio::test]
+async fn test_delete_dataset_requires_confirmation() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space_1".to_string());
+
+    for unconfirmed in [
+        json!({"dataset_id": "ds_1"}),
+        json!({"dataset_id": "ds_1", "confirm": false}),
+        json!({"dataset_id": "ds_1", "confirm": "yes"}),
+    ] {
+        let err = tools.delete_dataset(Some(unconfirmed)).await.unwrap_err();
+        assert!(err.message.contains("confirm=true"));
+    }
+    assert!(mock.requests().is_empty());
+
+    let result = tools
+        .delete_dataset(Some(json!({"dataset_id": "ds_1", "confirm": true})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["deleted"], true);
+
+    let result = tools
+        .delete_dataset(Some(json!({"dataset_id": "locked", "confirm": true})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let requests = calls(&mock);
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].0, "DELETE");
+    assert_eq!(requests[0].1, "/v1/datasets/ds_1");
+}
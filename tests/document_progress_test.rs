@@ -0,0 +1,326 @@
+// 文件处理进度测试 - 进度查询与上传后等待切片完成，经本地模拟端点验证
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::Uri;
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::knowledge_models::{document_status_text, DocumentProgress};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+// 进度查询次数；doc_1 在第 3 次查询时处理完毕
+type ProgressCalls = Arc<Mutex<u64>>;
+
+fn progress(document_id: &str, calls: u64) -> Value {
+    match document_id {
+        "doc_1" if calls < 3 => json!({
+            "document_id": "doc_1",
+            "document_name": "a.txt",
+            "status": 0,
+            "progress": (calls * 40).to_string(),
+            "remaining_time": 6,
+            "size": 12,
+            "type": "txt"
+        }),
+        "doc_1" => json!({
+            "document_id": "doc_1",
+            "document_name": "a.txt",
+            "status": 1,
+            "progress": 100,
+            "remaining_time": 0,
+            "size": "12",
+            "type": "txt"
+        }),
+        "doc_bad" => json!({
+            "document_id": "doc_bad",
+            "document_name": "bad.pdf",
+            "status": 9,
+            "progress": 0,
+            "status_descript": "parse failed"
+        }),
+        // 一直处于处理中
+        id => json!({"document_id": id, "document_name": "big.pdf", "status": 0, "progress": 10}),
+    }
+}
+
+async fn reply(State(progress_calls): State<ProgressCalls>, uri: Uri, body: Bytes) -> Json<Value> {
+    let path = uri.path().to_string();
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    Json(match path.as_str() {
+        "/open_api/knowledge/document/create" => {
+            let name = body["document_bases"][0]["name"].as_str().unwrap_or("");
+            let id = match name {
+                "bad.pdf" => "doc_bad",
+                "big.pdf" => "doc_big",
+                _ => "doc_1",
+            };
+            json!({
+                "code": 0,
+                "msg": "",
+                "document_infos": [{"document_id": id, "name": name, "status": 0}]
+            })
+        }
+        "/v1/datasets/forbidden/process" => json!({"code": 4100, "msg": "no permission"}),
+        _ => {
+            let calls = {
+                let mut calls = progress_calls.lock().unwrap();
+                *calls += 1;
+                *calls
+            };
+            let docs: Vec<Value> = body["document_ids"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|id| progress(id.as_str().unwrap(), calls))
+                .collect();
+            json!({"code": 0, "msg": "", "data": {"data": docs}})
+        }
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/open_api/knowledge/document/create", post(reply))
+        .route("/v1/datasets/{dataset_id}/process", post(reply))
+        .with_state(ProgressCalls::default());
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+fn temp_file(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("coze_progress_{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, "hello knowledge").unwrap();
+    path
+}
+
+#[test]
+fn test_document_progress_is_typed() {
+    let doc: DocumentProgress = serde_json::from_value(progress("doc_1", 1)).unwrap();
+    assert!(doc.is_processing());
+    assert_eq!(doc.progress, Some(40));
+    assert_eq!(doc.remaining_time, Some(6));
+    assert_eq!(doc.file_type.as_deref(), Some("txt"));
+    assert_eq!(doc.status_text(), "处理中");
+
+    let doc: DocumentProgress = serde_json::from_value(progress("doc_bad", 1)).unwrap();
+    assert!(doc.is_failed());
+    assert!(!doc.is_processing());
+    assert_eq!(doc.status_text(), "处理失败");
+    assert_eq!(document_status_text(Some(1)), "处理完毕");
+    assert_eq!(document_status_text(None), "未知");
+}
+
+#[tokio::test]
+async fn test_client_document_progress() {
+    let (client, mock) = spawn_mock().await;
+
+    let docs = client
+        .document_progress("ds_1", &["doc_1".to_string(), "doc_bad".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(docs.len(), 2);
+    assert_eq!(docs[0].document_id, "doc_1");
+    assert!(docs[1].is_failed());
+
+    let err = client
+        .document_progress("forbidden", &["doc_1".to_string()])
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(4100));
+    // 空列表在本地拒绝
+    assert!(client.document_progress("ds_1", &[]).await.is_err());
+
+    let requests = mock.path_bodies();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(
+        requests[0],
+        (
+            "/v1/datasets/ds_1/process".to_string(),
+            json!({"document_ids": ["doc_1", "doc_bad"]})
+        )
+    );
+}
+
+#[tokio::test]
+async fn test_get_document_progress_tool() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .get_document_progress(Some(
+            json!({"dataset_id": "ds_1", "document_ids": ["doc_bad", "doc_1"], "document_id": "doc_bad"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["processing"], 1);
+    assert_eq!(structured["failed"], 1);
+    assert_eq!(
+        structured["documents"][0]["status_descript"],
+        "parse failed"
+    );
+    let text = format!("{:?}", result.content);
+    assert!(text.contains("parse failed"));
+    assert!(text.contains("40%"));
+
+    let result = tools
+        .get_document_progress(Some(
+            json!({"dataset_id": "forbidden", "document_id": "doc_1"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    for bad in [
+        json!({"document_id": "doc_1"}),
+        json!({"dataset_id": "ds_1"}),
+        json!({"dataset_id": "ds_1", "document_ids": []}),
+    ] {
+        assert!(tools.get_document_progress(Some(bad)).await.is_err());
+    }
+
+    let requests = mock.path_bodies();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].1, json!({"document_ids": ["doc_bad", "doc_1"]}));
+}
+
+#[tokio::test]
+async fn test_upload_waits_until_processed() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+    let path = temp_file("a.txt");
+
+    let result = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "file_path": path,
+            "wait_until_processed": true,
+            "poll_interval_secs": 1
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["processed"], true);
+    assert_eq!(structured["timeout"], false);
+    assert_eq!(structured["progress"][0]["status"], 1);
+    assert!(format!("{:?}", result.content).contains("处理完毕"));
+
+    // 1 次上传 + 3 次进度查询
+    let requests = mock.path_bodies();
+    assert_eq!(requests.len(), 4);
+    assert_eq!(requests[3].0, "/v1/datasets/ds_1/process");
+    assert_eq!(requests[3].1, json!({"document_ids": ["doc_1"]}));
+}
+
+#[tokio::test]
+async fn test_upload_wait_reports_failure_and_timeout() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    // 处理失败的文件使工具结果标记为错误
+    let result = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "file_path": temp_file("bad.pdf"),
+            "wait_until_processed": true
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["processed"], true);
+    assert_eq!(structured["progress"][0]["status"], 9);
+    assert!(format!("{:?}", result.content).contains("parse failed"));
+
+    // 超时后返回当前进度
+    let result = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "file_path": temp_file("big.pdf"),
+            "wait_until_processed": true,
+            "timeout_secs": 0
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["processed"], false);
+    assert_eq!(structured["timeout"], true);
+    assert_eq!(structured["cancelled"], false);
+    assert_eq!(structured["progress"][0]["progress"], 10);
+
+    // 未要求等待时不查询进度
+    let result = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "file_path": temp_file("a.txt")
+        })))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert!(structured.get("progress").is_none());
+
+    assert_eq!(
+        mock.paths(),
+        vec![
+            "/open_api/knowledge/document/create",
+            "/v1/datasets/ds_1/process",
+            "/open_api/knowledge/document/create",
+            "/v1/datasets/ds_1/process",
+            "/open_api/knowledge/document/create",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_upload_wait_reports_cancellation() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        trigger.cancel();
+    });
+
+    // 客户端取消时区别于超时，调用方不应重试
+    let result = tokio::time::timeout(
+        Duration::from_secs(5),
+        tools.upload_document_to_knowledge_base_with_context(
+            Some(json!({
+                "dataset_id": "ds_1",
+                "file_path": temp_file("big.pdf"),
+                "wait_until_processed": true,
+                "timeout_secs": 60
+            })),
+            cancel,
+        ),
+    )
+    .await
+    .expect("wait should stop on cancel")
+    .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["processed"], false);
+    assert_eq!(structured["cancelled"], true);
+    assert_eq!(structured["timeout"], false);
+    assert!(format!("{:?}", result.content).contains("已取消等待"));
+    assert_eq!(
+        mock.paths(),
+        vec![
+            "/open_api/knowledge/document/create".to_string(),
+            "/v1/datasets/ds_1/process".to_string()
+        ]
+    );
+}
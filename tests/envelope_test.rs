@@ -0,0 +1,138 @@
+// 响应包裹解码测试 - Coze 业务错误码映射为类型化 ApiError，并保留 code / msg / logid
+mod common;
+
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::bot_models::ListBotsRequest;
+use coze_mcp_server::api::chat_models::ChatRequest;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::CozeApiClient;
+use serde_json::json;
+use std::sync::Arc;
+
+async fn spawn_mock() -> Arc<CozeApiClient> {
+    let app = Router::new()
+        .route(
+            "/v1/bots",
+            get(|| async {
+                Json(json!({
+                    "code": 4001,
+                    "msg": "token invalid",
+                    "detail": {"logid": "20250101-bots"}
+                }))
+            }),
+        )
+        .route(
+            "/v3/chat/retrieve",
+            get(|| async {
+                let mut headers = HeaderMap::new();
+                headers.insert("x-tt-logid", HeaderValue::from_static("hdr-logid"));
+                (
+                    headers,
+                    Json(json!({"code": 4004, "msg": "chat not found"})),
+                )
+            }),
+        )
+        .route(
+            "/v3/chat/message/list",
+            get(|| async {
+                (
+                    StatusCode::FORBIDDEN,
+                    Json(json!({
+                        "code": 4003,
+                        "msg": "no permission",
+                        "detail": {"logid": "forbidden-logid"}
+                    })),
+                )
+            }),
+        )
+        .route(
+            "/v3/chat",
+            post(|body: Json<serde_json::Value>| async move {
+                if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+                    Json(json!({"code": 4001, "msg": "stream auth failed"}))
+                } else {
+                    Json(json!({
+                        "code": 4029,
+                        "msg": "too many requests",
+                        "detail": {"logid": "chat-logid"}
+                    }))
+                }
+            }),
+        );
+    MockCoze::start(app).await.client()
+}
+
+#[test]
+fn test_code_mapping() {
+    let map = |code| ApiError::from_coze_code(code, "m".to_string(), None, None, None);
+    assert!(matches!(map(4000), ApiError::BadRequest(_)));
+    assert!(matches!(map(4001), ApiError::AuthenticationError(_)));
+    assert!(matches!(map(4003), ApiError::AuthorizationError(_)));
+    assert!(matches!(map(4004), ApiError::NotFound(_)));
+    assert!(matches!(map(4029), ApiError::RateLimitExceeded(_)));
+    assert!(matches!(map(5000), ApiError::ServerError(_)));
+    let unknown = map(720702002);
+    assert!(matches!(unknown, ApiError::BadRequest(_)));
+    assert_eq!(unknown.code(), Some(720702002));
+}
+
+#[tokio::test]
+async fn test_business_errors_are_typed() {
+    let client = spawn_mock().await;
+
+    let err = client
+        .list_bots_typed(&ListBotsRequest::new("space".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiError::AuthenticationError(_)));
+    assert_eq!(err.code(), Some(4001));
+    assert_eq!(err.logid(), Some("20250101-bots"));
+    assert_eq!(err.data().message, "token invalid");
+    assert_eq!(
+        err.to_string(),
+        "Authentication error: token invalid (code 4001, logid 20250101-bots)"
+    );
+
+    // 响应体没有 detail.logid 时回退到 X-Tt-Logid 头
+    let err = client.get_chat_detail("c", "x").await.unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+    assert_eq!(err.logid(), Some("hdr-logid"));
+
+    let err = client
+        .chat(ChatRequest::new("bot".to_string(), "hi".to_string()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiError::RateLimitExceeded(_)));
+    assert_eq!(err.logid(), Some("chat-logid"));
+
+    let serialized = serde_json::to_value(&err).unwrap();
+    assert_eq!(serialized["RateLimitExceeded"]["code"], 4029);
+    assert_eq!(serialized["RateLimitExceeded"]["logid"], "chat-logid");
+}
+
+#[tokio::test]
+async fn test_http_errors_keep_coze_fields() {
+    let client = spawn_mock().await;
+    let err = client.get_chat_messages("c", "x").await.unwrap_err();
+    assert!(matches!(err, ApiError::AuthorizationError(_)));
+    assert_eq!(err.data().status, Some(403));
+    assert_eq!(err.code(), Some(4003));
+    assert_eq!(err.logid(), Some("forbidden-logid"));
+}
+
+#[tokio::test]
+async fn test_stream_json_error_body() {
+    let client = spawn_mock().await;
+    let result = client
+        .chat_stream(ChatRequest::new("bot".to_string(), "hi".to_string()))
+        .await;
+    let err = match result {
+        Ok(_) => panic!("expected error"),
+        Err(e) => e,
+    };
+    assert!(matches!(err, ApiError::AuthenticationError(_)));
+    assert_eq!(err.code(), Some(4001));
+}
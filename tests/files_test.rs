@@ -0,0 +1,123 @@
+// 文件接口测试 - multipart 上传与文件详情查询，经本地模拟端点验证请求格式
+mod common;
+
+use axum::extract::Query;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::endpoints::endpoint_group;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+async fn upload() -> Json<Value> {
+    Json(json!({
+        "code": 0,
+        "msg": "",
+        "data": {"id": "file_1", "bytes": 11, "created_at": 1715847583, "file_name": "note.txt"}
+    }))
+}
+
+async fn retrieve(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+    if query.get("file_id").map(String::as_str) == Some("file_1") {
+        Json(json!({
+            "code": 0,
+            "data": {"id": "file_1", "bytes": 11, "created_at": 1715847583, "file_name": "note.txt"}
+        }))
+    } else {
+        Json(json!({"code": 4004, "msg": "file not found"}))
+    }
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/files/upload", post(upload))
+        .route("/v1/files/retrieve", get(retrieve));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+fn contains(haystack: &[u8], needle: &str) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|w| w == needle.as_bytes())
+}
+
+#[tokio::test]
+async fn test_upload_file_sends_multipart() {
+    let (client, mock) = spawn_mock().await;
+    let file = client
+        .upload_file("note.txt".to_string(), b"hello coze!".to_vec())
+        .await
+        .unwrap();
+    assert_eq!(file.id, "file_1");
+    assert_eq!(file.bytes, Some(11));
+
+    let upload = &mock.requests()[0];
+    let (content_type, body) = (upload.header("content-type").unwrap(), &upload.body);
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    assert!(contains(body, r#"name="file"; filename="note.txt""#));
+    assert!(contains(body, "hello coze!"));
+}
+
+#[tokio::test]
+async fn test_retrieve_file() {
+    let (client, _) = spawn_mock().await;
+    let file = client.retrieve_file("file_1").await.unwrap();
+    assert_eq!(file.file_name.as_deref(), Some("note.txt"));
+    assert_eq!(file.created_at, Some(1715847583));
+
+    let err = client.retrieve_file("missing").await.unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_upload_and_info_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let path = std::env::temp_dir().join(format!("coze_mcp_upload_{}.txt", std::process::id()));
+    std::fs::write(&path, "hello coze!").unwrap();
+    let result = tools
+        .upload_file(Some(json!({"file_path": path.to_string_lossy()})))
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["file_id"], "file_1");
+    let expected_name = format!(
+        r#"filename="{}""#,
+        path.file_name().unwrap().to_string_lossy()
+    );
+    assert!(contains(&mock.requests()[0].body, &expected_name));
+
+    let result = tools
+        .upload_file(Some(json!({"file_path": "/nonexistent/coze.txt"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert!(tools.upload_file(Some(json!({}))).await.is_err());
+
+    let result = tools
+        .get_file_info(Some(json!({"file_id": "file_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["file"]["bytes"], 11);
+
+    let result = tools
+        .get_file_info(Some(json!({"file_id": "missing"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4004);
+}
+
+#[test]
+fn test_files_endpoint_group() {
+    assert_eq!(endpoint_group("/v1/files/upload"), "files");
+    assert_eq!(endpoint_group("/v1/files/retrieve"), "files");
+}
@@ -0,0 +1,152 @@
+// Streamable HTTP 传输测试 - 在本地端口启动服务并验证多会话初始化与长连接计数
+use axum::response::sse::{Event, Sse};
+use axum::routing::post;
+use axum::{Json, Router};
+use coze_mcp_server::transport::{apply_server_limits, streamable_http_router, MCP_HTTP_PATH};
+use coze_mcp_server::utils::config::ServerConfig;
+use futures::{stream, StreamExt};
+use rmcp::model::{ServerCapabilities, ServerInfo};
+use rmcp::ServerHandler;
+use serde_json::json;
+
+#[derive(Clone)]
+struct EchoServer;
+
+impl ServerHandler for EchoServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+}
+
+async fn spawn_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = streamable_http_router(|| EchoServer);
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    format!("http://{addr}{MCP_HTTP_PATH}")
+}
+
+async fn initialize(client: &reqwest::Client, url: &str) -> reqwest::Response {
+    client
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2025-03-26",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "0.0.1" }
+            }
+        }))
+        .send()
+        .await
+        .expect("initialize request")
+}
+
+#[tokio::test]
+async fn test_http_transport_serves_multiple_sessions() {
+    let url = spawn_server().await;
+    let client = reqwest::Client::new();
+
+    let (first, second) = tokio::join!(initialize(&client, &url), initialize(&client, &url));
+    assert!(first.status().is_success());
+    assert!(second.status().is_success());
+
+    let first_session = first
+        .headers()
+        .get("mcp-session-id")
+        .expect("session header")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let second_session = second
+        .headers()
+        .get("mcp-session-id")
+        .expect("session header")
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_ne!(first_session, second_session);
+
+    let body = first.text().await.unwrap();
+    assert!(body.contains("\"protocolVersion\""));
+}
+
+#[tokio::test]
+async fn test_http_transport_rejects_unknown_session() {
+    let url = spawn_server().await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&url)
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .header("mcp-session-id", "does-not-exist")
+        .json(&json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_post_event_streams_count_against_max_connections() {
+    // POST 以 SSE 长响应返回时同样占用连接名额；普通 JSON 响应不占用
+    let config = ServerConfig {
+        max_connections: 1,
+        cors_origins: vec!["*".to_string()],
+        ..Default::default()
+    };
+    let router = Router::new()
+        .route(
+            "/stream",
+            post(|| async {
+                let events = stream::once(async {
+                    Ok::<_, std::convert::Infallible>(Event::default().data("started"))
+                })
+                .chain(stream::pending());
+                Sse::new(events)
+            }),
+        )
+        .route("/json", post(|| async { Json(json!({"ok": true})) }));
+    let router = apply_server_limits(router, &config);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    let base = format!("http://{addr}");
+    let client = reqwest::Client::new();
+
+    let mut held = client.post(format!("{base}/stream")).send().await.unwrap();
+    assert!(held.status().is_success());
+    held.chunk().await.unwrap().expect("first event");
+
+    let rejected = client.post(format!("{base}/stream")).send().await.unwrap();
+    assert_eq!(rejected.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    let plain = client.post(format!("{base}/json")).send().await.unwrap();
+    assert!(plain.status().is_success());
+
+    drop(held);
+    let mut reopened = None;
+    for _ in 0..50 {
+        let response = client.post(format!("{base}/stream")).send().await.unwrap();
+        if response.status().is_success() {
+            reopened = Some(response);
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert!(
+        reopened.is_some(),
+        "slot should be released after the stream closes"
+    );
+}
@@ -0,0 +1,308 @@
+// 知识库文件管理测试 - 文件列表 / 修改 / 删除，经本地模拟端点验证请求格式
+mod common;
+
+use axum::body::Bytes;
+use axum::http::Uri;
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::knowledge_models::{
+    DeleteDocumentsRequest, DocumentUpdateRule, KnowledgeDocumentUploadResponseCn,
+    ListDocumentsRequest, UpdateDocumentRequest,
+};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn document(i: u64) -> Value {
+    json!({
+        "document_id": format!("doc_{i}"),
+        "name": format!("手册 {i}.pdf"),
+        "status": if i == 2 { 9 } else { 1 },
+        "status_descript": if i == 2 { "parse failed" } else { "" },
+        "format_type": 0,
+        "type": "pdf",
+        "source_type": 0,
+        "size": format!("{}", 1024 * i),
+        "char_count": 300,
+        "slice_count": i,
+        "hit_count": "7",
+        "update_type": 0,
+        "update_interval": 0,
+        "chunk_strategy": {"chunk_type": 0},
+        "create_time": 1718289297,
+        "update_time": "1718289400",
+        "tos_uri": "tos://x"
+    })
+}
+
+async fn reply(uri: Uri, body: Bytes) -> Json<Value> {
+    let path = uri.path().to_string();
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    Json(match path.as_str() {
+        "/open_api/knowledge/document/list" if body["dataset_id"] == "forbidden" => {
+            json!({"code": 4100, "msg": "no permission"})
+        }
+        // 共 5 个文件
+        "/open_api/knowledge/document/list" => {
+            let page = body["page"].as_u64().unwrap();
+            let size = body["size"].as_u64().unwrap();
+            let docs: Vec<Value> = ((page - 1) * size..(page * size).min(5))
+                .map(document)
+                .collect();
+            json!({"code": 0, "msg": "", "document_infos": docs, "total": 5})
+        }
+        "/open_api/knowledge/document/update" => json!({"code": 0, "msg": ""}),
+        "/open_api/knowledge/document/delete" => json!({"code": 0, "msg": ""}),
+        _ => json!({"code": 4000, "msg": "unexpected path"}),
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/open_api/knowledge/document/list", post(reply))
+        .route("/open_api/knowledge/document/update", post(reply))
+        .route("/open_api/knowledge/document/delete", post(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[test]
+fn test_document_info_is_typed() {
+    // 上传接口返回的 document_infos 与文件列表使用同一结构
+    let resp: KnowledgeDocumentUploadResponseCn = serde_json::from_value(json!({
+        "code": 0,
+        "msg": "",
+        "document_infos": [document(2)]
+    }))
+    .unwrap();
+    let doc = &resp.document_infos.unwrap()[0];
+    assert_eq!(doc.document_id, "doc_2");
+    assert_eq!(doc.file_type.as_deref(), Some("pdf"));
+    assert_eq!(doc.size, Some(2048));
+    assert_eq!(doc.hit_count, Some(7));
+    assert_eq!(doc.update_time, Some(1718289400));
+    assert_eq!(doc.status_text(), "处理失败");
+    assert_eq!(doc.status_descript.as_deref(), Some("parse failed"));
+    // 未映射的字段保留
+    assert_eq!(doc.extra["tos_uri"], "tos://x");
+    let value = serde_json::to_value(doc).unwrap();
+    assert_eq!(value["type"], "pdf");
+    assert_eq!(value["tos_uri"], "tos://x");
+}
+
+#[test]
+fn test_update_request_shape() {
+    let request = UpdateDocumentRequest::new("doc_1");
+    assert!(request.is_empty());
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"document_id": "doc_1"})
+    );
+    let request = request
+        .with_name("新名称")
+        .with_update_rule(DocumentUpdateRule::every_hours(48));
+    assert!(!request.is_empty());
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "document_id": "doc_1",
+            "document_name": "新名称",
+            "update_rule": {"update_type": 1, "update_interval": 48}
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(DocumentUpdateRule::no_auto_update()).unwrap(),
+        json!({"update_type": 0})
+    );
+}
+
+#[tokio::test]
+async fn test_client_document_methods() {
+    let (client, mock) = spawn_mock().await;
+
+    let list = client
+        .list_documents(&ListDocumentsRequest::new("ds_1").with_page(2, 2))
+        .await
+        .unwrap();
+    assert_eq!(list.total, Some(5));
+    let ids: Vec<&str> = list
+        .document_infos
+        .iter()
+        .map(|d| d.document_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["doc_2", "doc_3"]);
+
+    client
+        .update_document(&UpdateDocumentRequest::new("doc_1").with_name("a.pdf"))
+        .await
+        .unwrap();
+    client
+        .delete_documents(&DeleteDocumentsRequest::new(vec![
+            "doc_1".to_string(),
+            "doc_2".to_string(),
+        ]))
+        .await
+        .unwrap();
+
+    // 空列表与超过 100 个在本地拒绝，不发请求
+    let too_many = (0..101).map(|i| format!("doc_{i}")).collect();
+    assert!(matches!(
+        client
+            .delete_documents(&DeleteDocumentsRequest::new(too_many))
+            .await,
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(client
+        .delete_documents(&DeleteDocumentsRequest::new(vec![]))
+        .await
+        .is_err());
+
+    let err = client
+        .list_documents(&ListDocumentsRequest::new("forbidden"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(4100));
+
+    let requests = mock.path_bodies();
+    assert_eq!(
+        requests[0],
+        (
+            "/open_api/knowledge/document/list".to_string(),
+            json!({"dataset_id": "ds_1", "page": 2, "size": 2})
+        )
+    );
+    assert_eq!(
+        requests[1].1,
+        json!({"document_id": "doc_1", "document_name": "a.pdf"})
+    );
+    assert_eq!(requests[2].1, json!({"document_ids": ["doc_1", "doc_2"]}));
+    assert_eq!(requests.len(), 4);
+}
+
+#[tokio::test]
+async fn test_list_knowledge_documents_tool() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .list_knowledge_documents(Some(json!({"dataset_id": "ds_1", "page_size": 2})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["total"], 5);
+    assert_eq!(structured["items"].as_array().unwrap().len(), 2);
+    assert_eq!(structured["items"][0]["document_id"], "doc_0");
+    assert_eq!(structured["items"][1]["slice_count"], 1);
+    assert_eq!(structured["next_cursor"], json!({"page": 2}));
+
+    let result = tools
+        .list_knowledge_documents(Some(
+            json!({"dataset_id": "ds_1", "page_size": 2, "all_pages": true}),
+        ))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["items"].as_array().unwrap().len(), 5);
+    assert_eq!(structured["has_more"], false);
+    let text = format!("{:?}", result.content);
+    assert!(text.contains("处理失败"));
+
+    let result = tools
+        .list_knowledge_documents(Some(json!({"dataset_id": "forbidden"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4100);
+
+    assert!(tools
+        .list_knowledge_documents(Some(json!({})))
+        .await
+        .is_err());
+    // 1 + 3 + 1 次请求
+    assert_eq!(mock.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn test_update_knowledge_document_tool() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .update_knowledge_document(Some(
+            json!({"document_id": "doc_1", "document_name": "新手册.pdf"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    tools
+        .update_knowledge_document(Some(json!({"document_id": "doc_1", "auto_update": true})))
+        .await
+        .unwrap();
+    tools
+        .update_knowledge_document(Some(json!({"document_id": "doc_1", "update_interval": 72})))
+        .await
+        .unwrap();
+    tools
+        .update_knowledge_document(Some(json!({"document_id": "doc_1", "auto_update": false})))
+        .await
+        .unwrap();
+
+    for bad in [
+        json!({"document_id": "doc_1"}),
+        json!({"document_name": "x"}),
+        json!({"document_id": "doc_1", "document_name": "  "}),
+        json!({"document_id": "doc_1", "update_interval": 12}),
+        json!({"document_id": "doc_1", "auto_update": false, "update_interval": 48}),
+    ] {
+        assert!(tools.update_knowledge_document(Some(bad)).await.is_err());
+    }
+
+    assert_eq!(
+        mock.bodies(),
+        vec![
+            json!({"document_id": "doc_1", "document_name": "新手册.pdf"}),
+            json!({"document_id": "doc_1", "update_rule": {"update_type": 1, "update_interval": 24}}),
+            json!({"document_id": "doc_1", "update_rule": {"update_type": 1, "update_interval": 72}}),
+            json!({"document_id": "doc_1", "update_rule": {"update_type": 0}}),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_delete_knowledge_documents_tool() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .delete_knowledge_documents(Some(
+            json!({"document_ids": ["doc_1", "doc_2", "doc_1"], "document_id": "doc_3"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["deleted"], json!(["doc_1", "doc_2", "doc_3"]));
+    assert_eq!(structured["count"], 3);
+
+    let too_many: Vec<String> = (0..101).map(|i| format!("doc_{i}")).collect();
+    for bad in [
+        json!({}),
+        json!({"document_ids": []}),
+        json!({"document_ids": "doc_1"}),
+        json!({"document_ids": ["doc_1", 2]}),
+        json!({"document_ids": too_many}),
+    ] {
+        assert!(tools.delete_knowledge_documents(Some(bad)).await.is_err());
+    }
+
+    let requests = mock.path_bodies();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].1,
+        json!({"document_ids": ["doc_1", "doc_2", "doc_3"]})
+    );
+}
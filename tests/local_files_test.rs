@@ -0,0 +1,218 @@
+// 本地文件访问范围测试 - 远程传输下禁止或限定工具按路径读写服务器文件，经本地模拟端点验证上传
+mod common;
+
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::tools::local_files::LocalFiles;
+use coze_mcp_server::utils::config::{ServerConfig, TransportType};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+async fn upload() -> Json<Value> {
+    Json(json!({
+        "code": 0,
+        "msg": "",
+        "data": {"id": "file_1", "bytes": 11, "file_name": "note.txt"}
+    }))
+}
+
+async fn spawn_mock() -> MockCoze {
+    MockCoze::start(Router::new().route("/v1/files/upload", post(upload))).await
+}
+
+// 每个测试独立的临时目录：root 为允许目录，outside 为其同级目录
+fn scratch(name: &str) -> (PathBuf, PathBuf) {
+    let base = std::env::temp_dir().join(format!("coze_mcp_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&base);
+    let (root, outside) = (base.join("root"), base.join("outside"));
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::create_dir_all(&outside).unwrap();
+    std::fs::write(root.join("note.txt"), "hello coze!").unwrap();
+    std::fs::write(outside.join("secret.txt"), "secret").unwrap();
+    (root, outside)
+}
+
+fn within(root: &PathBuf) -> LocalFiles {
+    LocalFiles::Within(std::fs::canonicalize(root).unwrap())
+}
+
+#[test]
+fn test_for_server_defaults_by_transport() {
+    let mut server = ServerConfig::default();
+    assert!(matches!(
+        LocalFiles::for_server(&server).unwrap(),
+        LocalFiles::Unrestricted
+    ));
+    for transport in [TransportType::Http, TransportType::Sse] {
+        server.transport = transport;
+        assert!(matches!(
+            LocalFiles::for_server(&server).unwrap(),
+            LocalFiles::Disabled
+        ));
+    }
+
+    let (root, _) = scratch("for_server");
+    server.file_root = Some(root.to_string_lossy().to_string());
+    assert!(matches!(
+        LocalFiles::for_server(&server).unwrap(),
+        LocalFiles::Within(_)
+    ));
+    server.file_root = Some("/nonexistent/coze_root".to_string());
+    assert!(LocalFiles::for_server(&server).is_err());
+}
+
+#[test]
+fn test_within_rejects_escapes() {
+    let (root, outside) = scratch("within");
+    let files = within(&root);
+
+    assert!(files
+        .readable(&root.join("note.txt").to_string_lossy())
+        .is_ok());
+    assert!(files
+        .readable(&outside.join("secret.txt").to_string_lossy())
+        .is_err());
+    let dotdot = root.join("..").join("outside").join("secret.txt");
+    assert!(files.readable(&dotdot.to_string_lossy()).is_err());
+    assert!(files.writable(&dotdot.to_string_lossy()).is_err());
+
+    #[cfg(unix)]
+    {
+        let link = root.join("link.txt");
+        std::os::unix::fs::symlink(outside.join("secret.txt"), &link).unwrap();
+        assert!(files.readable(&link.to_string_lossy()).is_err());
+        assert!(files.writable(&link.to_string_lossy()).is_err());
+    }
+
+    // 新文件只要所在目录在范围内即可写入
+    assert!(files
+        .writable(&root.join("export.json").to_string_lossy())
+        .is_ok());
+    assert!(files
+        .writable(&outside.join("export.json").to_string_lossy())
+        .is_err());
+
+    let disabled = LocalFiles::Disabled;
+    assert!(disabled
+        .readable(&root.join("note.txt").to_string_lossy())
+        .is_err());
+    assert!(disabled
+        .writable(&root.join("export.json").to_string_lossy())
+        .is_err());
+}
+
+#[test]
+fn test_within_resolves_relative_paths_against_root() {
+    let (root, _) = scratch("relative");
+    std::fs::create_dir_all(root.join("docs")).unwrap();
+    std::fs::write(root.join("docs").join("a.txt"), "a").unwrap();
+    let files = within(&root);
+    let canonical = std::fs::canonicalize(&root).unwrap();
+    // 测试进程的工作目录是仓库根目录，与 file_root 不同
+    assert_ne!(std::env::current_dir().unwrap(), canonical);
+
+    assert_eq!(
+        files.readable("docs/a.txt").unwrap(),
+        canonical.join("docs").join("a.txt")
+    );
+    assert_eq!(
+        files.readable("note.txt").unwrap(),
+        canonical.join("note.txt")
+    );
+    assert_eq!(
+        files.writable("docs/export.json").unwrap(),
+        canonical.join("docs").join("export.json")
+    );
+    assert_eq!(
+        files.writable("export.json").unwrap(),
+        canonical.join("export.json")
+    );
+    // 工作目录下存在、但不在 file_root 内的相对路径不会被命中
+    assert!(files.readable("Cargo.toml").is_err());
+    assert!(files.readable("../outside/secret.txt").is_err());
+    assert!(files.writable("../outside/export.json").is_err());
+    assert!(files.writable("missing/export.json").is_err());
+}
+
+#[tokio::test]
+async fn test_upload_file_respects_policy() {
+    let mock = spawn_mock().await;
+    let (root, outside) = scratch("upload");
+    let note = root.join("note.txt").to_string_lossy().to_string();
+
+    let disabled = mock.tools().with_local_files(LocalFiles::Disabled);
+    let err = disabled
+        .upload_file(Some(json!({"file_path": note})))
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("--file-root"));
+
+    let limited = mock.tools().with_local_files(within(&root));
+    assert!(limited
+        .upload_file(Some(json!({
+            "file_path": outside.join("secret.txt").to_string_lossy()
+        })))
+        .await
+        .is_err());
+    assert!(mock.requests().is_empty());
+
+    let result = limited
+        .upload_file(Some(json!({"file_path": note})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    // 相对路径相对 file_root 解析
+    let result = limited
+        .upload_file(Some(json!({"file_path": "note.txt"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(mock.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_upload_file_base64() {
+    let mock = spawn_mock().await;
+    // 禁止本地路径时仍可直接上传内容
+    let tools = mock.tools().with_local_files(LocalFiles::Disabled);
+
+    let result = tools
+        .upload_file(Some(json!({
+            "file_base64": "aGVsbG8gY296ZSE=",
+            "file_name": "note.txt"
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["file_id"], "file_1");
+    let body = &mock.requests()[0].body;
+    assert!(body.windows(11).any(|w| w == b"hello coze!"));
+
+    for bad in [
+        json!({"file_base64": "aGVsbG8gY296ZSE="}),
+        json!({"file_base64": "not base64!", "file_name": "a.txt"}),
+        json!({"file_base64": "", "file_name": "a.txt"}),
+        json!({"file_base64": "aGVsbG8=", "file_path": "/tmp/a.txt", "file_name": "a.txt"}),
+    ] {
+        assert!(tools.upload_file(Some(bad)).await.is_err());
+    }
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_knowledge_upload_respects_policy() {
+    let mock = spawn_mock().await;
+    let tools = mock.tools().with_local_files(LocalFiles::Disabled);
+    let (root, _) = scratch("knowledge");
+
+    let err = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "file_path": root.join("note.txt").to_string_lossy()
+        })))
+        .await
+        .unwrap_err();
+    assert!(err.message.contains("--file-root"));
+    assert!(mock.requests().is_empty());
+}
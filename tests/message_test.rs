@@ -0,0 +1,271 @@
+// 会话消息管理测试 - 创建 / 游标翻页列表 / 查看 / 修改 / 删除，经本地模拟端点验证请求格式
+mod common;
+
+use axum::body::Bytes;
+use axum::extract::Query;
+use axum::http::Uri;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::chat_models::{ChatMessage, ContentType, MessageRole};
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::message_models::ListMessagesRequest;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn message(id: &str, content: &str) -> Value {
+    json!({
+        "id": id,
+        "conversation_id": "conv_1",
+        "bot_id": "bot",
+        "chat_id": "chat_1",
+        "section_id": "sec_1",
+        "role": "assistant",
+        "type": "answer",
+        "content": content,
+        "content_type": "text",
+        "meta_data": {"source": "test"},
+        "created_at": 1718592898,
+        "updated_at": 1718592898
+    })
+}
+
+async fn reply(uri: Uri, Query(query): Query<HashMap<String, String>>, body: Bytes) -> Json<Value> {
+    let path = uri.path().to_string();
+    // GET / 无请求体的 POST 也带 JSON Content-Type，按原始字节解析
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    if query.get("message_id").map(String::as_str) == Some("missing") {
+        return Json(json!({"code": 4004, "msg": "message not found"}));
+    }
+    Json(match path.as_str() {
+        "/v1/conversation/message/create" => {
+            json!({"code": 0, "data": {
+                "id": "msg_new",
+                "conversation_id": query["conversation_id"],
+                "role": body["role"],
+                "content": body["content"],
+                "content_type": body["content_type"],
+                "meta_data": body.get("meta_data").cloned().unwrap_or(json!({}))
+            }})
+        }
+        // 分页游标与 data 同级
+        "/v1/conversation/message/list" => json!({
+            "code": 0,
+            "data": [message("msg_3", "第三条"), message("msg_2", "第二条")],
+            "first_id": "msg_3",
+            "last_id": "msg_2",
+            "has_more": true
+        }),
+        // 修改接口的消息位于顶层 message 字段
+        "/v1/conversation/message/modify" => {
+            json!({"code": 0, "msg": "", "message": message("msg_2", "改过的内容")})
+        }
+        _ => json!({"code": 0, "data": message(&query["message_id"], "第二条")}),
+    })
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/conversation/message/create", post(reply))
+        .route("/v1/conversation/message/list", post(reply))
+        .route("/v1/conversation/message/retrieve", get(reply))
+        .route("/v1/conversation/message/modify", post(reply))
+        .route("/v1/conversation/message/delete", post(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[tokio::test]
+async fn test_message_client_methods() {
+    let (client, mock) = spawn_mock().await;
+
+    let created = client
+        .create_message(
+            "conv_1",
+            &ChatMessage::text(MessageRole::User, "补充背景".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created.id, "msg_new");
+    assert_eq!(created.message.role, MessageRole::User);
+
+    let page = client
+        .list_messages(
+            "conv_1",
+            &ListMessagesRequest::new()
+                .with_before_id("msg_4".to_string())
+                .with_limit(2),
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.data[0].message_type.as_deref(), Some("answer"));
+    assert!(matches!(
+        page.data[0].message.content_type,
+        Some(ContentType::Text)
+    ));
+    assert_eq!(page.last_id.as_deref(), Some("msg_2"));
+    assert!(page.has_more);
+
+    let retrieved = client.retrieve_message("conv_1", "msg_2").await.unwrap();
+    assert_eq!(retrieved.chat_id.as_deref(), Some("chat_1"));
+    let meta = retrieved.message.meta_data.unwrap();
+    assert_eq!(meta.extra["source"], "test");
+
+    let updated = client
+        .update_message(
+            "conv_1",
+            "msg_2",
+            &ChatMessage::text(MessageRole::Assistant, "改过的内容".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.message.content.as_deref(), Some("改过的内容"));
+
+    let deleted = client.delete_message("conv_1", "msg_2").await.unwrap();
+    assert_eq!(deleted.id, "msg_2");
+    let err = client
+        .delete_message("conv_1", "missing")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+
+    let requests = mock.requests();
+    let (query, body) = (&requests[0].query, &requests[0].json());
+    assert_eq!(query["conversation_id"], "conv_1");
+    assert_eq!(
+        body,
+        &json!({"role": "user", "content": "补充背景", "content_type": "text"})
+    );
+    assert_eq!(
+        requests[1].json(),
+        json!({"before_id": "msg_4", "limit": 2})
+    );
+    assert_eq!(requests[2].query["message_id"], "msg_2");
+    // 修改消息不提交 role
+    assert_eq!(
+        requests[3].json(),
+        json!({"content": "改过的内容", "content_type": "text"})
+    );
+}
+
+#[tokio::test]
+async fn test_message_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .create_message(Some(json!({
+            "conversation_id": "conv_1",
+            "role": "assistant",
+            "content": [{"type": "image", "file_id": "file_1"}],
+            "meta_data": {"source": "mcp"}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["message_id"], "msg_new");
+    assert_eq!(structured["message"]["content_type"], "object_string");
+
+    let result = tools
+        .list_messages(Some(json!({
+            "conversation_id": "conv_1",
+            "order": "asc",
+            "after_id": "msg_1",
+            "limit": 100
+        })))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    let items = structured["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1]["id"], "msg_2");
+    assert_eq!(items[1]["content"], "第二条");
+    assert_eq!(items[1]["type"], "answer");
+    assert_eq!(structured["first_id"], "msg_3");
+    assert_eq!(structured["has_more"], true);
+
+    let result = tools
+        .get_message(Some(
+            json!({"conversation_id": "conv_1", "message_id": "missing"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4004);
+
+    let result = tools
+        .update_message(Some(json!({
+            "conversation_id": "conv_1",
+            "message_id": "msg_2",
+            "meta_data": {"reviewed": "yes"}
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+
+    let result = tools
+        .delete_message(Some(
+            json!({"conversation_id": "conv_1", "message_id": "msg_2"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["message_id"], "msg_2");
+
+    let requests = mock.requests();
+    let body = requests[0].json();
+    assert_eq!(body["role"], "assistant");
+    assert_eq!(body["meta_data"], json!({"source": "mcp"}));
+    assert_eq!(
+        requests[1].json(),
+        json!({"order": "asc", "after_id": "msg_1", "limit": 50})
+    );
+    assert_eq!(
+        requests[3].json(),
+        json!({"meta_data": {"reviewed": "yes"}})
+    );
+}
+
+#[tokio::test]
+async fn test_message_tools_validate_arguments() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    for args in [
+        json!({"content": "hi"}),
+        json!({"conversation_id": "conv_1"}),
+        json!({"conversation_id": "conv_1", "content": []}),
+        json!({"conversation_id": "conv_1", "content": "hi", "meta_data": "x"}),
+    ] {
+        assert!(tools.create_message(Some(args)).await.is_err());
+    }
+    assert!(tools
+        .list_messages(Some(json!({
+            "conversation_id": "conv_1",
+            "before_id": "a",
+            "after_id": "b"
+        })))
+        .await
+        .is_err());
+    assert!(tools
+        .list_messages(Some(
+            json!({"conversation_id": "conv_1", "order": "newest"})
+        ))
+        .await
+        .is_err());
+    assert!(tools
+        .update_message(Some(
+            json!({"conversation_id": "conv_1", "message_id": "msg_2"})
+        ))
+        .await
+        .is_err());
+    assert!(tools
+        .get_message(Some(json!({"conversation_id": "conv_1"})))
+        .await
+        .is_err());
+    assert!(mock.requests().is_empty());
+}
@@ -0,0 +1,122 @@
+// 多模态消息测试 - 文本 / 图片 / 文件片段序列化为 object_string 消息
+mod common;
+
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::chat_models::{ChatRequest, ContentPart};
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+
+async fn spawn_mock() -> (CozeTools, MockCoze) {
+    let app = Router::new().route(
+        "/v3/chat",
+        post(|| async {
+            Json(json!({
+                "code": 0,
+                "data": {"id": "chat_1", "conversation_id": "conv_1", "status": "completed"}
+            }))
+        }),
+    );
+    let mock = MockCoze::start(app).await;
+    (mock.tools(), mock)
+}
+
+#[test]
+fn test_object_string_serialization() {
+    let request = ChatRequest::new("bot".to_string(), String::new()).with_content_parts(&[
+        ContentPart::text("这张截图里是什么？"),
+        ContentPart::image_file("file_1"),
+        ContentPart::image_url("https://example.com/a.png"),
+        ContentPart::file("file_2"),
+    ]);
+    let value = serde_json::to_value(&request).unwrap();
+    let messages = value["additional_messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["role"], "user");
+    assert_eq!(messages[0]["content_type"], "object_string");
+    let parts: Value = serde_json::from_str(messages[0]["content"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        parts,
+        json!([
+            {"type": "text", "text": "这张截图里是什么？"},
+            {"type": "image", "file_id": "file_1"},
+            {"type": "image", "file_url": "https://example.com/a.png"},
+            {"type": "file", "file_id": "file_2"}
+        ])
+    );
+}
+
+#[test]
+fn test_content_part_validation() {
+    assert!(ContentPart::text("hi").validate().is_ok());
+    assert!(ContentPart::text("").validate().is_err());
+    assert!(ContentPart::image_url("https://example.com/a.png")
+        .validate()
+        .is_ok());
+    let neither = ContentPart::File {
+        file_id: None,
+        file_url: None,
+    };
+    assert!(neither.validate().is_err());
+    let both = ContentPart::Image {
+        file_id: Some("f".to_string()),
+        file_url: Some("https://example.com/a.png".to_string()),
+    };
+    assert!(both.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_chat_tool_sends_content_parts() {
+    let (tools, mock) = spawn_mock().await;
+    let result = tools
+        .chat(Some(json!({
+            "bot_id": "bot",
+            "message": "总结这份文档",
+            "content": [
+                {"type": "file", "file_id": "file_2"},
+                {"type": "image", "file_url": "https://example.com/a.png"}
+            ]
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+
+    // 仅 message 时仍为纯文本消息
+    tools
+        .chat(Some(json!({"bot_id": "bot", "message": "你好"})))
+        .await
+        .unwrap();
+
+    let captured = mock.bodies();
+    let message = &captured[0]["additional_messages"][0];
+    assert_eq!(message["content_type"], "object_string");
+    let parts: Value = serde_json::from_str(message["content"].as_str().unwrap()).unwrap();
+    assert_eq!(
+        parts,
+        json!([
+            {"type": "text", "text": "总结这份文档"},
+            {"type": "file", "file_id": "file_2"},
+            {"type": "image", "file_url": "https://example.com/a.png"}
+        ])
+    );
+    let message = &captured[1]["additional_messages"][0];
+    assert_eq!(message["content"], "你好");
+    assert!(message.get("content_type").is_none());
+}
+
+#[tokio::test]
+async fn test_invalid_content_rejected() {
+    let (tools, mock) = spawn_mock().await;
+    for args in [
+        json!({"bot_id": "bot"}),
+        json!({"bot_id": "bot", "content": []}),
+        json!({"bot_id": "bot", "content": [{"type": "image"}]}),
+        json!({"bot_id": "bot", "content": [{"type": "video", "file_id": "f"}]}),
+    ] {
+        let result = tools.chat(Some(args.clone())).await.unwrap();
+        assert_eq!(result.is_error, Some(true), "{args}");
+        assert!(tools.chat_stream(Some(args)).await.is_err());
+    }
+    assert!(mock.requests().is_empty());
+}
@@ -0,0 +1,436 @@
+// 自动翻页测试 - Paginator 游标语义，以及各列表工具的 all_pages / limit / cursor，经本地模拟端点验证
+mod common;
+
+use axum::extract::Query;
+use axum::http::Uri;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::error::{ApiError, ApiErrorData};
+use coze_mcp_server::api::pagination::{Page, PageCursor, PageToken, Paginator};
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// 内存中的 0..total，按页码分页
+fn numbers(total: u32, page_size: u32) -> Paginator<'static, u32> {
+    Paginator::new(PageCursor::default(), move |token| async move {
+        let page_num = PageCursor::page_num(token)?;
+        let start = (page_num - 1) * page_size;
+        let items: Vec<u32> = (start..(start + page_size).min(total)).collect();
+        Ok(Page::numbered(
+            items,
+            page_num,
+            page_size,
+            Some(total as u64),
+            None,
+        ))
+    })
+}
+
+fn numbers_from(cursor: PageCursor, total: u32, page_size: u32) -> Paginator<'static, u32> {
+    Paginator::new(cursor, move |token| async move {
+        let page_num = PageCursor::page_num(token)?;
+        let start = (page_num - 1) * page_size;
+        let items: Vec<u32> = (start..(start + page_size).min(total)).collect();
+        Ok(Page::numbered(items, page_num, page_size, None, None))
+    })
+}
+
+#[test]
+fn test_page_cursor_serde() {
+    let cursor = PageCursor::new(PageToken::Page(3));
+    assert_eq!(serde_json::to_value(&cursor).unwrap(), json!({"page": 3}));
+    let cursor = PageCursor {
+        token: Some(PageToken::BeforeId("msg_1".to_string())),
+        skip: 5,
+    };
+    let value = serde_json::to_value(&cursor).unwrap();
+    assert_eq!(value, json!({"before_id": "msg_1", "skip": 5}));
+    assert_eq!(serde_json::from_value::<PageCursor>(value).unwrap(), cursor);
+    assert_eq!(
+        serde_json::from_value::<PageCursor>(json!({})).unwrap(),
+        PageCursor::default()
+    );
+    assert_eq!(
+        serde_json::from_value::<PageCursor>(json!({"skip": 2}))
+            .unwrap()
+            .skip,
+        2
+    );
+}
+
+#[tokio::test]
+async fn test_paginator_walks_all_pages() {
+    let items: Vec<u32> = numbers(45, 20).items().map(Result::unwrap).collect().await;
+    assert_eq!(items, (0..45).collect::<Vec<_>>());
+
+    let all = numbers(45, 20).collect(None, None).await.unwrap();
+    assert_eq!(all.items.len(), 45);
+    assert_eq!(all.next, None);
+    assert_eq!(all.total, Some(45));
+
+    // 只取一页
+    let first = numbers(45, 20).collect(None, Some(1)).await.unwrap();
+    assert_eq!(first.items.len(), 20);
+    assert_eq!(first.next, Some(PageCursor::new(PageToken::Page(2))));
+
+    // total 恰为整页时不再多请求一页
+    let exact = numbers(40, 20).collect(None, None).await.unwrap();
+    assert_eq!(exact.items.len(), 40);
+    assert_eq!(exact.next, None);
+}
+
+#[tokio::test]
+async fn test_limit_cursor_resumes_without_gaps() {
+    let mut seen = Vec::new();
+    let mut cursor = PageCursor::default();
+    // 每次取 7 条，游标在页中截断并带 skip
+    loop {
+        let batch = numbers_from(cursor.clone(), 45, 20)
+            .collect(Some(7), None)
+            .await
+            .unwrap();
+        seen.extend(batch.items);
+        match batch.next {
+            Some(next) => cursor = next,
+            None => break,
+        }
+    }
+    assert_eq!(seen, (0..45).collect::<Vec<_>>());
+
+    let batch = numbers(45, 20).collect(Some(25), None).await.unwrap();
+    assert_eq!(batch.items.len(), 25);
+    assert_eq!(
+        batch.next,
+        Some(PageCursor {
+            token: Some(PageToken::Page(2)),
+            skip: 5
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_paginator_stops_on_error_and_empty_page() {
+    let failing: Paginator<'static, u32> =
+        Paginator::new(PageCursor::default(), |token| async move {
+            match PageCursor::page_num(token)? {
+                1 => Ok(Page {
+                    items: vec![1, 2],
+                    next: Some(PageToken::Page(2)),
+                    total: None,
+                }),
+                _ => Err(ApiError::ServerError(ApiErrorData::new(
+                    "server",
+                    "boom".to_string(),
+                    Some(500),
+                    None,
+                ))),
+            }
+        });
+    let results: Vec<_> = failing.items().collect().await;
+    assert_eq!(results.len(), 3);
+    assert!(results[2].is_err());
+
+    // 接口误报 has_more 的空页
+    let lying: Paginator<'static, u32> =
+        Paginator::new(PageCursor::default(), |token| async move {
+            let page_num = PageCursor::page_num(token)?;
+            Ok(Page {
+                items: if page_num == 1 { vec![1] } else { vec![] },
+                next: Some(PageToken::Page(page_num + 1)),
+                total: None,
+            })
+        });
+    let collected = lying.collect(None, None).await.unwrap();
+    assert_eq!(collected.items, vec![1]);
+    assert_eq!(collected.next, None);
+
+    let wrong: Paginator<'static, u32> = Paginator::new(
+        PageCursor::new(PageToken::BeforeId("x".to_string())),
+        |token| async move {
+            PageCursor::page_num(token)?;
+            Ok(Page {
+                items: vec![],
+                next: None,
+                total: None,
+            })
+        },
+    );
+    assert!(matches!(
+        wrong.collect(None, None).await,
+        Err(ApiError::BadRequest(_))
+    ));
+}
+
+fn page_of(query: &HashMap<String, String>, total: usize) -> (Vec<usize>, usize) {
+    let page: usize = query["page_num"].parse().unwrap();
+    let size: usize = query["page_size"].parse().unwrap();
+    let start = (page - 1) * size;
+    ((start..(start + size).min(total)).collect(), page * size)
+}
+
+async fn handler(
+    uri: Uri,
+    Query(query): Query<HashMap<String, String>>,
+    body: axum::body::Bytes,
+) -> Json<Value> {
+    let path = uri.path().to_string();
+    let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+    Json(match path.as_str() {
+        "/v1/bots" => {
+            let (ids, _) = page_of(&query, 45);
+            let items: Vec<Value> = ids
+                .iter()
+                .map(|i| json!({"id": format!("bot_{i}"), "name": format!("Bot {i}")}))
+                .collect();
+            json!({"code": 0, "msg": "", "data": {"items": items, "total": 45}})
+        }
+        "/v1/datasets" => {
+            let (ids, _) = page_of(&query, 5);
+            let items: Vec<Value> = ids
+                .iter()
+                .map(|i| json!({"dataset_id": format!("ds_{i}"), "name": format!("知识库 {i}")}))
+                .collect();
+            json!({"code": 0, "data": {"dataset_list": items, "total_count": 5}})
+        }
+        "/v1/conversations" => {
+            let (ids, end) = page_of(&query, 30);
+            let items: Vec<Value> = ids
+                .iter()
+                .map(|i| json!({"id": format!("conv_{i}")}))
+                .collect();
+            json!({"code": 0, "data": {"conversations": items, "has_more": end < 30}})
+        }
+        "/v1/workflows" => {
+            let (ids, end) = page_of(&query, 12);
+            let items: Vec<Value> = ids
+                .iter()
+                .map(|i| json!({"workflow_id": format!("wf_{i}"), "workflow_name": format!("流程 {i}")}))
+                .collect();
+            json!({"code": 0, "data": {"items": items, "has_more": end < 12}})
+        }
+        // 消息 m_0..m_9，desc 排序：m_9 最新
+        "/v1/conversation/message/list" => {
+            let limit = body["limit"].as_u64().unwrap_or(50) as usize;
+            let newest = match body["before_id"].as_str() {
+                Some(id) => id.trim_start_matches("m_").parse::<usize>().unwrap(),
+                None => 10,
+            };
+            let ids: Vec<usize> = (0..newest).rev().take(limit).collect();
+            let data: Vec<Value> = ids
+                .iter()
+                .map(|i| {
+                    json!({"id": format!("m_{i}"), "conversation_id": "conv_1", "role": "user", "content": format!("第 {i} 条")})
+                })
+                .collect();
+            json!({
+                "code": 0,
+                "data": data,
+                "first_id": ids.first().map(|i| format!("m_{i}")),
+                "last_id": ids.last().map(|i| format!("m_{i}")),
+                "has_more": ids.last().is_some_and(|i| *i > 0)
+            })
+        }
+        _ => json!({"code": 4000, "msg": "unexpected path"}),
+    })
+}
+
+async fn spawn_mock() -> (CozeTools, MockCoze) {
+    let app = Router::new()
+        .route("/v1/bots", get(handler))
+        .route("/v1/datasets", get(handler))
+        .route("/v1/conversations", get(handler))
+        .route("/v1/workflows", get(handler))
+        .route("/v1/conversation/message/list", post(handler));
+    let mock = MockCoze::start(app).await;
+    (mock.tools(), mock)
+}
+
+fn ids(structured: &Value, key: &str) -> Vec<String> {
+    structured["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item[key].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_list_bots_all_pages_and_cursor() {
+    let (tools, mock) = spawn_mock().await;
+
+    // 默认只取一页，且返回整页（不再只截取前 5 个）
+    let result = tools.list_bots(Some(json!({}))).await.unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["items"].as_array().unwrap().len(), 20);
+    assert_eq!(structured["next_cursor"], json!({"page": 2}));
+    assert_eq!(structured["has_more"], true);
+
+    let result = tools
+        .list_bots(Some(json!({"all_pages": true})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["items"].as_array().unwrap().len(), 45);
+    assert_eq!(structured["total"], 45);
+    assert_eq!(structured["next_cursor"], Value::Null);
+    assert_eq!(structured["has_more"], false);
+
+    let result = tools
+        .list_bots(Some(json!({"all_pages": true, "limit": 25})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    let first = ids(&structured, "bot_id");
+    assert_eq!(first.len(), 25);
+    assert_eq!(structured["next_cursor"], json!({"page": 2, "skip": 5}));
+
+    let result = tools
+        .list_bots(Some(
+            json!({"all_pages": true, "cursor": structured["next_cursor"]}),
+        ))
+        .await
+        .unwrap();
+    let rest = ids(&result.structured_content.unwrap(), "bot_id");
+    assert_eq!(rest.len(), 20);
+    assert_eq!(rest[0], "bot_25");
+    assert_eq!(rest[19], "bot_44");
+
+    let pages: Vec<String> = mock
+        .requests()
+        .iter()
+        .map(|r| r.query["page_num"].clone())
+        .collect();
+    assert_eq!(pages, vec!["1", "1", "2", "3", "1", "2", "2", "3"]);
+}
+
+#[tokio::test]
+async fn test_other_list_tools_paginate() {
+    let (tools, mock) = spawn_mock().await;
+
+    let result = tools
+        .list_knowledge_bases(Some(
+            json!({"name": "知识", "page_size": 2, "all_pages": true}),
+        ))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(
+        ids(&structured, "dataset_id"),
+        vec!["ds_0", "ds_1", "ds_2", "ds_3", "ds_4"]
+    );
+    assert_eq!(structured["total"], 5);
+
+    let result = tools
+        .list_conversations(Some(
+            json!({"bot_id": "bot", "all_pages": true, "limit": 45}),
+        ))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(ids(&structured, "id").len(), 30);
+    assert_eq!(structured["has_more"], false);
+
+    let result = tools
+        .list_workflows(Some(json!({"page_size": 5, "page": 2})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(ids(&structured, "workflow_id")[0], "wf_5");
+    assert_eq!(structured["next_cursor"], json!({"page": 3}));
+
+    let result = tools
+        .list_workflows(Some(json!({"page_size": 5, "cursor": {"page": 3}})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(ids(&structured, "workflow_id"), vec!["wf_10", "wf_11"]);
+    assert_eq!(structured["has_more"], false);
+
+    let requests = mock.requests();
+    assert_eq!(requests[0].query["name"], "知识");
+    // 会话列表使用官方的 page_num 参数
+    let conversation_pages: Vec<&str> = requests
+        .iter()
+        .filter(|r| r.path == "/v1/conversations")
+        .map(|r| r.query["page_num"].as_str())
+        .collect();
+    assert_eq!(conversation_pages, vec!["1", "2"]);
+}
+
+#[tokio::test]
+async fn test_list_messages_follows_id_cursor() {
+    let (tools, mock) = spawn_mock().await;
+
+    let result = tools
+        .list_messages(Some(
+            json!({"conversation_id": "conv_1", "page_size": 4, "all_pages": true}),
+        ))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    let all = ids(&structured, "id");
+    assert_eq!(all.len(), 10);
+    assert_eq!(all[0], "m_9");
+    assert_eq!(all[9], "m_0");
+    assert_eq!(structured["has_more"], false);
+
+    let result = tools
+        .list_messages(Some(json!({"conversation_id": "conv_1", "limit": 3})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(ids(&structured, "id"), vec!["m_9", "m_8", "m_7"]);
+    assert_eq!(structured["next_cursor"], json!({"before_id": "m_7"}));
+
+    let result = tools
+        .list_messages(Some(json!({
+            "conversation_id": "conv_1",
+            "limit": 3,
+            "cursor": structured["next_cursor"]
+        })))
+        .await
+        .unwrap();
+    assert_eq!(
+        ids(&result.structured_content.unwrap(), "id"),
+        vec!["m_6", "m_5", "m_4"]
+    );
+
+    let before_ids: Vec<Value> = mock
+        .bodies()
+        .iter()
+        .map(|body| body["before_id"].clone())
+        .collect();
+    assert_eq!(
+        before_ids,
+        vec![
+            Value::Null,
+            json!("m_6"),
+            json!("m_2"),
+            Value::Null,
+            json!("m_7")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_paging_arguments_are_validated() {
+    let (tools, mock) = spawn_mock().await;
+    assert!(tools
+        .list_bots(Some(json!({"cursor": "page-2"})))
+        .await
+        .is_err());
+    assert!(tools.list_bots(Some(json!({"limit": 0}))).await.is_err());
+
+    // 页码游标不能用于消息列表
+    let result = tools
+        .list_messages(Some(
+            json!({"conversation_id": "conv_1", "cursor": {"page": 2}}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert!(mock.requests().is_empty());
+}
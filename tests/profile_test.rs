@@ -0,0 +1,254 @@
+// 命名 profile 测试 - 配置解析与会话内运行时切换（两个本地模拟端点分别代表 cn / com 区域）
+mod common;
+
+use axum::http::HeaderMap;
+use axum::routing::get;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::tools::context::{SessionAccess, SessionContext};
+use coze_mcp_server::utils::config::{CliArgs, Config, CozeConfig, ServerConfig, TransportType};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+type Seen = Arc<Mutex<Vec<String>>>;
+
+const PROFILES_TOML: &str = r#"
+profile = "cn"
+
+[coze]
+timeout = 12
+default_space_id = "shared_space"
+
+[profiles.cn]
+base_url = "https://api.coze.cn"
+api_token = "pat_cn_token"
+
+[profiles.global]
+base_url = "https://api.coze.com"
+api_token = "pat_global_token"
+default_space_id = "global_space"
+timeout = 60
+"#;
+
+// 每个模拟端点记录 "<区域标签> <Authorization>"
+async fn spawn_region(label: &'static str, seen: Seen) -> String {
+    let app = Router::new().route(
+        "/v1/bots",
+        get(move |headers: HeaderMap| {
+            let seen = seen.clone();
+            async move {
+                let auth = headers
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("")
+                    .to_string();
+                seen.lock().unwrap().push(format!("{label} {auth}"));
+                Json(json!({"code": 0, "msg": "", "data": {"items": [], "total": 0}}))
+            }
+        }),
+    );
+    MockCoze::start(app).await.base_url
+}
+
+#[test]
+fn test_profiles_inherit_from_coze_section() {
+    let config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    let cn = config.profile_config("cn").unwrap();
+    assert_eq!(cn.base_url, "https://api.coze.cn");
+    assert_eq!(cn.api_token, "pat_cn_token");
+    assert_eq!(cn.default_space_id, "shared_space");
+    assert_eq!(cn.timeout, 12);
+
+    let global = config.profile_config("global").unwrap();
+    assert_eq!(global.base_url, "https://api.coze.com");
+    assert_eq!(global.default_space_id, "global_space");
+    assert_eq!(global.timeout, 60);
+
+    let active = config.active_coze().unwrap();
+    assert_eq!(active.api_token, "pat_cn_token");
+    assert_eq!(
+        config.resolved_profiles().keys().collect::<Vec<_>>(),
+        vec!["cn", "global"]
+    );
+
+    // save 输出可被重新加载
+    let reloaded: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+    assert_eq!(reloaded.profile.as_deref(), Some("cn"));
+    assert_eq!(reloaded.profile_config("global").unwrap().timeout, 60);
+}
+
+#[test]
+fn test_profile_selection_precedence() {
+    let mut config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    config
+        .apply_env(|k| (k == "COZE_PROFILE").then(|| "global".to_string()))
+        .unwrap();
+    assert_eq!(config.profile.as_deref(), Some("global"));
+
+    config
+        .apply_cli(&CliArgs::parse(vec![
+            "--profile".to_string(),
+            "cn".to_string(),
+        ]))
+        .unwrap();
+    assert_eq!(
+        config.active_coze().unwrap().base_url,
+        "https://api.coze.cn"
+    );
+
+    config.profile = Some("missing".to_string());
+    let err = config.active_coze().unwrap_err().to_string();
+    assert!(err.contains("missing") && err.contains("global"));
+}
+
+#[test]
+fn test_env_and_cli_override_profile_fields() {
+    // 环境变量覆盖 profile 中的 api_token
+    let mut config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    config
+        .apply_env(|k| (k == "COZE_API_TOKEN").then(|| "pat_from_env".to_string()))
+        .unwrap();
+    let active = config.active_coze().unwrap();
+    assert_eq!(active.api_token, "pat_from_env");
+    assert_eq!(active.base_url, "https://api.coze.cn");
+
+    // 命令行再覆盖环境变量
+    config
+        .apply_cli(&CliArgs::parse(vec![
+            "--api-key".to_string(),
+            "pat_from_cli".to_string(),
+            "--base-url".to_string(),
+            "http://localhost:9".to_string(),
+            "--timeout".to_string(),
+            "7".to_string(),
+        ]))
+        .unwrap();
+    let active = config.active_coze().unwrap();
+    assert_eq!(active.api_token, "pat_from_cli");
+    assert_eq!(active.base_url, "http://localhost:9");
+    assert_eq!(active.timeout, 7);
+
+    // 覆盖只作用于启动时选中的 profile：其他 profile 保留自身的 token 与地址，
+    // 未设置的字段继承配置文件中的 [coze] 而非进程级覆盖
+    let profiles = config.resolved_profiles();
+    assert_eq!(profiles["cn"].api_token, "pat_from_cli");
+    let global = &profiles["global"];
+    assert_eq!(global.api_token, "pat_global_token");
+    assert_eq!(global.base_url, "https://api.coze.com");
+    assert_eq!(global.timeout, 60);
+    assert_eq!(global.retry_attempts, CozeConfig::default().retry_attempts);
+
+    let mut config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    config.profiles.get_mut("global").unwrap().timeout = None;
+    config
+        .apply_cli(&CliArgs::parse(vec![
+            "--timeout".to_string(),
+            "7".to_string(),
+        ]))
+        .unwrap();
+    assert_eq!(config.profile_config("global").unwrap().timeout, 12);
+}
+
+#[tokio::test]
+async fn test_switch_profile_at_runtime() {
+    let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+    let cn_url = spawn_region("cn", seen.clone()).await;
+    let global_url = spawn_region("global", seen.clone()).await;
+
+    let mut config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    config.profiles.get_mut("cn").unwrap().base_url = Some(cn_url);
+    config.profiles.get_mut("global").unwrap().base_url = Some(global_url);
+    let session = SessionContext::with_profiles(
+        config.active_coze().unwrap(),
+        config.profile.clone(),
+        Arc::new(config.resolved_profiles()),
+    );
+
+    let tools = session.tools(None).await.unwrap().unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+
+    // configure 的 key 属于旧租户，切换后应失效
+    session
+        .configure(Some(json!({"api_key": "pat_session_override"})))
+        .await
+        .unwrap();
+    let r = session
+        .profiles_tool(Some(json!({"name": "global"})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(false));
+    assert_eq!(session.active_profile().await.as_deref(), Some("global"));
+    let tools = session.tools(None).await.unwrap().unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+
+    assert_eq!(
+        seen.lock().unwrap().clone(),
+        vec![
+            "cn Bearer pat_cn_token".to_string(),
+            "global Bearer pat_global_token".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_profiles_tool_lists_without_tokens() {
+    let config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    let session = SessionContext::with_profiles(
+        config.active_coze().unwrap(),
+        config.profile.clone(),
+        Arc::new(config.resolved_profiles()),
+    );
+
+    let r = session.profiles_tool(None).await.unwrap();
+    let listed = r.structured_content.unwrap();
+    assert_eq!(listed["active"], "cn");
+    assert_eq!(listed["profiles"][1]["name"], "global");
+    assert_eq!(listed["profiles"][1]["has_token"], true);
+    assert!(!listed.to_string().contains("pat_global_token"));
+
+    let r = session
+        .profiles_tool(Some(json!({"name": "nope"})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(true));
+    assert_eq!(session.active_profile().await.as_deref(), Some("cn"));
+}
+
+#[tokio::test]
+async fn test_remote_sessions_cannot_switch_profiles_by_default() {
+    let config: Config = toml::from_str(PROFILES_TOML).unwrap();
+    let mut server = ServerConfig {
+        transport: TransportType::Sse,
+        ..ServerConfig::default()
+    };
+    let remote = SessionContext::with_profiles(
+        config.active_coze().unwrap(),
+        config.profile.clone(),
+        Arc::new(config.resolved_profiles()),
+    )
+    .with_access(SessionAccess::for_server(&server));
+
+    let r = remote
+        .profiles_tool(Some(json!({"name": "global"})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(true));
+    assert!(remote.switch_profile("global").await.is_err());
+    assert_eq!(remote.active_profile().await.as_deref(), Some("cn"));
+
+    // 仍可列出 profile，但不展示是否配置了 token
+    let listed = remote.profiles_tool(None).await.unwrap();
+    let listed = listed.structured_content.unwrap();
+    assert_eq!(listed["profiles"][1]["name"], "global");
+    assert!(listed["profiles"][1].get("has_token").is_none());
+
+    server.allow_profile_switch = true;
+    let allowed = SessionContext::with_profiles(
+        config.active_coze().unwrap(),
+        config.profile.clone(),
+        Arc::new(config.resolved_profiles()),
+    )
+    .with_access(SessionAccess::for_server(&server));
+    allowed.switch_profile("global").await.unwrap();
+    assert_eq!(allowed.active_profile().await.as_deref(), Some("global"));
+}
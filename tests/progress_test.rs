@@ -0,0 +1,115 @@
+// 流式聊天进度通知测试 - 经内存管道连接 MCP 客户端，验证每段增量都以 notifications/progress 送达
+mod common;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::post;
+use axum::Router;
+use common::MockCoze;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use coze_mcp_server::tools::progress::ProgressNotifier;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, ProgressNotificationParam, ServerCapabilities, ServerInfo,
+};
+use rmcp::service::{NotificationContext, RequestContext, RoleClient, RoleServer};
+use rmcp::{ClientHandler, ErrorData as McpError, ServerHandler, ServiceExt};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+const STREAM: &str =
+    "event: conversation.chat.created\ndata: {\"id\":\"chat\",\"conversation_id\":\"conv\"}\n\n\
+event: conversation.message.delta\ndata: {\"delta\":{\"content\":\"你好\"}}\n\n\
+event: conversation.message.delta\ndata: {\"delta\":{\"content\":\"，世界\"}}\n\n\
+event: conversation.chat.completed\ndata: {\"id\":\"chat\",\"conversation_id\":\"conv\"}\n\n";
+
+struct StreamServer {
+    tools: Arc<CozeTools>,
+}
+
+impl ServerHandler for StreamServer {
+    async fn call_tool(
+        &self,
+        params: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let args = params.arguments.map(serde_json::Value::Object);
+        self.tools
+            .chat_stream_with_context(
+                args,
+                ProgressNotifier::from_context(&context),
+                context.ct.clone(),
+            )
+            .await
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..ServerInfo::default()
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+    updates: Arc<Mutex<Vec<ProgressNotificationParam>>>,
+}
+
+impl ClientHandler for Recorder {
+    async fn on_progress(
+        &self,
+        params: ProgressNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        self.updates.lock().unwrap().push(params);
+    }
+}
+
+#[tokio::test]
+async fn test_chat_stream_forwards_deltas_as_progress() {
+    let app = Router::new().route(
+        "/v3/chat",
+        post(|| async { ([(CONTENT_TYPE, "text/event-stream")], STREAM) }),
+    );
+    let mock = MockCoze::start(app).await;
+    let client = CozeApiClient::new(mock.base_url, "pat_test".to_string()).unwrap();
+    let server = StreamServer {
+        tools: Arc::new(CozeTools::new(Arc::new(client), "space".to_string())),
+    };
+
+    let (server_io, client_io) = tokio::io::duplex(64 * 1024);
+    tokio::spawn(async move {
+        let running = server.serve(server_io).await.unwrap();
+        let _ = running.waiting().await;
+    });
+    let recorder = Recorder::default();
+    let mcp = recorder.clone().serve(client_io).await.unwrap();
+
+    let result = mcp
+        .call_tool(CallToolRequestParam {
+            name: "chat_stream".into(),
+            arguments: json!({"bot_id": "bot", "message": "hi"})
+                .as_object()
+                .cloned(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["content"], "你好，世界");
+
+    // 通知先于响应发出，但客户端可能异步处理，稍等片刻
+    for _ in 0..100 {
+        if recorder.updates.lock().unwrap().len() >= 2 {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let updates = recorder.updates.lock().unwrap().clone();
+    let messages: Vec<_> = updates.iter().map(|u| u.message.clone().unwrap()).collect();
+    assert_eq!(messages, ["你好", "，世界"]);
+    assert_eq!(updates[0].progress, 1.0);
+    assert_eq!(updates[1].progress, 2.0);
+    assert_eq!(updates[0].progress_token, updates[1].progress_token);
+
+    mcp.cancel().await.unwrap();
+}
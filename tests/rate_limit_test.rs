@@ -0,0 +1,202 @@
+// 客户端限流测试 - 令牌桶节流、并发上限排队、分组隔离与跨会话共享
+mod common;
+
+use axum::routing::get;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::endpoints::endpoint_group;
+use coze_mcp_server::api::rate_limit::RateLimiter;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::models::{CozeApiRequest, HttpMethod};
+use coze_mcp_server::tools::context::SessionContext;
+use coze_mcp_server::utils::config::{CozeConfig, RateLimitConfig};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn limits(entries: &[(&str, RateLimitConfig)]) -> BTreeMap<String, RateLimitConfig> {
+    entries
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.clone()))
+        .collect()
+}
+
+fn rate(rps: f64, burst: u32) -> RateLimitConfig {
+    RateLimitConfig {
+        requests_per_second: Some(rps),
+        burst: Some(burst),
+        max_concurrency: None,
+    }
+}
+
+#[test]
+fn test_endpoint_group_mapping() {
+    assert_eq!(endpoint_group("/v3/chat"), "chat");
+    assert_eq!(endpoint_group("/v3/chat/message/list"), "chat");
+    assert_eq!(endpoint_group("/v1/conversations"), "conversation");
+    assert_eq!(endpoint_group("/v1/datasets"), "datasets_v1");
+    assert_eq!(
+        endpoint_group("/open_api/knowledge/document/create"),
+        "datasets_cn"
+    );
+    assert_eq!(endpoint_group("/v1/bots"), "bots");
+    assert_eq!(endpoint_group("/v1/unknown"), "default");
+}
+
+#[test]
+fn test_invalid_config_rejected() {
+    assert!(RateLimiter::new(&limits(&[("chats", rate(1.0, 1))])).is_err());
+    assert!(RateLimiter::new(&limits(&[("chat", rate(0.0, 1))])).is_err());
+    let zero = RateLimitConfig {
+        max_concurrency: Some(0),
+        ..RateLimitConfig::default()
+    };
+    assert!(RateLimiter::new(&limits(&[("bots", zero)])).is_err());
+    assert!(CozeApiClient::from_config(&CozeConfig {
+        rate_limits: limits(&[("nope", rate(1.0, 1))]),
+        ..CozeConfig::default()
+    })
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_token_bucket_paces_requests() {
+    let limiter = RateLimiter::new(&limits(&[("chat", rate(10.0, 2))])).unwrap();
+    let started = Instant::now();
+    // 前 2 个为突发额度，其后每 100ms 一个
+    for _ in 0..6 {
+        limiter.acquire("chat").await;
+    }
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(380), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(1000), "{elapsed:?}");
+
+    // 其他分组不受影响
+    let started = Instant::now();
+    for _ in 0..20 {
+        limiter.acquire("bots").await;
+    }
+    assert!(started.elapsed() < Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn test_default_applies_per_group() {
+    let limiter = RateLimiter::new(&limits(&[("default", rate(5.0, 1))])).unwrap();
+    let started = Instant::now();
+    limiter.acquire("bots").await;
+    limiter.acquire("chat").await;
+    assert!(started.elapsed() < Duration::from_millis(50));
+    limiter.acquire("bots").await;
+    assert!(started.elapsed() >= Duration::from_millis(150));
+}
+
+fn max_concurrency(n: usize) -> BTreeMap<String, RateLimitConfig> {
+    limits(&[(
+        "bots",
+        RateLimitConfig {
+            max_concurrency: Some(n),
+            ..RateLimitConfig::default()
+        },
+    )])
+}
+
+// 每个请求耗时 100ms，返回的计数器记录服务端观察到的最大并发数
+async fn spawn_tracked() -> (MockCoze, Arc<AtomicUsize>) {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let (f, p) = (in_flight.clone(), peak.clone());
+    let app = Router::new().route(
+        "/v1/bots",
+        get(move || {
+            let (f, p) = (f.clone(), p.clone());
+            async move {
+                let now = f.fetch_add(1, Ordering::SeqCst) + 1;
+                p.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                f.fetch_sub(1, Ordering::SeqCst);
+                Json(json!({"code": 0, "msg": "", "data": {"items": [], "total": 0}}))
+            }
+        }),
+    );
+    (MockCoze::start(app).await, peak)
+}
+
+#[tokio::test]
+async fn test_concurrency_cap_queues_requests() {
+    let (mock, peak) = spawn_tracked().await;
+
+    let client = CozeApiClient::from_config(&CozeConfig {
+        base_url: mock.base_url,
+        api_token: "pat_test".to_string(),
+        rate_limits: max_concurrency(2),
+        ..CozeConfig::default()
+    })
+    .unwrap();
+
+    let calls = (0..6).map(|_| {
+        let client = client.clone();
+        async move {
+            client
+                .execute_request(CozeApiRequest {
+                    endpoint: "/v1/bots".to_string(),
+                    method: HttpMethod::Get,
+                    headers: HashMap::new(),
+                    params: HashMap::new(),
+                    body: None,
+                })
+                .await
+        }
+    });
+    let results = futures::future::join_all(calls).await;
+    assert!(results
+        .iter()
+        .all(|r| r.as_ref().unwrap().status_code == 200));
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_sessions_share_process_limiter() {
+    let (mock, peak) = spawn_tracked().await;
+    let config = CozeConfig {
+        base_url: mock.base_url.clone(),
+        rate_limits: max_concurrency(1),
+        ..CozeConfig::default()
+    };
+    let session_for = |token: &str, limiter: Option<Arc<RateLimiter>>| {
+        let session = SessionContext::new(CozeConfig {
+            api_token: token.to_string(),
+            ..config.clone()
+        });
+        match limiter {
+            Some(limiter) => session.with_limiter(limiter),
+            None => session,
+        }
+    };
+    let list_concurrently = |sessions: Vec<SessionContext>| async move {
+        let calls = sessions.iter().map(|session| async move {
+            let tools = session.tools(None).await.unwrap().unwrap();
+            tools.list_bots(Some(json!({}))).await.unwrap()
+        });
+        futures::future::join_all(calls).await;
+    };
+
+    // 共享限流器：两个会话（不同 token）合计只有 1 个并发名额
+    let limiter = Arc::new(RateLimiter::new(&config.rate_limits).unwrap());
+    list_concurrently(vec![
+        session_for("pat_alice", Some(limiter.clone())),
+        session_for("pat_bob", Some(limiter)),
+    ])
+    .await;
+    assert_eq!(peak.swap(0, Ordering::SeqCst), 1);
+
+    // 未共享时各会话的客户端分别计数
+    list_concurrently(vec![
+        session_for("pat_alice", None),
+        session_for("pat_bob", None),
+    ])
+    .await;
+    assert_eq!(peak.load(Ordering::SeqCst), 2);
+    assert_eq!(mock.requests().len(), 4);
+}
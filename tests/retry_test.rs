@@ -0,0 +1,236 @@
+// 重试策略测试 - 本地模拟端点按脚本依次返回状态码或包裹内的 4029，记录命中次数
+mod common;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::{any, get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::retry::{self, RetryMode};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::models::{CozeApiRequest, HttpMethod};
+use coze_mcp_server::utils::config::CozeConfig;
+use reqwest::Method;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+// (状态码, Retry-After)
+type Reply = (u16, Option<&'static str>);
+
+#[derive(Clone, Default)]
+struct Script {
+    // 脚本耗尽后返回 200
+    replies: Arc<Mutex<VecDeque<Reply>>>,
+    hits: Arc<Mutex<usize>>,
+}
+
+async fn scripted(State(script): State<Script>) -> impl IntoResponse {
+    *script.hits.lock().unwrap() += 1;
+    let (status, retry_after) = script
+        .replies
+        .lock()
+        .unwrap()
+        .pop_front()
+        .unwrap_or((200, None));
+    let mut headers = HeaderMap::new();
+    if let Some(v) = retry_after {
+        headers.insert("retry-after", HeaderValue::from_static(v));
+    }
+    (
+        StatusCode::from_u16(status).unwrap(),
+        headers,
+        format!(r#"{{"code":0,"msg":"status {status}"}}"#),
+    )
+}
+
+async fn spawn_scripted(replies: &[Reply]) -> (CozeApiClient, Script) {
+    spawn_with_retries(replies, 3).await
+}
+
+async fn spawn_with_retries(replies: &[Reply], retry_attempts: u32) -> (CozeApiClient, Script) {
+    let script = Script::default();
+    script
+        .replies
+        .lock()
+        .unwrap()
+        .extend(replies.iter().copied());
+    let app = Router::new()
+        .route("/api", any(scripted))
+        .with_state(script.clone());
+    let mock = MockCoze::start(app).await;
+    let client = CozeApiClient::from_config(&CozeConfig {
+        base_url: mock.base_url,
+        api_token: "pat_test".to_string(),
+        retry_attempts,
+        ..CozeConfig::default()
+    })
+    .unwrap();
+    (client, script)
+}
+
+fn request(method: HttpMethod) -> CozeApiRequest {
+    CozeApiRequest {
+        endpoint: "/api".to_string(),
+        method,
+        headers: HashMap::new(),
+        params: HashMap::new(),
+        body: None,
+    }
+}
+
+#[tokio::test]
+async fn test_get_retries_transient_failures() {
+    let (client, script) = spawn_scripted(&[(503, None), (502, None)]).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 200);
+    assert_eq!(*script.hits.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_retry_attempts() {
+    let (client, script) =
+        spawn_with_retries(&[(500, None), (500, None), (500, None), (500, None)], 2).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 500);
+    assert_eq!(*script.hits.lock().unwrap(), 3);
+}
+
+#[tokio::test]
+async fn test_non_idempotent_and_client_errors_not_retried() {
+    let (client, script) = spawn_scripted(&[(503, None)]).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Post))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 503);
+    assert_eq!(*script.hits.lock().unwrap(), 1);
+
+    let (client, script) = spawn_scripted(&[(400, None)]).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 400);
+    assert_eq!(*script.hits.lock().unwrap(), 1);
+
+    let (client, script) = spawn_with_retries(&[(503, None)], 0).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 503);
+    assert_eq!(*script.hits.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn test_rate_limit_honors_retry_after() {
+    let (client, script) = spawn_scripted(&[(429, Some("1"))]).await;
+    let started = Instant::now();
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 200);
+    assert!(started.elapsed() >= Duration::from_secs(1));
+    assert_eq!(*script.hits.lock().unwrap(), 2);
+
+    // 超出上限的 Retry-After 不等待，直接返回限流响应
+    let (client, script) = spawn_scripted(&[(429, Some("3600"))]).await;
+    let resp = client
+        .execute_request(request(HttpMethod::Get))
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 429);
+    assert_eq!(*script.hits.lock().unwrap(), 1);
+}
+
+// 前 `limited` 次在 HTTP 200 包裹中返回 4029，之后成功
+async fn spawn_envelope_limited(limited: usize, retry_attempts: u32) -> (CozeApiClient, MockCoze) {
+    let hits = Arc::new(Mutex::new(0));
+    let reply = move || {
+        let hits = hits.clone();
+        async move {
+            let mut hits = hits.lock().unwrap();
+            *hits += 1;
+            if *hits <= limited {
+                Json(json!({"code": 4029, "msg": "too many requests"}))
+            } else {
+                Json(json!({"code": 0, "msg": "", "data": {"id": "file_1", "bytes": 11}}))
+            }
+        }
+    };
+    let app = Router::new()
+        .route("/v1/files/retrieve", get(reply.clone()))
+        .route("/v3/chat", post(reply));
+    let mock = MockCoze::start(app).await;
+    let client = CozeApiClient::from_config(&CozeConfig {
+        base_url: mock.base_url.clone(),
+        api_token: "pat_test".to_string(),
+        retry_attempts,
+        ..CozeConfig::default()
+    })
+    .unwrap();
+    (client, mock)
+}
+
+#[tokio::test]
+async fn test_envelope_rate_limit_is_retried() {
+    let (client, mock) = spawn_envelope_limited(2, 3).await;
+    let file = client.retrieve_file("file_1").await.unwrap();
+    assert_eq!(file.id, "file_1");
+    assert_eq!(mock.requests().len(), 3);
+
+    // 重试耗尽后仍解码为限流错误
+    let (client, mock) = spawn_envelope_limited(5, 1).await;
+    let err = client.retrieve_file("file_1").await.unwrap_err();
+    assert!(matches!(err, ApiError::RateLimitExceeded(_)));
+    assert_eq!(mock.requests().len(), 2);
+
+    // 不可重试的请求不重发
+    let (client, mock) = spawn_envelope_limited(1, 3).await;
+    let resp = client
+        .execute_request(CozeApiRequest {
+            endpoint: "/v3/chat".to_string(),
+            ..request(HttpMethod::Post)
+        })
+        .await
+        .unwrap();
+    assert_eq!(resp.status_code, 200);
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn test_retry_mode_and_helpers() {
+    assert!(RetryMode::Auto.allows(&Method::GET));
+    assert!(RetryMode::Auto.allows(&Method::DELETE));
+    assert!(!RetryMode::Auto.allows(&Method::POST));
+    assert!(RetryMode::Safe.allows(&Method::POST));
+    assert!(!RetryMode::Never.allows(&Method::GET));
+
+    for attempt in 0..20 {
+        let delay = retry::backoff_delay(attempt);
+        let ceiling = retry::BASE_DELAY
+            .saturating_mul(1u32 << attempt.min(16))
+            .min(retry::MAX_DELAY);
+        assert!(delay >= ceiling / 2 && delay <= ceiling);
+    }
+
+    let mut headers = reqwest::header::HeaderMap::new();
+    assert_eq!(retry::retry_after(&headers), None);
+    headers.insert("retry-after", "7".parse().unwrap());
+    assert_eq!(retry::retry_after(&headers), Some(Duration::from_secs(7)));
+    let later = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(30));
+    headers.insert("retry-after", later.parse().unwrap());
+    let wait = retry::retry_after(&headers).unwrap();
+    assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+}
@@ -0,0 +1,155 @@
+// 会话级凭证测试 - 使用本地模拟 Coze 端点记录每次请求携带的 Authorization 头
+mod common;
+
+use axum::http::{HeaderMap, HeaderValue};
+use axum::routing::get;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::tools::context::{bearer_token, SessionAccess, SessionContext};
+use coze_mcp_server::utils::config::{CozeConfig, ServerConfig, TransportType};
+use serde_json::json;
+
+async fn spawn_mock_coze() -> MockCoze {
+    MockCoze::start(Router::new().route(
+        "/v1/bots",
+        get(|| async { Json(json!({"code": 0, "msg": "", "data": {"items": [], "total": 0}})) }),
+    ))
+    .await
+}
+
+fn authorizations(mock: &MockCoze) -> Vec<String> {
+    mock.requests()
+        .iter()
+        .map(|r| r.header("authorization").unwrap_or_default())
+        .collect()
+}
+
+fn session_for(base_url: &str) -> SessionContext {
+    SessionContext::new(CozeConfig {
+        base_url: base_url.to_string(),
+        default_space_id: "space".to_string(),
+        ..CozeConfig::default()
+    })
+}
+
+#[tokio::test]
+async fn test_sessions_never_share_tokens() {
+    let mock = spawn_mock_coze().await;
+    let alice = session_for(&mock.base_url);
+    let bob = session_for(&mock.base_url);
+
+    let r = alice
+        .configure(Some(json!({"api_key": "pat_alice_token"})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(false));
+    let r = bob
+        .configure(Some(json!({"api_key": "pat_bob_token"})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(false));
+
+    let alice_tools = alice.tools(None).await.unwrap().expect("alice tools");
+    let bob_tools = bob.tools(None).await.unwrap().expect("bob tools");
+    alice_tools.list_bots(Some(json!({}))).await.unwrap();
+    bob_tools.list_bots(Some(json!({}))).await.unwrap();
+    alice_tools.list_bots(Some(json!({}))).await.unwrap();
+
+    let seen = authorizations(&mock);
+    assert_eq!(
+        seen,
+        vec![
+            "Bearer pat_alice_token".to_string(),
+            "Bearer pat_bob_token".to_string(),
+            "Bearer pat_alice_token".to_string(),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_header_token_takes_precedence() {
+    let mock = spawn_mock_coze().await;
+    let session = session_for(&mock.base_url);
+    session
+        .configure(Some(json!({"api_key": "pat_configured"})))
+        .await
+        .unwrap();
+
+    let tools = session
+        .tools(Some("pat_from_header"))
+        .await
+        .unwrap()
+        .unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+    let tools = session.tools(None).await.unwrap().unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+
+    let seen = authorizations(&mock);
+    assert_eq!(seen[0], "Bearer pat_from_header");
+    assert_eq!(seen[1], "Bearer pat_configured");
+}
+
+#[tokio::test]
+async fn test_unconfigured_session_has_no_tools() {
+    let session = session_for("http://localhost");
+    assert!(session.tools(None).await.unwrap().is_none());
+
+    // 非法 key 不会被接受
+    let r = session
+        .configure(Some(json!({"api_key": "   "})))
+        .await
+        .unwrap();
+    assert_eq!(r.is_error, Some(true));
+    assert!(session.tools(None).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_remote_sessions_do_not_inherit_operator_token() {
+    let mock = spawn_mock_coze().await;
+    let operator = CozeConfig {
+        base_url: mock.base_url.clone(),
+        api_token: "pat_operator".to_string(),
+        ..CozeConfig::default()
+    };
+    let mut server = ServerConfig {
+        transport: TransportType::Http,
+        ..ServerConfig::default()
+    };
+
+    // 远程会话没有自己的凭证时不回退到运维者的 token
+    let remote =
+        SessionContext::new(operator.clone()).with_access(SessionAccess::for_server(&server));
+    assert!(remote.tools(None).await.unwrap().is_none());
+    let tools = remote.tools(Some("pat_caller")).await.unwrap().unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+
+    // 运维者显式开启后才共享进程级 token；stdio 始终使用
+    server.share_operator_token = true;
+    let shared =
+        SessionContext::new(operator.clone()).with_access(SessionAccess::for_server(&server));
+    let tools = shared.tools(None).await.unwrap().unwrap();
+    tools.list_bots(Some(json!({}))).await.unwrap();
+    let stdio = SessionContext::new(operator)
+        .with_access(SessionAccess::for_server(&ServerConfig::default()));
+    assert!(stdio.tools(None).await.unwrap().is_some());
+
+    assert_eq!(
+        authorizations(&mock),
+        vec![
+            "Bearer pat_caller".to_string(),
+            "Bearer pat_operator".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_bearer_token_parsing() {
+    let mut headers = HeaderMap::new();
+    assert_eq!(bearer_token(&headers), None);
+    headers.insert("authorization", HeaderValue::from_static("Bearer pat_abc"));
+    assert_eq!(bearer_token(&headers), Some("pat_abc".to_string()));
+    headers.insert("authorization", HeaderValue::from_static("Basic xyz"));
+    assert_eq!(bearer_token(&headers), None);
+    headers.insert("authorization", HeaderValue::from_static("Bearer "));
+    assert_eq!(bearer_token(&headers), None);
+}
@@ -0,0 +1,185 @@
+// SSE 解码测试 - 任意字节切分下事件一致，多行 data / event / id / retry 字段与批量事件
+mod common;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::post;
+use axum::Router;
+use common::MockCoze;
+use coze_mcp_server::api::chat_models::{ChatRequest, StreamEventType};
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::sse::{SseDecoder, SseEvent};
+use coze_mcp_server::api::CozeApiClient;
+use futures::StreamExt;
+use rand::Rng;
+use std::time::Duration;
+
+const STREAM: &str = "\u{feff}: keep-alive\r\n\
+event:conversation.chat.created\r\n\
+id: 1\r\n\
+data:{\"id\":\"chat\",\"conversation_id\":\"conv\"}\r\n\
+\r\n\
+event: conversation.message.delta\n\
+data: {\"delta\":{\"content\":\"你好，\"}}\n\
+\n\
+event: conversation.message.delta\r\
+retry: 3000\r\
+data: {\"delta\":\"first\r\
+data: line\"}\r\
+\r\
+event: ignored-without-data\n\
+\n\
+id\n\
+data\n\
+\n\
+event:done\n\
+data:\"[DONE]\"\n\
+\n";
+
+fn decode_all(chunks: &[&[u8]]) -> Vec<SseEvent> {
+    let mut decoder = SseDecoder::new();
+    chunks.iter().flat_map(|c| decoder.push(c)).collect()
+}
+
+fn expected() -> Vec<SseEvent> {
+    vec![
+        SseEvent {
+            event: Some("conversation.chat.created".to_string()),
+            data: r#"{"id":"chat","conversation_id":"conv"}"#.to_string(),
+            id: Some("1".to_string()),
+            retry: None,
+        },
+        SseEvent {
+            event: Some("conversation.message.delta".to_string()),
+            data: r#"{"delta":{"content":"你好，"}}"#.to_string(),
+            id: Some("1".to_string()),
+            retry: None,
+        },
+        SseEvent {
+            event: Some("conversation.message.delta".to_string()),
+            data: "{\"delta\":\"first\nline\"}".to_string(),
+            id: Some("1".to_string()),
+            retry: Some(Duration::from_millis(3000)),
+        },
+        SseEvent {
+            event: None,
+            data: String::new(),
+            id: Some(String::new()),
+            retry: None,
+        },
+        SseEvent {
+            event: Some("done".to_string()),
+            data: r#""[DONE]""#.to_string(),
+            id: Some(String::new()),
+            retry: None,
+        },
+    ]
+}
+
+#[test]
+fn test_whole_stream_in_one_chunk() {
+    assert_eq!(decode_all(&[STREAM.as_bytes()]), expected());
+}
+
+#[test]
+fn test_every_single_split_point() {
+    let bytes = STREAM.as_bytes();
+    for at in 0..=bytes.len() {
+        let (a, b) = bytes.split_at(at);
+        assert_eq!(decode_all(&[a, b]), expected(), "split at {at}");
+    }
+}
+
+#[test]
+fn test_byte_at_a_time() {
+    let chunks: Vec<&[u8]> = STREAM.as_bytes().chunks(1).collect();
+    assert_eq!(decode_all(&chunks), expected());
+}
+
+#[test]
+fn test_random_splits() {
+    let bytes = STREAM.as_bytes();
+    let mut rng = rand::rng();
+    for _ in 0..500 {
+        let mut chunks = Vec::new();
+        let mut rest = bytes;
+        while !rest.is_empty() {
+            let n = rng.random_range(0..=rest.len().min(16));
+            let (head, tail) = rest.split_at(n);
+            chunks.push(head);
+            rest = tail;
+        }
+        assert_eq!(decode_all(&chunks), expected());
+    }
+}
+
+#[test]
+fn test_incomplete_event_is_held() {
+    let mut decoder = SseDecoder::new();
+    assert!(decoder.push(b"data: a\ndata: b").is_empty());
+    assert!(decoder.push(b"\n").is_empty());
+    let events = decoder.push(b"\n");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].data, "a\nb");
+    assert_eq!(events[0].event, None);
+}
+
+async fn spawn_stream(body: &'static str) -> CozeApiClient {
+    let app = Router::new().route(
+        "/v3/chat",
+        post(move || async move { ([(CONTENT_TYPE, "text/event-stream")], body) }),
+    );
+    let mock = MockCoze::start(app).await;
+    CozeApiClient::new(mock.base_url, "pat_test".to_string()).unwrap()
+}
+
+#[tokio::test]
+async fn test_chat_stream_yields_batched_events() {
+    let client = spawn_stream(STREAM).await;
+    let stream = client
+        .chat_stream(ChatRequest::new("bot".to_string(), "hi".to_string()))
+        .await
+        .unwrap();
+    let events: Vec<_> = stream.collect().await;
+    // 空 data 的事件被跳过，其余 4 个按顺序到达
+    assert_eq!(events.len(), 4, "{events:?}");
+    let first = events[0].as_ref().unwrap();
+    assert!(matches!(
+        first.event,
+        StreamEventType::ConversationChatCreated
+    ));
+    assert_eq!(first.conversation_id.as_deref(), Some("conv"));
+    let delta = events[1].as_ref().unwrap();
+    assert!(matches!(
+        delta.event,
+        StreamEventType::ConversationMessageDelta
+    ));
+    assert_eq!(
+        delta.delta.as_ref().unwrap().content.as_deref(),
+        Some("你好，")
+    );
+    // 第三个事件的多行 data 拼接后不是合法 JSON，解析失败但不影响后续事件
+    assert!(events[2].is_err());
+    assert!(matches!(
+        events[3].as_ref().unwrap().event,
+        StreamEventType::Done
+    ));
+}
+
+#[tokio::test]
+async fn test_chat_stream_error_event() {
+    let client = spawn_stream(
+        "event: conversation.message.delta\ndata: {\"delta\":{\"content\":\"a\"}}\n\n\
+event: error\ndata: {\"code\":4029,\"msg\":\"slow down\"}\n\n",
+    )
+    .await;
+    let stream = client
+        .chat_stream(ChatRequest::new("bot".to_string(), "hi".to_string()))
+        .await
+        .unwrap();
+    let events: Vec<_> = stream.collect().await;
+    assert_eq!(events.len(), 2);
+    assert!(events[0].is_ok());
+    let err = events[1].as_ref().unwrap_err();
+    assert!(matches!(err, ApiError::RateLimitExceeded(_)));
+    assert_eq!(err.code(), Some(4029));
+}
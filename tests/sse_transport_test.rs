@@ -0,0 +1,152 @@
+// 旧版 HTTP+SSE 传输测试 - 验证会话路由、连接数上限与 CORS 来源限制
+use coze_mcp_server::transport::{sse_router, SSE_PATH};
+use coze_mcp_server::utils::config::ServerConfig;
+use rmcp::model::{ServerCapabilities, ServerInfo};
+use rmcp::ServerHandler;
+use serde_json::json;
+use std::time::Duration;
+
+#[derive(Clone)]
+struct EchoServer;
+
+impl ServerHandler for EchoServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+}
+
+async fn spawn_server(max_connections: u32, cors_origins: Vec<String>) -> String {
+    let config = ServerConfig {
+        listen_addr: "127.0.0.1:0".to_string(),
+        max_connections,
+        cors_origins,
+        ..Default::default()
+    };
+    let listener = tokio::net::TcpListener::bind(&config.listen_addr)
+        .await
+        .unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (router, _ct) = sse_router(&config, || EchoServer).unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    format!("http://{addr}")
+}
+
+async fn open_stream(client: &reqwest::Client, base: &str) -> reqwest::Response {
+    client
+        .get(format!("{base}{SSE_PATH}"))
+        .header("Accept", "text/event-stream")
+        .send()
+        .await
+        .expect("sse request")
+}
+
+#[tokio::test]
+async fn test_sse_endpoint_event_routes_to_session() {
+    let base = spawn_server(10, vec!["*".to_string()]).await;
+    let client = reqwest::Client::new();
+
+    let mut stream = open_stream(&client, &base).await;
+    assert!(stream.status().is_success());
+    let first = stream.chunk().await.unwrap().expect("endpoint event");
+    let text = String::from_utf8_lossy(&first).to_string();
+    assert!(text.contains("event: endpoint"));
+    let endpoint = text
+        .lines()
+        .find_map(|l| l.strip_prefix("data: "))
+        .expect("endpoint data")
+        .trim()
+        .to_string();
+    assert!(endpoint.contains("sessionId="));
+
+    let resp = client
+        .post(format!("{base}{endpoint}"))
+        .json(&json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "test-client", "version": "0.0.1" }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::ACCEPTED);
+
+    let reply = tokio::time::timeout(Duration::from_secs(5), stream.chunk())
+        .await
+        .expect("initialize reply")
+        .unwrap()
+        .expect("reply chunk");
+    assert!(String::from_utf8_lossy(&reply).contains("protocolVersion"));
+
+    // 未知会话返回 404
+    let resp = client
+        .post(format!("{base}/message?sessionId=unknown"))
+        .json(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_sse_max_connections_enforced() {
+    let base = spawn_server(1, vec!["*".to_string()]).await;
+    let client = reqwest::Client::new();
+
+    let mut first = open_stream(&client, &base).await;
+    assert!(first.status().is_success());
+    let _ = first.chunk().await.unwrap();
+
+    let second = open_stream(&client, &base).await;
+    assert_eq!(second.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+
+    // 关闭第一个连接后名额被归还
+    drop(first);
+    let mut reopened = false;
+    for _ in 0..20 {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        if open_stream(&client, &base).await.status().is_success() {
+            reopened = true;
+            break;
+        }
+    }
+    assert!(reopened);
+}
+
+#[tokio::test]
+async fn test_sse_rejects_disallowed_origin() {
+    let base = spawn_server(10, vec!["https://allowed.example".to_string()]).await;
+    let client = reqwest::Client::new();
+
+    let denied = client
+        .get(format!("{base}{SSE_PATH}"))
+        .header("Origin", "https://evil.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(denied.status(), reqwest::StatusCode::FORBIDDEN);
+
+    let allowed = client
+        .get(format!("{base}{SSE_PATH}"))
+        .header("Origin", "https://allowed.example")
+        .send()
+        .await
+        .unwrap();
+    assert!(allowed.status().is_success());
+    assert_eq!(
+        allowed
+            .headers()
+            .get("access-control-allow-origin")
+            .and_then(|v| v.to_str().ok()),
+        Some("https://allowed.example")
+    );
+}
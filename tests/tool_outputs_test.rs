@@ -0,0 +1,151 @@
+// 端插件测试 - requires_action 时返回待执行的 tool_calls，submit_tool_outputs 提交结果后继续对话
+mod common;
+
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+
+fn required_action() -> Value {
+    json!({
+        "type": "submit_tool_outputs",
+        "submit_tool_outputs": {
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"北京\"}"}
+            }]
+        }
+    })
+}
+
+async fn create_chat(Json(body): Json<Value>) -> Response {
+    if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+        let stream = format!(
+            "event: conversation.message.delta\ndata: {}\n\n\
+event: conversation.chat.requires_action\ndata: {}\n\n",
+            json!({"id": "msg_1", "chat_id": "chat_1", "conversation_id": "conv_1", "delta": {"content": "查询中"}}),
+            json!({"id": "chat_1", "conversation_id": "conv_1", "status": "requires_action", "required_action": required_action()}),
+        );
+        ([(CONTENT_TYPE, "text/event-stream")], stream).into_response()
+    } else {
+        Json(json!({
+            "code": 0,
+            "data": {
+                "id": "chat_1",
+                "conversation_id": "conv_1",
+                "status": "requires_action",
+                "required_action": required_action()
+            }
+        }))
+        .into_response()
+    }
+}
+
+async fn submit(Json(body): Json<Value>) -> Response {
+    if body.get("stream").and_then(|v| v.as_bool()) == Some(true) {
+        let events =
+            "event: conversation.message.delta\ndata: {\"delta\":{\"content\":\"北京晴\"}}\n\n\
+event: conversation.chat.completed\ndata: {\"id\":\"chat_1\",\"conversation_id\":\"conv_1\"}\n\n";
+        ([(CONTENT_TYPE, "text/event-stream")], events).into_response()
+    } else {
+        Json(json!({
+            "code": 0,
+            "data": {"id": "chat_1", "conversation_id": "conv_1", "status": "completed"}
+        }))
+        .into_response()
+    }
+}
+
+async fn spawn_mock() -> (CozeTools, MockCoze) {
+    let app = Router::new()
+        .route("/v3/chat", post(create_chat))
+        .route("/v3/chat/submit_tool_outputs", post(submit));
+    let mock = MockCoze::start(app).await;
+    (mock.tools(), mock)
+}
+
+#[tokio::test]
+async fn test_chat_surfaces_pending_tool_calls() {
+    let (tools, _) = spawn_mock().await;
+    let args = json!({"bot_id": "bot", "message": "北京天气"});
+
+    let result = tools.chat(Some(args.clone())).await.unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["status"], "requires_action");
+    assert_eq!(structured["chat_id"], "chat_1");
+    assert_eq!(structured["tool_calls"][0]["id"], "call_1");
+    assert_eq!(
+        structured["tool_calls"][0]["function"]["name"],
+        "get_weather"
+    );
+
+    let result = tools.chat_stream(Some(args)).await.unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["status"], "requires_action");
+    assert_eq!(structured["conversation_id"], "conv_1");
+    assert_eq!(structured["chat_id"], "chat_1");
+    assert_eq!(structured["tool_calls"][0]["id"], "call_1");
+    assert_eq!(structured["content"], "查询中");
+}
+
+#[tokio::test]
+async fn test_submit_tool_outputs() {
+    let (tools, mock) = spawn_mock().await;
+
+    let result = tools
+        .submit_tool_outputs(Some(json!({
+            "conversation_id": "conv_1",
+            "chat_id": "chat_1",
+            "tool_outputs": [{"tool_call_id": "call_1", "output": {"weather": "晴"}}]
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["status"], "completed");
+
+    let result = tools
+        .submit_tool_outputs(Some(json!({
+            "conversation_id": "conv_1",
+            "chat_id": "chat_1",
+            "tool_outputs": [{"tool_call_id": "call_1", "output": "晴"}],
+            "stream": true
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["content"], "北京晴");
+
+    let submitted = mock.requests_to("/v3/chat/submit_tool_outputs");
+    assert_eq!(submitted.len(), 2);
+    let query = &submitted[0].query;
+    assert_eq!(query["conversation_id"], "conv_1");
+    assert_eq!(query["chat_id"], "chat_1");
+    assert_eq!(
+        submitted[0].json(),
+        json!({
+            "tool_outputs": [{"tool_call_id": "call_1", "output": "{\"weather\":\"晴\"}"}],
+            "stream": false
+        })
+    );
+    assert_eq!(submitted[1].json()["stream"], true);
+}
+
+#[tokio::test]
+async fn test_submit_tool_outputs_requires_outputs() {
+    let (tools, mock) = spawn_mock().await;
+    for args in [
+        json!({"conversation_id": "conv_1", "chat_id": "chat_1"}),
+        json!({"conversation_id": "conv_1", "chat_id": "chat_1", "tool_outputs": []}),
+        json!({"conversation_id": "conv_1", "chat_id": "chat_1", "tool_outputs": [{"output": "x"}]}),
+        json!({"chat_id": "chat_1", "tool_outputs": [{"tool_call_id": "call_1", "output": "x"}]}),
+    ] {
+        assert!(tools.submit_tool_outputs(Some(args)).await.is_err());
+    }
+    assert!(mock.requests().is_empty());
+}
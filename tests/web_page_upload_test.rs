@@ -0,0 +1,178 @@
+// 在线网页上传测试 - web_url 与自动更新规则，经本地模拟端点验证请求格式
+mod common;
+
+use axum::routing::post;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::knowledge_models::{
+    ChunkStrategyCn, DocumentBaseCn, DocumentUpdateRule, KnowledgeDocumentUploadRequestCn,
+    KnowledgeDocumentUploadResponseCn, SourceInfo,
+};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+async fn reply(Json(body): Json<Value>) -> Json<Value> {
+    let name = body["document_bases"][0]["name"].clone();
+    Json(json!({
+        "code": 0,
+        "msg": "",
+        "document_infos": [{"document_id": "doc_web", "name": name, "source_type": 1}]
+    }))
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new().route("/open_api/knowledge/document/create", post(reply));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[test]
+fn test_web_page_request_shape() {
+    let request = KnowledgeDocumentUploadRequestCn {
+        dataset_id: "ds_1".to_string(),
+        document_bases: vec![DocumentBaseCn {
+            name: "文档站".to_string(),
+            source_info: SourceInfo::web_page("https://docs.example.com".to_string()),
+            caption: None,
+            update_rule: Some(DocumentUpdateRule::every_hours(24)),
+        }],
+        chunk_strategy: ChunkStrategyCn::text("\n\n".to_string(), 800, 0),
+        format_type: 0,
+    };
+    let value = serde_json::to_value(request.sanitized()).unwrap();
+    assert_eq!(
+        value["document_bases"][0],
+        json!({
+            "name": "文档站",
+            "source_info": {"web_url": "https://docs.example.com", "document_source": 1},
+            "update_rule": {"update_type": 1, "update_interval": 24}
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_upload_web_page_tool() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "web_url": "https://docs.example.com/guide",
+            "document_name": "使用指南",
+            "update_interval": 48
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["web_url"], "https://docs.example.com/guide");
+    assert_eq!(structured["file_size"], Value::Null);
+    assert_eq!(
+        structured["update_rule"],
+        json!({"update_type": 1, "update_interval": 48})
+    );
+    assert!(format!("{:?}", result.content).contains("每 48 小时"));
+
+    // 未指定名称时以地址命名；auto_update=true 默认每 24 小时
+    tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "web_url": "https://docs.example.com/faq",
+            "auto_update": true
+        })))
+        .await
+        .unwrap();
+    // 不设置更新规则时不发送 update_rule
+    tools
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "web_url": "https://docs.example.com/about"
+        })))
+        .await
+        .unwrap();
+
+    let requests = mock.bodies();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[0]["document_bases"][0],
+        json!({
+            "name": "使用指南",
+            "source_info": {"web_url": "https://docs.example.com/guide", "document_source": 1},
+            "update_rule": {"update_type": 1, "update_interval": 48}
+        })
+    );
+    assert_eq!(
+        requests[1]["document_bases"][0]["name"],
+        "https://docs.example.com/faq"
+    );
+    assert_eq!(
+        requests[1]["document_bases"][0]["update_rule"],
+        json!({"update_type": 1, "update_interval": 24})
+    );
+    assert!(requests[2]["document_bases"][0]
+        .get("update_rule")
+        .is_none());
+    assert_eq!(requests[2]["format_type"], 0);
+}
+
+#[tokio::test]
+async fn test_upload_web_page_validation() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    for bad in [
+        json!({"dataset_id": "ds_1"}),
+        json!({"dataset_id": "ds_1", "web_url": "https://a.com", "file_path": "/tmp/a.txt"}),
+        json!({"dataset_id": "ds_1", "web_url": "ftp://a.com/file"}),
+        json!({"dataset_id": "ds_1", "web_url": "not a url"}),
+        json!({"dataset_id": "ds_1", "web_url": "https://a.com", "update_interval": 12}),
+        json!({"dataset_id": "ds_1", "web_url": "https://a.com", "auto_update": false, "update_interval": 48}),
+        json!({"dataset_id": "ds_1", "web_url": "https://a.com", "format_type": 2}),
+        // 更新规则只适用于在线网页
+        json!({"dataset_id": "ds_1", "file_path": "/tmp/a.txt", "auto_update": true}),
+    ] {
+        assert!(tools
+            .upload_document_to_knowledge_base(Some(bad))
+            .await
+            .is_err());
+    }
+    assert!(mock.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_upload_response_without_document_id() {
+    let response: KnowledgeDocumentUploadResponseCn = serde_json::from_value(json!({
+        "code": 0,
+        "msg": "",
+        "document_infos": [{"name": "使用指南", "source_type": 1}]
+    }))
+    .unwrap();
+    assert_eq!(response.document_infos.unwrap()[0].document_id, "");
+
+    // 缺少 document_id 的上传仍算成功，也不会用空 ID 轮询处理进度
+    let app = Router::new().route(
+        "/open_api/knowledge/document/create",
+        post(|| async {
+            Json(json!({"code": 0, "msg": "", "document_infos": [{"name": "使用指南"}]}))
+        }),
+    );
+    let mock = MockCoze::start(app).await;
+    let result = mock
+        .tools()
+        .upload_document_to_knowledge_base(Some(json!({
+            "dataset_id": "ds_1",
+            "web_url": "https://docs.example.com/guide",
+            "wait_until_processed": true
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(result.structured_content.unwrap()["returned_count"], 1);
+    assert_eq!(
+        mock.paths(),
+        vec!["/open_api/knowledge/document/create".to_string()]
+    );
+}
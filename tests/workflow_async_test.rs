@@ -0,0 +1,190 @@
+// 工作流异步执行测试 - is_async 返回 execute_id，get_workflow_run_history 查询 / 等待执行结果
+mod common;
+
+use axum::extract::{Path, State};
+use axum::routing::get;
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::error::ApiError;
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+// 每个 execute_id 被查询的次数
+type Polls = Arc<Mutex<HashMap<String, u32>>>;
+
+async fn run_history(
+    State(polls): State<Polls>,
+    Path((workflow_id, execute_id)): Path<(String, String)>,
+) -> Json<Value> {
+    assert_eq!(workflow_id, "wf_1");
+    let count = {
+        let mut polls = polls.lock().unwrap();
+        let count = polls.entry(execute_id.clone()).or_default();
+        *count += 1;
+        *count
+    };
+    let record = match execute_id.as_str() {
+        // 第二次查询时完成
+        "exec_done" if count >= 2 => json!({
+            "execute_id": execute_id,
+            "execute_status": "Success",
+            "run_mode": 2,
+            "output": "{\"Output\":\"hello\"}",
+            "token": "42",
+            "create_time": 1718289297,
+            "update_time": "1718289300",
+            "debug_url": "https://www.coze.cn/debug"
+        }),
+        "exec_fail" => json!({
+            "execute_id": execute_id,
+            "execute_status": "Fail",
+            "error_code": "720701013",
+            "error_message": "node timeout"
+        }),
+        "missing" => return Json(json!({"code": 0, "data": []})),
+        _ => json!({"execute_id": execute_id, "execute_status": "Running", "output": ""}),
+    };
+    Json(json!({"code": 0, "msg": "", "data": [record]}))
+}
+
+async fn spawn_mock() -> (CozeTools, Arc<CozeApiClient>, Polls) {
+    let polls = Polls::default();
+    let app = Router::new()
+        .route(
+            "/v1/workflows/{workflow_id}/run_histories/{execute_id}",
+            get(run_history),
+        )
+        .with_state(polls.clone());
+    let mock = MockCoze::start(app).await;
+    (mock.tools(), mock.client(), polls)
+}
+
+#[tokio::test]
+async fn test_get_workflow_run_history_client() {
+    let (_, client, _) = spawn_mock().await;
+
+    let history = client
+        .get_workflow_run_history("wf_1", "exec_running")
+        .await
+        .unwrap();
+    assert!(history.is_running());
+
+    let history = client
+        .get_workflow_run_history("wf_1", "exec_fail")
+        .await
+        .unwrap();
+    assert_eq!(history.error_code, Some(720701013));
+
+    let err = client
+        .get_workflow_run_history("wf_1", "missing")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ApiError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn test_run_history_without_wait_returns_current_status() {
+    let (tools, _, polls) = spawn_mock().await;
+    let result = tools
+        .get_workflow_run_history(Some(
+            json!({"workflow_id": "wf_1", "execute_id": "exec_done"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["execute_status"], "Running");
+    assert_eq!(structured["timeout"], false);
+    assert_eq!(polls.lock().unwrap()["exec_done"], 1);
+
+    let result = tools
+        .get_workflow_run_history(Some(
+            json!({"workflow_id": "wf_1", "execute_id": "exec_fail"}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["code"], 720701013);
+    assert_eq!(structured["error"], "node timeout");
+
+    for args in [
+        json!({"workflow_id": "wf_1"}),
+        json!({"execute_id": "exec_done"}),
+    ] {
+        assert!(tools.get_workflow_run_history(Some(args)).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_run_history_waits_until_finished() {
+    let (tools, _, polls) = spawn_mock().await;
+    let result = tools
+        .get_workflow_run_history(Some(json!({
+            "workflow_id": "wf_1",
+            "execute_id": "exec_done",
+            "wait": true,
+            "poll_interval_secs": 1
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["execute_status"], "Success");
+    assert_eq!(structured["output"], json!({"Output": "hello"}));
+    assert_eq!(structured["history"]["token"], 42);
+    assert_eq!(polls.lock().unwrap()["exec_done"], 2);
+}
+
+#[tokio::test]
+async fn test_run_history_wait_deadline() {
+    let (tools, _, _) = spawn_mock().await;
+    let started = std::time::Instant::now();
+    let result = tools
+        .get_workflow_run_history(Some(json!({
+            "workflow_id": "wf_1",
+            "execute_id": "exec_running",
+            "wait": true,
+            "timeout_secs": 1,
+            "poll_interval_secs": 5
+        })))
+        .await
+        .unwrap();
+    // 等待不超过截止时间（轮询间隔被截短）
+    assert!(started.elapsed() < std::time::Duration::from_secs(3));
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["execute_status"], "Running");
+    assert_eq!(structured["timeout"], true);
+}
+
+#[tokio::test]
+async fn test_run_history_wait_cancelled() {
+    let (tools, _, _) = spawn_mock().await;
+    let cancel = CancellationToken::new();
+    let trigger = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        trigger.cancel();
+    });
+    let result = tools
+        .get_workflow_run_history_with_context(
+            Some(json!({
+                "workflow_id": "wf_1",
+                "execute_id": "exec_running",
+                "wait": true,
+                "timeout_secs": 30
+            })),
+            cancel,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["error"], "cancelled");
+    assert_eq!(structured["execute_id"], "exec_running");
+}
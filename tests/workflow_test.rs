@@ -0,0 +1,371 @@
+// 工作流测试 - 列表、同步执行、流式执行（含中断）与恢复，经本地模拟端点验证
+mod common;
+
+use axum::body::Body;
+use axum::extract::Query;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use common::MockCoze;
+use coze_mcp_server::api::endpoints::endpoint_group;
+use coze_mcp_server::api::workflow_models::{
+    ListWorkflowsRequest, ResumeWorkflowRequest, RunWorkflowRequest, WorkflowEventType,
+    WorkflowMode,
+};
+use coze_mcp_server::api::CozeApiClient;
+use coze_mcp_server::tools::coze_tools::CozeTools;
+use coze_mcp_server::utils::config::CozeConfig;
+use futures::StreamExt;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+async fn list(Query(query): Query<HashMap<String, String>>) -> Json<Value> {
+    assert_eq!(query["workspace_id"], "space");
+    Json(json!({
+        "code": 0,
+        "data": {
+            "items": [{
+                "workflow_id": "wf_1",
+                "workflow_name": "翻译",
+                "description": "中译英",
+                "creator": {"id": "u1", "name": "张三"},
+                "created_at": "1718289297",
+                "updated_at": 1718289297
+            }],
+            "has_more": query.get("workflow_mode").map(String::as_str) == Some("chatflow")
+        }
+    }))
+}
+
+async fn run(Json(body): Json<Value>) -> Json<Value> {
+    if body["workflow_id"] == "broken" {
+        return Json(json!({"code": 4200, "msg": "workflow not published"}));
+    }
+    if body["is_async"] == true {
+        return Json(
+            json!({"code": 0, "msg": "", "execute_id": "exec_1", "debug_url": "https://www.coze.cn/debug"}),
+        );
+    }
+    Json(json!({
+        "code": 0,
+        "msg": "",
+        "data": "{\"output\":\"hello\"}",
+        "debug_url": "https://www.coze.cn/debug",
+        "usage": {"input_count": 10, "output_count": 5, "token_count": 15}
+    }))
+}
+
+fn sse(events: &[(&str, Value)]) -> Response {
+    let body: String = events
+        .iter()
+        .enumerate()
+        .map(|(i, (name, data))| format!("id: {i}\nevent: {name}\ndata: {data}\n\n"))
+        .collect();
+    ([(CONTENT_TYPE, "text/event-stream")], body).into_response()
+}
+
+async fn stream_run(Json(body): Json<Value>) -> Response {
+    match body["workflow_id"].as_str() {
+        Some("ask") => sse(&[
+            ("PING", json!({})),
+            (
+                "Message",
+                json!({"content": "你想翻译成哪种语言？", "node_title": "问答", "node_seq_id": "0", "node_is_finish": true}),
+            ),
+            (
+                "Interrupt",
+                json!({"interrupt_data": {"event_id": "evt_1/1", "type": 2}, "node_title": "问答"}),
+            ),
+        ]),
+        Some("fail") => sse(&[
+            ("Message", json!({"content": "开始", "node_title": "开始"})),
+            (
+                "Error",
+                json!({"error_code": 720701013, "error_message": "node timeout"}),
+            ),
+        ]),
+        _ => sse(&[
+            (
+                "Message",
+                json!({"content": "Hello", "node_title": "结束", "node_is_finish": false}),
+            ),
+            (
+                "Message",
+                json!({"content": " world", "node_title": "结束", "node_is_finish": true, "usage": {"token_count": 7}}),
+            ),
+            ("Done", json!({"debug_url": "https://www.coze.cn/debug"})),
+        ]),
+    }
+}
+
+async fn stream_resume() -> Response {
+    sse(&[
+        (
+            "Message",
+            json!({"content": "Hello", "node_title": "结束", "node_is_finish": true}),
+        ),
+        ("Done", json!({})),
+    ])
+}
+
+async fn spawn_mock() -> (Arc<CozeApiClient>, MockCoze) {
+    let app = Router::new()
+        .route("/v1/workflows", get(list))
+        .route("/v1/workflow/run", post(run))
+        .route("/v1/workflow/stream_run", post(stream_run))
+        .route("/v1/workflow/stream_resume", post(stream_resume));
+    let mock = MockCoze::start(app).await;
+    (mock.client(), mock)
+}
+
+#[tokio::test]
+async fn test_workflow_client_methods() {
+    let (client, mock) = spawn_mock().await;
+
+    let data = client
+        .list_workflows(
+            &ListWorkflowsRequest::new("space".to_string())
+                .with_workflow_mode(WorkflowMode::Chatflow),
+        )
+        .await
+        .unwrap();
+    assert_eq!(data.items[0].workflow_name, "翻译");
+    assert_eq!(data.items[0].created_at, Some(1718289297));
+    assert!(data.has_more);
+
+    let request = RunWorkflowRequest::new("wf_1".to_string())
+        .with_parameters(HashMap::from([("text".to_string(), json!("你好"))]));
+    let response = client.run_workflow(&request).await.unwrap();
+    assert_eq!(response.data.as_deref(), Some("{\"output\":\"hello\"}"));
+    assert_eq!(response.usage.unwrap().token_count, Some(15));
+
+    let response = client
+        .run_workflow(&request.clone().with_async(true))
+        .await
+        .unwrap();
+    assert_eq!(response.execute_id.as_deref(), Some("exec_1"));
+
+    let events: Vec<_> = client
+        .stream_workflow(&request)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].event, WorkflowEventType::Message);
+    assert_eq!(events[0].id.as_deref(), Some("0"));
+    assert_eq!(events[1].content.as_deref(), Some(" world"));
+    assert_eq!(events[2].event, WorkflowEventType::Done);
+
+    // PING 不会出现在事件流中
+    let events: Vec<_> = client
+        .stream_workflow(&RunWorkflowRequest::new("ask".to_string()))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(events.len(), 2);
+    let interrupt = events[1].as_ref().unwrap().interrupt_data.clone().unwrap();
+    assert_eq!(interrupt.event_id, "evt_1/1");
+    assert_eq!(interrupt.interrupt_type, 2);
+
+    let events: Vec<_> = client
+        .stream_workflow(&RunWorkflowRequest::new("fail".to_string()))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    let err = events[1].as_ref().unwrap_err();
+    assert_eq!(err.code(), Some(720701013));
+
+    let resume = ResumeWorkflowRequest::new(
+        "ask".to_string(),
+        "evt_1/1".to_string(),
+        "英语".to_string(),
+        2,
+    );
+    let events: Vec<_> = client
+        .resume_workflow(&resume)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(events.len(), 2);
+
+    // 第 0 个请求是工作流列表
+    let bodies = mock.bodies();
+    assert_eq!(
+        bodies[1],
+        json!({"workflow_id": "wf_1", "parameters": {"text": "你好"}})
+    );
+    assert_eq!(bodies[2]["is_async"], true);
+    assert_eq!(
+        bodies[6],
+        json!({"workflow_id": "ask", "event_id": "evt_1/1", "resume_data": "英语", "interrupt_type": 2})
+    );
+}
+
+#[tokio::test]
+async fn test_run_workflow_tool() {
+    let (client, _) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .list_workflows(Some(json!({"workflow_mode": "workflow"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(
+        result.structured_content.unwrap()["items"][0]["workflow_id"],
+        "wf_1"
+    );
+
+    let result = tools
+        .run_workflow(Some(
+            json!({"workflow_id": "wf_1", "parameters": {"text": "你好"}}),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["output"], json!({"output": "hello"}));
+    assert_eq!(structured["usage"]["token_count"], 15);
+
+    let result = tools
+        .run_workflow(Some(json!({"workflow_id": "broken"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.structured_content.unwrap()["code"], 4200);
+
+    for args in [
+        json!({}),
+        json!({"workflow_id": "wf_1", "parameters": "text"}),
+    ] {
+        assert!(tools.run_workflow(Some(args)).await.is_err());
+    }
+    assert!(tools
+        .list_workflows(Some(json!({"workflow_mode": "agent"})))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_workflow_stream_interrupt_and_resume_tools() {
+    let (client, mock) = spawn_mock().await;
+    let tools = CozeTools::new(client, "space".to_string());
+
+    let result = tools
+        .run_workflow_stream(Some(json!({"workflow_id": "wf_1"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["status"], "completed");
+    assert_eq!(structured["content"], "Hello world");
+    assert_eq!(structured["usage"]["token_count"], 7);
+    assert_eq!(structured["debug_url"], "https://www.coze.cn/debug");
+
+    let result = tools
+        .run_workflow_stream(Some(json!({"workflow_id": "ask"})))
+        .await
+        .unwrap();
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["status"], "interrupted");
+    assert_eq!(structured["event_id"], "evt_1/1");
+    assert_eq!(structured["interrupt_type"], 2);
+    assert_eq!(structured["content"], "你想翻译成哪种语言？");
+
+    let result = tools
+        .resume_workflow(Some(json!({
+            "workflow_id": "ask",
+            "event_id": structured["event_id"],
+            "resume_data": "英语",
+            "interrupt_type": structured["interrupt_type"]
+        })))
+        .await
+        .unwrap();
+    assert_eq!(result.structured_content.unwrap()["status"], "completed");
+
+    let result = tools
+        .run_workflow_stream(Some(json!({"workflow_id": "fail"})))
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+    let structured = result.structured_content.unwrap();
+    assert_eq!(structured["code"], 720701013);
+    assert_eq!(structured["messages"][0]["content"], "开始");
+
+    assert!(tools
+        .resume_workflow(Some(json!({"workflow_id": "ask", "event_id": "evt_1/1"})))
+        .await
+        .is_err());
+    // 流式接口不提交 is_async
+    assert!(mock.bodies().iter().all(|b| b.get("is_async").is_none()));
+}
+
+// 每 300ms 推送一个事件，总时长超过客户端的 1 秒超时
+async fn slow_stream() -> Response {
+    let events = futures::stream::iter(0..6).then(|i| async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let name = if i == 5 { "Done" } else { "Message" };
+        Ok::<_, std::convert::Infallible>(format!(
+            "id: {i}\nevent: {name}\ndata: {}\n\n",
+            json!({"content": format!("part {i}"), "node_title": "结束"})
+        ))
+    });
+    (
+        [(CONTENT_TYPE, "text/event-stream")],
+        Body::from_stream(events),
+    )
+        .into_response()
+}
+
+async fn slow_run() -> Json<Value> {
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    Json(json!({"code": 0, "msg": "", "data": "{}"}))
+}
+
+#[tokio::test]
+async fn test_timeout_spares_long_streams() {
+    let app = Router::new()
+        .route("/v1/workflow/stream_run", post(slow_stream))
+        .route("/v1/workflow/run", post(slow_run));
+    let mock = MockCoze::start(app).await;
+    let client = CozeApiClient::from_config(&CozeConfig {
+        base_url: mock.base_url.clone(),
+        api_token: "pat_test".to_string(),
+        timeout: 1,
+        retry_attempts: 0,
+        ..CozeConfig::default()
+    })
+    .unwrap();
+
+    // 流只受读取空闲超时限制，持续时间超过 timeout 也能完整读完
+    let started = Instant::now();
+    let events: Vec<_> = client
+        .stream_workflow(&RunWorkflowRequest::new("wf_1".to_string()))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert!(started.elapsed() > Duration::from_secs(1));
+    assert_eq!(events.len(), 6);
+    assert!(events.iter().all(Result::is_ok));
+
+    // 非流式请求仍受总超时限制
+    assert!(client
+        .run_workflow(&RunWorkflowRequest::new("wf_1".to_string()))
+        .await
+        .is_err());
+}
+
+#[test]
+fn test_workflows_endpoint_group() {
+    assert_eq!(endpoint_group("/v1/workflows"), "workflows");
+    assert_eq!(endpoint_group("/v1/workflow/stream_run"), "workflows");
+}